@@ -15,6 +15,17 @@ export const DEVNET_URL = process.env.SOLANA_RPC_URL || "https://api.devnet.sola
 export const PROGRAM_ID = new PublicKey(process.env.PROGRAM_ID || "EcmMaHYxoz3VhNg8M8TBFVAc7Xy4VHW6nBBWhPyE8HrP");
 export const TOKEN_DECIMALS = 9;
 
+// === Migration AMMs (must be on the program's allowlists) ===
+export const AMM_PROGRAMS = {
+  raydiumCpmm:   new PublicKey(process.env.RAYDIUM_CPMM_PROGRAM_ID   || "DRaycpLY18LhpbydsBWbVJtxpNv9oXPgjRSfpF2bWpYb"),
+  orcaWhirlpool: new PublicKey(process.env.ORCA_WHIRLPOOL_PROGRAM_ID || "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc"),
+  meteoraDamm:   new PublicKey(process.env.METEORA_DAMM_PROGRAM_ID   || "cpamdpZCGKUy5JxQXB4dcpGPiikHawvSWAd6mEn1sGG"),
+};
+export const DEFAULT_AMM = "raydiumCpmm";
+
+// === Metaplex Token Metadata (launch_token CPIs it) ===
+export const TOKEN_METADATA_PROGRAM_ID = new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
 // === Files ===
 export const tokensFile = path.join(process.cwd(), "data", "tokens.json");
 export const holdingsFile = path.join(process.cwd(), "data", "holdings.json");
@@ -5,8 +5,17 @@ import * as anchor from "@coral-xyz/anchor";
 
 export async function buildBuyTxBase64({ walletAddress, mintPubkey, amountLamports }) {
   const program = getProgram(walletAddress);
-  const { mintPk, poolPDA, solVault, dexConfigPDA, poolTokenAccount, userTokenAccount, userPk } =
-    await deriveForMint(mintPubkey, walletAddress);
+  const {
+    mintPk, poolPDA, solVault, dexConfigPDA, poolTokenAccount, userTokenAccount, userPk,
+    vestingEscrow, vestingTokenAccount, tierParticipation, treasuryPDA, treasuryTokenAccount,
+  } = await deriveForMint(mintPubkey, walletAddress);
+
+  // Side accounts only matter for pools that vest early-tier buys or pay tier bonuses
+  const pool = await program.account.liquidityPool.fetch(poolPDA);
+  const vests = Number(pool.vestedTiers) > 0;
+  const bonusOn = Number(pool.bonusPerTier) > 0;
+  const proRata = bonusOn && !!pool.bonusMode?.proRata;
+  const crossing = bonusOn && !proRata;
 
   const buyIx = await program.methods
     .buy(new BN(amountLamports))
@@ -17,6 +26,11 @@ export async function buildBuyTxBase64({ walletAddress, mintPubkey, amountLampor
       poolTokenAccount,
       userTokenAccount,
       poolSolVault: solVault,
+      vestingEscrow: vests ? vestingEscrow : null,
+      vestingTokenAccount: vests ? vestingTokenAccount : null,
+      tierParticipation: proRata ? tierParticipation : null,
+      treasuryPda: crossing ? treasuryPDA : null,
+      treasuryTokenAccount: crossing ? treasuryTokenAccount : null,
       user: userPk,
       rent: anchor.web3.SYSVAR_RENT_PUBKEY,
       tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
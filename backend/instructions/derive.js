@@ -13,5 +13,15 @@ export async function deriveForMint(mint, userPubkey) {
   const userPk = typeof userPubkey === "string" ? new PublicKey(userPubkey) : userPubkey;
   const userTokenAccount = await anchor.utils.token.associatedAddress({ mint: mintPk, owner: userPk });
 
-  return { mintPk, poolPDA, solVault, solVaultBump, dexConfigPDA, poolTokenAccount, userTokenAccount, userPk };
+  const [vestingEscrow] = PublicKey.findProgramAddressSync([Buffer.from("vesting_escrow"), poolPDA.toBuffer(), userPk.toBuffer()], PROGRAM_ID);
+  const vestingTokenAccount = await anchor.utils.token.associatedAddress({ mint: mintPk, owner: vestingEscrow });
+
+  const [tierParticipation] = PublicKey.findProgramAddressSync([Buffer.from("tier_participation"), poolPDA.toBuffer(), userPk.toBuffer()], PROGRAM_ID);
+  const [treasuryPDA] = PublicKey.findProgramAddressSync([Buffer.from("treasury"), mintPk.toBuffer()], PROGRAM_ID);
+  const treasuryTokenAccount = await anchor.utils.token.associatedAddress({ mint: mintPk, owner: treasuryPDA });
+
+  return {
+    mintPk, poolPDA, solVault, solVaultBump, dexConfigPDA, poolTokenAccount, userTokenAccount, userPk,
+    vestingEscrow, vestingTokenAccount, tierParticipation, treasuryPDA, treasuryTokenAccount,
+  };
 }
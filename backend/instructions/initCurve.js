@@ -3,6 +3,10 @@ import fs from "fs";
 import { PublicKey } from "@solana/web3.js";
 import { connection, idl, PROGRAM_ID } from "../config/index.js";
 
+// CurveConfiguration (fees only) as deployed before GlobalConfig
+const LEGACY_CONFIG_SIZE = 48;
+const BPF_LOADER_UPGRADEABLE = new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111");
+
 export async function tryInitializeCurveConfig() {
   try {
     const adminKeypair = anchor.web3.Keypair.fromSecretKey(
@@ -13,6 +17,22 @@ export async function tryInitializeCurveConfig() {
 
     const [dexConfigurationPDA] = PublicKey.findProgramAddressSync([Buffer.from("CurveConfiguration")], PROGRAM_ID);
     const existing = await connection.getAccountInfo(dexConfigurationPDA);
+    if (existing && existing.data.length === LEGACY_CONFIG_SIZE) {
+      // Pre-GlobalConfig deployment: rewrite in place (signer must be the upgrade authority)
+      const [programData] = PublicKey.findProgramAddressSync([PROGRAM_ID.toBuffer()], BPF_LOADER_UPGRADEABLE);
+      const tx = await program.methods
+        .upgradeConfig()
+        .accounts({
+          dexConfigurationAccount: dexConfigurationPDA,
+          program: PROGRAM_ID,
+          programData,
+          authority: adminKeypair.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      console.log("✅ Curve config upgraded to GlobalConfig:", tx);
+      return;
+    }
     if (existing) { console.log("✅ Curve config already initialized."); return; }
 
     const tx = await program.methods
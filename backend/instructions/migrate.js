@@ -1,45 +1,49 @@
-// instructions/migrate.js — CPMM (devnet), startMigration-first, tidy logs
+// instructions/migrate.js — startMigration + on-chain CPI finalize into the pool's AMM (devnet), tidy logs
 import * as anchor from "@coral-xyz/anchor";
 import {
   PublicKey,
   Keypair,
   SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+  ComputeBudgetProgram,
   VersionedTransaction,
   TransactionMessage,
   LAMPORTS_PER_SOL,
 } from "@solana/web3.js";
 import {
-  createAssociatedTokenAccountInstruction,
-  createSyncNativeInstruction,
   NATIVE_MINT as WSOL_MINT,
   TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddressSync,
 } from "@solana/spl-token";
-import BN from "bn.js";
 import Decimal from "decimal.js";
 
 import { connection, PROGRAM_ID, getProgram as getCurveProgram } from "../config/index.js";
 import { loadHoldings, updateRaydiumMeta } from "../lib/files.js";
 import { resyncMintFromChain } from "../lib/chain.js";
 
-import { Raydium, TxVersion } from "@raydium-io/raydium-sdk-v2";
 import { broadcastHoldings } from "../lib/sse.js";
 
-// Show Raydium SDK version in logs
-import { createRequire } from "module";
-const require = createRequire(import.meta.url);
-let RAYDIUM_SDK_VERSION = "unknown";
-try {
-  RAYDIUM_SDK_VERSION =
-    require("@raydium-io/raydium-sdk-v2/package.json")?.version ?? "unknown";
-} catch {}
-
 /* ---------------------------------- CONSTS --------------------------------- */
 
-// Raydium CPMM program (devnet)
-const CPMM_PROGRAM_ID = new PublicKey("DRaycpLY18LhpbydsBWbVJtxpNv9oXPgjRSfpF2bWpYb");
-
 // Devnet create-pool fee TOKEN ACCOUNT (Raydium requires this for SOL pairs)
-const DEVNET_CPMM_CREATE_FEE_TA = "3oE58BKVt8KuYkGxx8zBojugnymWmBiyafWgMrnb6eYy";
+const DEVNET_CPMM_CREATE_FEE_TA = new PublicKey("3oE58BKVt8KuYkGxx8zBojugnymWmBiyafWgMrnb6eYy");
+
+// CPMM fee tier used for graduated pools (amm_config index)
+const CPMM_AMM_CONFIG_INDEX = 0;
+
+// Orca: devnet WhirlpoolsConfig + tick spacing of the fee tier graduated pools use
+const ORCA_WHIRLPOOLS_CONFIG = new PublicKey(process.env.ORCA_WHIRLPOOLS_CONFIG || "FcrweFY1G9HJAHG5inkGB6pKg1HZ6x9UC2WioAfWrGkR");
+const ORCA_TICK_SPACING = 64;
+const ORCA_MAX_TICK = 443636;
+const ORCA_TICK_ARRAY_SIZE = 88;
+
+// Meteora DAMM v2: static config graduated pools use (no default on devnet)
+const METEORA_DAMM_CONFIG = process.env.METEORA_DAMM_CONFIG ? new PublicKey(process.env.METEORA_DAMM_CONFIG) : null;
+
+// finalizeMigration creates ATAs + the AMM pool (Orca: + tick arrays + position) in one ix
+const FINALIZE_COMPUTE_UNITS = 600_000;
 
 /* --------------------------------- LOGGING --------------------------------- */
 
@@ -64,7 +68,8 @@ const sol = (lamports) => {
   try { return new Decimal(lamports.toString()).div(LAMPORTS_PER_SOL).toString(); }
   catch { return String(lamports); }
 };
-const phaseName = (p) => (p?.migrating ? "Migrating" : p?.raydiumLive ? "RaydiumLive" : "Active");
+const phaseName = (p) => (p?.migrating ? "Migrating" : p?.graduated ? "Graduated" : p?.expired ? "Expired" : "Active");
+const ammKindName = (k) => Object.keys(k || {})[0] || "raydiumCpmm";
 const toBigIntLike = (x) => {
   if (x == null) return 0n;
   if (typeof x === "bigint") return x;
@@ -76,16 +81,6 @@ const toBigIntLike = (x) => {
 
 /* --------------------------------- HELPERS --------------------------------- */
 
-function makeNodeWallet(kp) {
-  return {
-    publicKey: kp.publicKey,
-    payer: kp,
-    feePayer: kp.publicKey,
-    async signTransaction(tx) { tx.partialSign(kp); return tx; },
-    async signAllTransactions(txs) { txs.forEach((t) => t.partialSign(kp)); return txs; },
-  };
-}
-
 async function sendAndConfirmV0(tx, label = "tx") {
   try {
     step(`Sending transaction [${label}]`, {
@@ -161,206 +156,161 @@ async function fetchMintDecimals(mintPk) {
   return d;
 }
 
-async function ensureAtaIx(owner, mint) {
-  const ata = await anchor.utils.token.associatedAddress({ mint, owner });
-  const info = await connection.getAccountInfo(ata);
-  return info ? { ata, ix: null } : { ata, ix: createAssociatedTokenAccountInstruction(owner, ata, owner, mint) };
-}
+/* ------------------------------ AMM ADDRESSES ------------------------------ */
 
-async function ensureAtaExistsNow({ owner, mint, signer }) {
-  const ata = await anchor.utils.token.associatedAddress({ mint, owner });
-  const info = await connection.getAccountInfo(ata);
-  if (!info) {
-    const ix = createAssociatedTokenAccountInstruction(owner, ata, owner, mint);
-    const { blockhash } = await connection.getLatestBlockhash();
-    const msg = new TransactionMessage({ payerKey: owner, recentBlockhash: blockhash, instructions: [ix] })
-      .compileToV0Message();
-    const tx = new VersionedTransaction(msg);
-    tx.sign([signer]);
-    const sig = await connection.sendTransaction(tx, { maxRetries: 3 });
-    await connection.confirmTransaction(sig, "confirmed");
-    step("Created ATA", { owner: owner.toBase58(), mint: mint.toBase58(), ata: ata.toBase58(), sig });
-  }
-  return ata;
-}
+// Accounts finalizeMigration needs for the pool's AMM: the named AMM accounts,
+// the adapter's remaining_accounts (order per utils/amm/<kind>.rs) and any
+// fresh keypairs that must co-sign. Token side and wSOL side seed from the
+// migration escrow's ATAs; the escrow PDA is the AMM creator.
+const meta = (pubkey, isWritable = false, isSigner = false) => ({ pubkey, isWritable, isSigner });
 
-/* -------------------------- RAYDIUM: LOAD + CONFIG ------------------------- */
+function lpEscrowFor(mintPk, lpMint, lpTokenProgram) {
+  const [lpEscrow] = PublicKey.findProgramAddressSync([Buffer.from("lp_escrow"), mintPk.toBuffer()], PROGRAM_ID);
+  const lpEscrowTokenAccount = getAssociatedTokenAddressSync(lpMint, lpEscrow, true, lpTokenProgram);
+  return { lpEscrow, lpEscrowTokenAccount };
+}
 
-async function ensureCpmmConfigsLoaded(r) {
-  if (Array.isArray(r.apiData?.cpmmConfigs) && r.apiData.cpmmConfigs.length) return r.apiData.cpmmConfigs;
+// Mirrors cp-swap's PDA seeds; token_0 must sort before token_1.
+function deriveCpmm(programId, mintPk, escrow) {
+  const [mint0, mint1] =
+    Buffer.compare(mintPk.toBuffer(), WSOL_MINT.toBuffer()) < 0 ? [mintPk, WSOL_MINT] : [WSOL_MINT, mintPk];
 
-  try {
-    if (typeof r.api?.getCpmmConfigs === "function") {
-      const list = await r.api.getCpmmConfigs();
-      if (Array.isArray(list) && list.length) {
-        r.apiData = { ...(r.apiData || {}), cpmmConfigs: list };
-        return list;
-      }
-    }
-  } catch {}
+  const idx = Buffer.alloc(2);
+  idx.writeUInt16BE(CPMM_AMM_CONFIG_INDEX);
+  const pda = (seeds) => PublicKey.findProgramAddressSync(seeds, programId)[0];
 
-  const attempts = ["updateCpmmConfigs", "fetchCpmmConfigs", "loadCpmmConfigs", "refresh"];
-  for (const fn of attempts) {
-    try {
-      if (typeof r.api?.[fn] === "function") {
-        const res = await r.api[fn]();
-        const list = res?.cpmmConfigs ?? res?.data?.cpmmConfigs ?? r.apiData?.cpmmConfigs;
-        if (Array.isArray(list) && list.length) {
-          r.apiData = { ...(r.apiData || {}), cpmmConfigs: list };
-          return list;
-        }
-      }
-    } catch {}
-  }
+  const ammConfig     = pda([Buffer.from("amm_config"), idx]);
+  const ammAuthority  = pda([Buffer.from("vault_and_lp_mint_auth_seed")]);
+  const poolState     = pda([Buffer.from("pool"), ammConfig.toBuffer(), mint0.toBuffer(), mint1.toBuffer()]);
+  const lpMint        = pda([Buffer.from("pool_lp_mint"), poolState.toBuffer()]);
+  const vault0        = pda([Buffer.from("pool_vault"), poolState.toBuffer(), mint0.toBuffer()]);
+  const vault1        = pda([Buffer.from("pool_vault"), poolState.toBuffer(), mint1.toBuffer()]);
+  const observation   = pda([Buffer.from("observation"), poolState.toBuffer()]);
 
-  throw new Error("Raydium CPMM fee configs unavailable (devnet).");
+  return {
+    ammPool: poolState,
+    lpMint,
+    lpTokenProgram: TOKEN_PROGRAM_ID,
+    escrowLpAccount: getAssociatedTokenAddressSync(lpMint, escrow, true),
+    remaining: [
+      meta(ammConfig),
+      meta(ammAuthority),
+      meta(vault0, true),
+      meta(vault1, true),
+      meta(DEVNET_CPMM_CREATE_FEE_TA, true),
+      meta(observation, true),
+    ],
+    signers: [],
+    baseVault: mint0.equals(mintPk) ? vault0 : vault1,
+  };
 }
 
-async function loadRaydiumSafe({ connection, ownerKeypair }) {
-  // console.log("→ Raydium SDK v2 version:", RAYDIUM_SDK_VERSION);
-  const ownerWallet = makeNodeWallet(ownerKeypair);
+// Full-range Whirlpool position; vaults and the position mint are fresh keypairs.
+function deriveWhirlpool(programId, mintPk, escrow) {
+  const [mintA, mintB] =
+    Buffer.compare(mintPk.toBuffer(), WSOL_MINT.toBuffer()) < 0 ? [mintPk, WSOL_MINT] : [WSOL_MINT, mintPk];
+  const pda = (seeds) => PublicKey.findProgramAddressSync(seeds, programId)[0];
 
-  const attempts = [
-    { name: "devnet+disableLoadToken", opts: { connection, owner: ownerWallet, cluster: "devnet", disableLoadToken: true } },
-    { name: "inferCluster+disableLoadToken", opts: { connection, owner: ownerWallet, disableLoadToken: true } },
-  ];
+  const ts = Buffer.alloc(2);
+  ts.writeUInt16LE(ORCA_TICK_SPACING);
+  const feeTier   = pda([Buffer.from("fee_tier"), ORCA_WHIRLPOOLS_CONFIG.toBuffer(), ts]);
+  const whirlpool = pda([Buffer.from("whirlpool"), ORCA_WHIRLPOOLS_CONFIG.toBuffer(), mintA.toBuffer(), mintB.toBuffer(), ts]);
 
-  let lastErr;
-  for (const a of attempts) {
-    try {
-      step("Raydium.load", { opts: a.name });
-      const r = await Raydium.load(a.opts);
+  const upper = Math.floor(ORCA_MAX_TICK / ORCA_TICK_SPACING) * ORCA_TICK_SPACING;
+  const span = ORCA_TICK_SPACING * ORCA_TICK_ARRAY_SIZE;
+  const tickArray = (tick) =>
+    pda([Buffer.from("tick_array"), whirlpool.toBuffer(), Buffer.from(String(Math.floor(tick / span) * span))]);
 
-      r.wallet = ownerWallet;
-      r._owner = ownerWallet;
-      if (typeof r.setWallet === "function") r.setWallet(ownerWallet);
+  const vaultA = Keypair.generate();
+  const vaultB = Keypair.generate();
+  const positionMint = Keypair.generate();
+  const position = pda([Buffer.from("position"), positionMint.publicKey.toBuffer()]);
 
-      await ensureCpmmConfigsLoaded(r);
-      ok("Raydium client ready");
-      return r;
-    } catch (e) {
-      lastErr = e;
-      console.error(`❌ Raydium.load failed (${a.name}):`, e?.message || e);
-    }
-  }
-  const err = new Error(`${lastErr?.message || lastErr}\nRaydium.load() could not initialize (devnet).`);
-  err.stack = lastErr?.stack || err.stack;
-  throw err;
-}
-
-/* -------------------------- RAYDIUM: CPMM BUILDER -------------------------- */
-
-async function buildRaydiumCpmmIxs({
-  mintPk,
-  decimals,
-  signerKp,
-  tokenAta,
-  wsolAta,
-  tokenAmountBaseUnits,
-  wsolLamports,
-  plannedBaseTopup = 0n,
-}) {
-  banner("Raydium: init client");
-  const raydium = await loadRaydiumSafe({ connection, ownerKeypair: signerKp });
-
-  const cpmmConfigs = await ensureCpmmConfigsLoaded(raydium);
-  const feeConfig = cpmmConfigs.find((c) => c.id) ?? cpmmConfigs[0];
-  if (!feeConfig?.id || typeof feeConfig.index !== "number") throw new Error("Invalid CPMM feeConfig from API");
-
-  // Post-startMigration balances should satisfy inputs
-  const baseBalInfo = await connection.getTokenAccountBalance(tokenAta).catch(() => null);
-  const wsolBalInfo = await connection.getTokenAccountBalance(wsolAta).catch(() => null);
-
-  const baseHaveNow = BigInt(baseBalInfo?.value?.amount || "0");
-  const baseNeed = tokenAmountBaseUnits;
-  const baseHavePost = baseHaveNow + BigInt(plannedBaseTopup);
-  if (baseHavePost < baseNeed) {
-    throw new Error(
-      `Preflight: base-token after top-up still short. haveNow=${baseHaveNow} topup=${plannedBaseTopup} need=${baseNeed} (ATA ${fmtPk(tokenAta)})`
-    );
-  }
-  step("Balances (pre-builder)", {
-    baseHaveNow: baseHaveNow.toString(),
-    baseHavePost: baseHavePost.toString(),
-    baseNeed: baseNeed.toString(),
-    wsolHaveBefore: BigInt(wsolBalInfo?.value?.amount || "0").toString(),
-    wsolPlanned: BigInt(wsolLamports).toString(),
-  });
-
-  // Ensure ATAs exist (SDK may probe)
-  await ensureAtaExistsNow({ owner: signerKp.publicKey, mint: mintPk, signer: signerKp });
-  await ensureAtaExistsNow({ owner: signerKp.publicKey, mint: WSOL_MINT, signer: signerKp });
-
-  banner("Raydium: CPMM createPool");
-  const poolFeeAccountPk = new PublicKey(DEVNET_CPMM_CREATE_FEE_TA);
-  const params = {
-    programId: CPMM_PROGRAM_ID,
-    poolFeeAccount: poolFeeAccountPk, // create-pool fee token account (WSOL)
-    mintA: { address: mintPk, decimals, programId: TOKEN_PROGRAM_ID },
-    mintB: { address: WSOL_MINT, decimals: 9, programId: TOKEN_PROGRAM_ID },
-    mintAAmount: new BN(tokenAmountBaseUnits.toString()),
-    mintBAmount: new BN(wsolLamports.toString()),
-    startTime: new BN(0),
-    feeConfig, // SDK will wire the amm_config PDA
-    associatedOnly: true,
-    checkCreateATAOwner: true,
-    ownerInfo: { owner: makeNodeWallet(signerKp), feePayer: signerKp.publicKey, useSOLBalance: false },
-    feePayer: signerKp.publicKey,
-    txVersion: TxVersion.V0,
+  return {
+    ammPool: whirlpool,
+    lpMint: positionMint.publicKey,
+    lpTokenProgram: TOKEN_PROGRAM_ID,
+    escrowLpAccount: getAssociatedTokenAddressSync(positionMint.publicKey, escrow, true),
+    remaining: [
+      meta(ORCA_WHIRLPOOLS_CONFIG),
+      meta(feeTier),
+      meta(vaultA.publicKey, true, true),
+      meta(vaultB.publicKey, true, true),
+      meta(tickArray(-upper), true),
+      meta(tickArray(upper), true),
+      meta(position, true),
+    ],
+    signers: [vaultA, vaultB, positionMint],
+    baseVault: mintA.equals(mintPk) ? vaultA.publicKey : vaultB.publicKey,
   };
+}
 
-  step("createPool params (brief)", {
-    mintA: params.mintA.address.toBase58(),
-    mintB: params.mintB.address.toBase58(),
-    mintAAmount: params.mintAAmount.toString(),
-    mintBAmount: params.mintBAmount.toString(),
-    poolFeeAccount: params.poolFeeAccount.toBase58(),
-    ammConfigId: feeConfig.id,
-  });
-
-  const { builder: createPoolBuilder, extInfo, poolKeys } = await raydium.cpmm.createPool(params);
+// DAMM v2 pool; token A = our token, token B = wSOL. Position NFT is Token-2022.
+function deriveDamm(programId, mintPk) {
+  if (!METEORA_DAMM_CONFIG) throw new Error("METEORA_DAMM_CONFIG missing");
+  const pda = (seeds) => PublicKey.findProgramAddressSync(seeds, programId)[0];
+  const [hi, lo] =
+    Buffer.compare(mintPk.toBuffer(), WSOL_MINT.toBuffer()) > 0 ? [mintPk, WSOL_MINT] : [WSOL_MINT, mintPk];
 
-  const vaultA =
-    poolKeys?.vaultA ??
-    extInfo?.address?.vaultA ??
-    createPoolBuilder?.extInfo?.vaultA ?? null;
+  const pool           = pda([Buffer.from("pool"), METEORA_DAMM_CONFIG.toBuffer(), hi.toBuffer(), lo.toBuffer()]);
+  const poolAuthority  = pda([Buffer.from("pool_authority")]);
+  const eventAuthority = pda([Buffer.from("__event_authority")]);
+  const vaultA         = pda([Buffer.from("token_vault"), mintPk.toBuffer(), pool.toBuffer()]);
+  const vaultB         = pda([Buffer.from("token_vault"), WSOL_MINT.toBuffer(), pool.toBuffer()]);
 
-  const vaultOwner =
-    poolKeys?.authority ??
-    poolKeys?.vaultOwner ??
-    extInfo?.address?.authority ??
-    createPoolBuilder?.extInfo?.authority ?? null;
+  const positionNftMint = Keypair.generate();
+  const position           = pda([Buffer.from("position"), positionNftMint.publicKey.toBuffer()]);
+  const positionNftAccount = pda([Buffer.from("position_nft_account"), positionNftMint.publicKey.toBuffer()]);
 
-  const extraSigners = createPoolBuilder?.signers ?? [];
+  return {
+    ammPool: pool,
+    lpMint: positionNftMint.publicKey,
+    lpTokenProgram: TOKEN_2022_PROGRAM_ID,
+    escrowLpAccount: positionNftAccount,
+    remaining: [
+      meta(METEORA_DAMM_CONFIG),
+      meta(poolAuthority),
+      meta(position, true),
+      meta(vaultA, true),
+      meta(vaultB, true),
+      meta(eventAuthority),
+    ],
+    signers: [positionNftMint],
+    baseVault: vaultA,
+  };
+}
 
-  const ixs = [
-    ...(createPoolBuilder?.instructions ?? []),
-    ...(createPoolBuilder?.endInstructions ?? []),
-  ];
+const AMM_ADAPTERS = {
+  raydiumCpmm: deriveCpmm,
+  orcaWhirlpool: deriveWhirlpool,
+  meteoraDamm: deriveDamm,
+};
 
-  const addr = extInfo?.address;
-  const candidates = [
-    addr?.poolId,
-    addr?.id,
-    extInfo?.poolId,
-    poolKeys?.id,
-    poolKeys?.poolId,
-    createPoolBuilder?.extInfo?.poolId,
-    createPoolBuilder?.poolId,
-    createPoolBuilder?.targetPoolId,
-  ].filter(Boolean);
-
-  if (!candidates.length) throw new Error("Raydium SDK did not surface a pool id");
-  const raydiumPoolPk = new PublicKey(candidates[0]);
-
-  ok("Raydium CPMM target", {
-    poolId: raydiumPoolPk.toBase58(),
-    baseTokens: tokenAmountBaseUnits.toString(),
-    quoteLamports: wsolLamports.toString(),
-    quoteSol: sol(wsolLamports),
+function deriveAmmForPool(pool, mintPk, migrationEscrow) {
+  const kind = ammKindName(pool.ammKind);
+  const adapter = AMM_ADAPTERS[kind];
+  if (!adapter) throw new Error(`Unsupported AMM kind: ${kind}`);
+  const programId = pool.ammProgram;
+
+  const out = {
+    kind,
+    programId,
+    migrationEscrow,
+    escrowTokenAccount: getAssociatedTokenAddressSync(mintPk, migrationEscrow, true),
+    escrowWsolAccount: getAssociatedTokenAddressSync(WSOL_MINT, migrationEscrow, true),
+    ...adapter(programId, mintPk, migrationEscrow),
+  };
+  Object.assign(out, lpEscrowFor(mintPk, out.lpMint, out.lpTokenProgram));
+  step("AMM addresses", {
+    kind,
+    ...Object.fromEntries(
+      Object.entries(out)
+        .filter(([k]) => !["kind", "remaining", "signers"].includes(k))
+        .map(([k, v]) => [k, fmtPk(v)])
+    ),
+    remaining: out.remaining.map((m) => fmtPk(m.pubkey)),
   });
-
-  return { ixs, signers: extraSigners, raydiumPoolPk, vaultA, vaultOwner };
+  return out;
 }
 
 /* ------------------------------- MIGRATION -------------------------------- */
@@ -392,17 +342,15 @@ export async function migrateIfReady(mintStr) {
     return { skipped: true, reason: "pool_not_found" };
   }
 
-  // Early exit if already migrated to Raydium
+  // Early exit if already graduated
   const phase = phaseName(pool.phase);
-  if (phase === "RaydiumLive" || pool.raydiumPool) {
-    const poolId = fmtPk(pool.raydiumPool);
+  if (phase === "Graduated" || pool.ammPool) {
+    const poolId = fmtPk(pool.ammPool);
     const links = {
       explorerPool: `https://explorer.solana.com/address/${poolId}?cluster=devnet`,
-      raydiumPool: `https://raydium.io/pools?cluster=devnet&poolId=${poolId}`,
-      raydiumAddLiq: `https://raydium.io/liquidity/add?cluster=devnet&poolId=${poolId}`,
     };
-    ok("Already RaydiumLive — skipping migration", { raydiumPool: poolId, ...links });
-    return { skipped: true, reason: "already_migrated", raydiumPool: poolId, links };
+    ok("Already Graduated — skipping migration", { ammPool: poolId, ...links });
+    return { skipped: true, reason: "already_migrated", ammPool: poolId, links };
   }
   
   const decimals = await fetchMintDecimals(mintPk);
@@ -425,50 +373,46 @@ export async function migrateIfReady(mintStr) {
     return { skipped: true, reason: "cap_not_reached" };
   }
 
-  banner("Ensure ATAs");
-  const { ata: wsolAta, ix: ensureWsolAtaIx } = await ensureAtaIx(signer.publicKey, WSOL_MINT);
-  const { ata: signerTokenAta, ix: ensureTokenAtaIx } = await ensureAtaIx(signer.publicKey, mintPk);
-  if (ensureTokenAtaIx) step("Create ATA (token)", { ata: signerTokenAta.toBase58() });
-  if (ensureWsolAtaIx) step("Create ATA (WSOL)", { ata: wsolAta.toBase58() });
-
-  // Base token top-up expected after startMigration (pool+treasury → signer)
-  const poolTokBal = await connection.getTokenAccountBalance(poolTokenAccount).catch(() => null);
-  const treTokBal  = await connection.getTokenAccountBalance(treasuryAta).catch(() => null);
-  const plannedBaseTopup =
-    BigInt(poolTokBal?.value?.amount || "0") + BigInt(treTokBal?.value?.amount || "0");
-  step("Planned base top-up", { fromPool: poolTokBal?.value?.amount || "0", fromTreasury: treTokBal?.value?.amount || "0" });
-
-  // Choose SOL amount (snapshot preferred, else vault balance)
+  // Liquidity the program will seed (pool + treasury tokens, vault SOL minus AMM reserve).
+  // A retry after start_migration finds the curve side already in the escrow.
   banner("Liquidity inputs");
+  const [migrationEscrow] = PublicKey.findProgramAddressSync(
+    [Buffer.from("migration_escrow"), mintPk.toBuffer()],
+    PROGRAM_ID
+  );
   const vaultInfo = await connection.getAccountInfo(solVault);
+  const escrowInfo = await connection.getAccountInfo(migrationEscrow);
   const vaultLamports = BigInt(vaultInfo?.lamports ?? 0);
-  const snapshotSolLamports = toBigIntLike(pool.reserveSnapshotSol);
-  const snapshotTokenBase   = toBigIntLike(pool.reserveSnapshotToken);
-  const wsolLamports = snapshotSolLamports > 0n ? snapshotSolLamports : vaultLamports;
-
-  step("Snapshots / vault", {
-    tokenBaseSnapshot: snapshotTokenBase.toString(),
-    solLamportsSnapshot: snapshotSolLamports.toString(),
+  const escrowLamports = BigInt(escrowInfo?.lamports ?? 0);
+  const poolTokBal = await connection.getTokenAccountBalance(poolTokenAccount).catch(() => null);
+  const treTokBal  = await connection.getTokenAccountBalance(treasuryAta).catch(() => null);
+  step("Pool PDAs", {
     solVaultLamports: vaultLamports.toString(),
-    chosenLamports: wsolLamports.toString(),
-    chosenSol: sol(wsolLamports),
+    solVaultSol: sol(vaultLamports),
+    escrowLamports: escrowLamports.toString(),
+    poolTokens: poolTokBal?.value?.amount || "0",
+    treasuryTokens: treTokBal?.value?.amount || "0",
   });
-  if (wsolLamports === 0n) throw new Error("No SOL in snapshot/vault; aborting migration");
+  if (vaultLamports + escrowLamports === 0n) throw new Error("No SOL in vault or escrow; aborting migration");
 
-  // ① startMigration: drain PDAs → signer
+  const amm = deriveAmmForPool(pool, mintPk, migrationEscrow);
+  const [dexConfigPDA] = PublicKey.findProgramAddressSync([Buffer.from("CurveConfiguration")], PROGRAM_ID);
+
+  // ① startMigration: flip phase + snapshot, move curve reserves into the migration escrow
   banner("Build startMigration");
   const startMigIx = await program.methods
     .startMigration()
     .accounts({
+      dexConfigurationAccount: dexConfigPDA,
       pool: poolPDA,
       tokenMint: mintPk,
       poolTokenAccount: poolTokenAccount,
       poolSolVault: solVault,
-      treasuryPda: treasuryPDA,
-      treasuryTokenAccount: treasuryAta,
-      destTokenAccount: signerTokenAta,
-      migrationAuthority: signer.publicKey,
+      migrationEscrow: amm.migrationEscrow,
+      escrowTokenAccount: amm.escrowTokenAccount,
+      caller: signer.publicKey,
       tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
       systemProgram: SystemProgram.programId,
     })
     .instruction();
@@ -476,62 +420,48 @@ export async function migrateIfReady(mintStr) {
   // Tell clients we entered Migrating as soon as we’re about to send the tx
   broadcastHoldings({ mint: fmtPk(mintPk), source: "phase", phase: "Migrating" });
 
-  // ② Wrap SOL to WSOL (transfer lamports to WSOL ATA, then sync)
-  banner("Wrap SOL → WSOL");
-  const transferLamportsIx = SystemProgram.transfer({
-    fromPubkey: signer.publicKey,
-    toPubkey: wsolAta,
-    lamports: Number(wsolLamports),
-  });
-  step("Wrap meta", { wsolAta: wsolAta.toBase58(), sol: sol(wsolLamports) });
-
-  // ③ Build Raydium CPMM (createPool)
-  banner("Build Raydium CPMM");
-  const tokenAmountBaseUnits = 200_000_000n * 10n ** BigInt(decimals);
-  const { ixs: rayIxs, signers: raySigners = [], raydiumPoolPk, vaultA, vaultOwner } = await buildRaydiumCpmmIxs({
-    mintPk,
-    decimals,
-    signerKp: signer,
-    tokenAta: signerTokenAta,
-    wsolAta,
-    tokenAmountBaseUnits,
-    wsolLamports,
-    plannedBaseTopup,
-  });
-
-  // ④ finalizeMigration
+  // ② finalizeMigration: program CPIs the pool's AMM with the escrow PDA as creator
   banner("Build finalizeMigration");
   const finalizeIx = await program.methods
     .finalizeMigration()
     .accounts({
+      dexConfigurationAccount: dexConfigPDA,
       pool: poolPDA,
       tokenMint: mintPk,
-      migrationAuthority: signer.publicKey,
-      raydiumPool: raydiumPoolPk,
+      wsolMint: WSOL_MINT,
+      treasuryPda: treasuryPDA,
+      treasuryTokenAccount: treasuryAta,
+      migrationEscrow: amm.migrationEscrow,
+      escrowTokenAccount: amm.escrowTokenAccount,
+      escrowWsolAccount: amm.escrowWsolAccount,
+      caller: signer.publicKey,
+      protocolFeeVault: PublicKey.findProgramAddressSync([Buffer.from("protocol_fee")], PROGRAM_ID)[0],
+      creator: pool.creator,
+      poolSolVault: solVault,
+      ammProgram: amm.programId,
+      ammPool: amm.ammPool,
+      ammLpMint: amm.lpMint,
+      escrowLpAccount: amm.escrowLpAccount,
+      lpEscrow: amm.lpEscrow,
+      lpEscrowTokenAccount: amm.lpEscrowTokenAccount,
       tokenProgram: TOKEN_PROGRAM_ID,
+      lpTokenProgram: amm.lpTokenProgram,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+      rent: SYSVAR_RENT_PUBKEY,
     })
+    .remainingAccounts(amm.remaining)
     .instruction();
-  ok("finalizeMigration ix ready", { raydiumPool: raydiumPoolPk.toBase58() });
+  ok("finalizeMigration ix ready", { kind: amm.kind, ammPool: amm.ammPool.toBase58() });
 
   // Compose & send single v0 tx (order matters)
   banner("Compose & send");
   const { blockhash, lastValidBlockHeight } = await connection.getLatestBlockhash();
   step("Blockhash", { blockhash, lastValidBlockHeight });
 
-  const [tokenAtaInfo, wsolAtaInfo] = await Promise.all([
-    connection.getAccountInfo(signerTokenAta),
-    connection.getAccountInfo(wsolAta),
-  ]);
-  const maybeCreateTokenAtaIx = tokenAtaInfo ? null : ensureTokenAtaIx;
-  const maybeCreateWsolAtaIx  = wsolAtaInfo  ? null : ensureWsolAtaIx;
-
   const ixs = [
-    ...(maybeCreateTokenAtaIx ? [maybeCreateTokenAtaIx] : []),
+    ComputeBudgetProgram.setComputeUnitLimit({ units: FINALIZE_COMPUTE_UNITS }),
     startMigIx,
-    ...(maybeCreateWsolAtaIx ? [maybeCreateWsolAtaIx] : []),
-    transferLamportsIx,
-    createSyncNativeInstruction(wsolAta),
-    ...rayIxs,
     finalizeIx,
   ];
 
@@ -542,11 +472,11 @@ export async function migrateIfReady(mintStr) {
   }).compileToV0Message();
 
   const tx = new VersionedTransaction(msg);
-  tx.sign([signer, ...raySigners]);
+  tx.sign([signer, ...amm.signers]);
 
-  ok("Tx ready", { numInstr: ixs.length, hasCreateTokenAta: !!maybeCreateTokenAtaIx, hasCreateWsolAta: !!maybeCreateWsolAtaIx });
+  ok("Tx ready", { numInstr: ixs.length });
 
-  const sig = await sendAndConfirmV0(tx, "startMigration+cpmm+finalize");
+  const sig = await sendAndConfirmV0(tx, "startMigration+finalize");
   ok("Migration tx sent", {
     signature: sig,
     explorerTx: `https://explorer.solana.com/tx/${sig}?cluster=devnet`,
@@ -556,20 +486,21 @@ export async function migrateIfReady(mintStr) {
   banner("Post-verify");
   const poolAfter = await program.account.liquidityPool.fetch(poolPDA);
   const phaseAfter = phaseName(poolAfter.phase);
-  const raydiumPoolAfter = poolAfter?.raydiumPool;
+  const ammPoolAfter = poolAfter?.ammPool;
 
-  if (!raydiumPoolAfter) throw new Error("Post-verify: raydiumPool still null after finalizeMigration()");
-  if (phaseAfter !== "RaydiumLive") throw new Error(`Post-verify: expected RaydiumLive, got ${phaseAfter}`);
+  if (!ammPoolAfter) throw new Error("Post-verify: ammPool still null after finalizeMigration()");
+  if (phaseAfter !== "Graduated") throw new Error(`Post-verify: expected Graduated, got ${phaseAfter}`);
 
-  const poolId = fmtPk(raydiumPoolAfter);
-  const { value: vaultAInfo } = await connection.getParsedAccountInfo(vaultA);
+  const poolId = fmtPk(ammPoolAfter);
+  const { value: baseVaultInfo } = await connection.getParsedAccountInfo(amm.baseVault);
   const parsedOwner =
-    vaultAInfo?.data?.parsed?.info?.owner || null;
-  const raydiumBaseVault  = vaultA ? fmtPk(vaultA) : null;       // token account
+    baseVaultInfo?.data?.parsed?.info?.owner || null;
+  // DB/SSE columns keep their raydium_* names for every AMM
+  const raydiumBaseVault  = fmtPk(amm.baseVault);               // token account
   const raydiumVaultOwner = parsedOwner ? String(parsedOwner) : null;  // <-- THIS is what your holdersMap uses
 
   await updateRaydiumMeta(fmtPk(mintPk), {
-    poolId: fmtPk(raydiumPoolAfter),
+    poolId: fmtPk(ammPoolAfter),
     baseVault: raydiumBaseVault,
     vaultOwner: raydiumVaultOwner,
   });
@@ -596,9 +527,11 @@ export async function migrateIfReady(mintStr) {
   const links = {
     explorerTx:  `https://explorer.solana.com/tx/${sig}?cluster=devnet`,
     explorerPool:`https://explorer.solana.com/address/${poolId}?cluster=devnet`,
-    raydiumSwap: `https://raydium.io/swap/?cluster=devnet&inputMint=sol&outputMint=${tokenMintStr}`,
-    raydiumAddLiq: `https://raydium.io/liquidity/add/?cluster=devnet&base=${tokenMintStr}&quote=${WSOL_MINT_STR}`,
-    raydiumPool: `https://raydium.io/pool/${poolId}?cluster=devnet`,
+    ...(amm.kind === "raydiumCpmm" && {
+      raydiumSwap: `https://raydium.io/swap/?cluster=devnet&inputMint=sol&outputMint=${tokenMintStr}`,
+      raydiumAddLiq: `https://raydium.io/liquidity/add/?cluster=devnet&base=${tokenMintStr}&quote=${WSOL_MINT_STR}`,
+      raydiumPool: `https://raydium.io/pool/${poolId}?cluster=devnet`,
+    }),
   };
 
   ok("Post-verification OK", {
@@ -606,12 +539,12 @@ export async function migrateIfReady(mintStr) {
     ...links,
   });
 
-  // Broadcast “RaydiumLive” + pool id so the UI flips immediately without refresh
+  // Broadcast “Graduated” + pool id so the UI flips immediately without refresh
   broadcastHoldings({
     mint: fmtPk(mintPk),
     source: "phase",
     phase: phaseAfter,
-    raydiumPool: fmtPk(raydiumPoolAfter),
+    raydiumPool: poolId,
   });
 
   // Final summary (key addresses + links)
@@ -622,9 +555,13 @@ export async function migrateIfReady(mintStr) {
     solVault: fmtPk(solVault),
     treasuryPDA: fmtPk(treasuryPDA),
     poolTokenAccount: fmtPk(poolTokenAccount),
-    signerTokenAta: fmtPk(signerTokenAta),
-    wsolAta: fmtPk(wsolAta),
-    raydiumPool: poolId,
+    ammKind: amm.kind,
+    migrationEscrow: fmtPk(amm.migrationEscrow),
+    escrowTokenAccount: fmtPk(amm.escrowTokenAccount),
+    escrowWsolAccount: fmtPk(amm.escrowWsolAccount),
+    escrowLpAccount: fmtPk(amm.escrowLpAccount),
+    lpEscrowTokenAccount: fmtPk(amm.lpEscrowTokenAccount),
+    ammPool: poolId,
     signature: sig,
     links,
   }, null, 2));
@@ -632,11 +569,90 @@ export async function migrateIfReady(mintStr) {
   return {
     ok: true,
     signature: sig,
-    raydiumPool: poolId,
-    raydiumPoolPredicted: fmtPk(raydiumPoolPk),
+    ammKind: amm.kind,
+    ammPool: poolId,
+    ammPoolPredicted: fmtPk(amm.ammPool),
   };
 }
 
+/* ---------------------------------- ABORT ---------------------------------- */
+
+// Returns escrowed reserves to the pool and reopens the curve after a failed
+// finalize. The program only allows it MIGRATION_ABORT_DELAY_SLOTS after the cap.
+export async function abortMigration(mintStr) {
+  banner(`Abort migration: ${mintStr}`);
+
+  const signer = await loadSigner();
+  const program = getCurveProgram(signer.publicKey.toBase58());
+  const { mintPk, poolPDA, solVault, poolTokenAccount } = await deriveForMint(mintStr, signer.publicKey);
+
+  const [migrationEscrow] = PublicKey.findProgramAddressSync(
+    [Buffer.from("migration_escrow"), mintPk.toBuffer()],
+    PROGRAM_ID
+  );
+
+  const sig = await program.methods
+    .abortMigration()
+    .accounts({
+      pool: poolPDA,
+      tokenMint: mintPk,
+      poolTokenAccount,
+      poolSolVault: solVault,
+      migrationEscrow,
+      escrowTokenAccount: getAssociatedTokenAddressSync(mintPk, migrationEscrow, true),
+      authority: signer.publicKey,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([signer])
+    .rpc();
+  ok("Migration aborted", { signature: sig });
+
+  broadcastHoldings({ mint: fmtPk(mintPk), source: "phase", phase: "Active" });
+  try {
+    await resyncMintFromChain(fmtPk(mintPk));
+  } catch (e) {
+    console.error("post-abort resync failed:", e?.message || e);
+  }
+
+  return { ok: true, signature: sig };
+}
+
+export async function closeGraduatedPool(mintStr) {
+  banner(`Close graduated pool: ${mintStr}`);
+
+  const signer = await loadSigner();
+  const program = getCurveProgram(signer.publicKey.toBase58());
+  const { mintPk, poolPDA, solVault, poolTokenAccount } = await deriveForMint(mintStr, signer.publicKey);
+
+  const pool = await program.account.liquidityPool.fetch(poolPDA);
+  if (phaseName(pool?.phase) !== "Graduated") throw new Error("Pool has not graduated");
+
+  // A timed LP lock must show its (released) escrow ATA; burned / locked-for-good need none
+  const timedLock = !pool.lpBurn && !pool.lpRecipient.equals(PublicKey.default);
+
+  const sig = await program.methods
+    .closeGraduatedPool()
+    .accounts({
+      pool: poolPDA,
+      tokenMint: mintPk,
+      poolTokenAccount,
+      poolSolVault: solVault,
+      protocolFeeVault: PublicKey.findProgramAddressSync([Buffer.from("protocol_fee")], PROGRAM_ID)[0],
+      creator: pool.creator,
+      lpEscrowTokenAccount: timedLock ? pool.lpTimelock : null,
+      caller: signer.publicKey,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([signer])
+    .rpc();
+  ok("Graduated pool closed", { signature: sig });
+
+  return { ok: true, signature: sig };
+}
+
 /* ------------------------------ BATCH / DRIVER ----------------------------- */
 
 export async function autoScanAndMigrateAll() {
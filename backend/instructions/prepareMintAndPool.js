@@ -3,42 +3,24 @@ import { BN } from "bn.js";
 import {
   TOKEN_PROGRAM_ID,
   ASSOCIATED_TOKEN_PROGRAM_ID,
-  createMintToInstruction,
-  createAssociatedTokenAccountInstruction,
   getAssociatedTokenAddress,
-  createSetAuthorityInstruction,
-  AuthorityType,
 } from "@solana/spl-token";
 import {
   PublicKey,
   Keypair,
   VersionedTransaction,
-  TransactionInstruction,
   TransactionMessage,
 } from "@solana/web3.js";
 import {
   PROGRAM_ID,
-  DEVNET_URL,
   TOKEN_DECIMALS,
+  TOKEN_METADATA_PROGRAM_ID,
   getProgram,
   connection,
+  AMM_PROGRAMS,
+  DEFAULT_AMM,
 } from "../config/index.js";
 
-// UMI / Metaplex
-import {
-  createUmi,
-  createSignerFromKeypair,
-  percentAmount,
-  signerIdentity,
-} from "@metaplex-foundation/umi";
-import { createWeb3JsRpc } from "@metaplex-foundation/umi-rpc-web3js";
-import { createWeb3JsEddsa } from "@metaplex-foundation/umi-eddsa-web3js";
-import { createWeb3JsTransactionFactory } from "@metaplex-foundation/umi-transaction-factory-web3js";
-import { mplToolbox } from "@metaplex-foundation/mpl-toolbox";
-import { createDefaultProgramRepository } from "@metaplex-foundation/umi-program-repository";
-import mpl from "@metaplex-foundation/mpl-token-metadata";
-const { createV1, mplTokenMetadata, TokenStandard } = mpl;
-
 /* =========================
    Validation (mirror front-end)
    ========================= */
@@ -46,6 +28,9 @@ const NAME_REGEX = /^[A-Za-z0-9 ._\-]{1,32}$/;
 const SYMBOL_REGEX = /^[A-Z0-9]{1,10}$/;
 const URI_REGEX = /^(https?:\/\/|ipfs:\/\/|ar:\/\/).+/i;
 const MIGRATION_AUTHORITY = new PublicKey(process.env.MIGRATION_AUTHORITY_PUBLIC_KEY);
+const MAX_TIERS = 8;
+const SOLD_CAP_TOKENS = 800_000_000n;
+const TREASURY_SUPPLY_TOKENS = 200_000_000n;
 
 function assert(cond, msg) {
   if (!cond) throw new Error(msg);
@@ -57,16 +42,6 @@ function cleanText(s) {
   return noCtrl.replace(/\s+/g, " ").trim();
 }
 
-function createDummySigner(pubkeyStr) {
-  const pk = new PublicKey(pubkeyStr);
-  return {
-    publicKey: pk,
-    signMessage: async () => { throw new Error("Dummy signer cannot sign messages."); },
-    signTransaction: async () => { throw new Error("Dummy signer cannot sign transactions."); },
-    signAllTransactions: async () => { throw new Error("Dummy signer cannot sign transactions."); },
-  };
-}
-
 export async function buildPrepareMintAndPoolTxBase64({
   walletAddress,
   mintSecretKey,      // Uint8Array-like array
@@ -74,6 +49,14 @@ export async function buildPrepareMintAndPoolTxBase64({
   symbol,
   metadataUri,
   initialBuyLamports, // optional lamports
+  tiers,              // optional [{ solWidthLamports, tokenAlloc }]; empty = default 3 tiers
+  tierRatchet,        // optional bool; sells cannot unwind completed tiers
+  vesting,            // optional { vestedTiers, durationSecs }
+  tierBonus,          // optional { perTier, mode: "crossingBuyer" | "proRata" }
+  lpLock,             // optional { lockSlots, recipient } or { burn: true }; omitted = LP locked for good
+  amm,                // optional "raydiumCpmm" | "orcaWhirlpool" | "meteoraDamm"; AMM the pool graduates to
+  launchDeadline,     // optional unix secs; unfilled by then, the pool expires and holders redeem
+  creatorBonusBps,    // optional creator cut of the curve SOL at graduation (max 200 = 2%)
 }) {
   /* ===== Validate inputs ===== */
   assert(typeof walletAddress === "string" && walletAddress.length > 0, "Missing wallet address");
@@ -85,7 +68,7 @@ export async function buildPrepareMintAndPoolTxBase64({
 
   assert(NAME_REGEX.test(nameClean), "Invalid token name");
   assert(SYMBOL_REGEX.test(symbolClean), "Invalid token symbol");
-  assert(URI_REGEX.test(uriClean) && uriClean.length <= 300, "Invalid metadata URI");
+  assert(URI_REGEX.test(uriClean) && uriClean.length <= 200, "Invalid metadata URI");
 
   if (initialBuyLamports !== undefined && initialBuyLamports !== null) {
     const n = Number(initialBuyLamports);
@@ -96,188 +79,157 @@ export async function buildPrepareMintAndPoolTxBase64({
     }
   }
 
-  assert(Number.isInteger(TOKEN_DECIMALS) && TOKEN_DECIMALS >= 0 && TOKEN_DECIMALS <= 9, "Bad TOKEN_DECIMALS");
+  assert(TOKEN_DECIMALS === 9, "Bad TOKEN_DECIMALS"); // launch_token mints with the curve's decimals
+
+  // Tier schedule (mirrors Tier::validate_schedule on-chain)
+  const tierList = Array.isArray(tiers) ? tiers : [];
+  assert(tierList.length <= MAX_TIERS, "Too many tiers");
+  let tierAllocSum = 0n;
+  const tierArgs = tierList.map((t) => {
+    const width = BigInt(t?.solWidthLamports ?? 0);
+    const alloc = BigInt(t?.tokenAlloc ?? 0);
+    assert(width > 0n && alloc > 0n, "Invalid tier");
+    tierAllocSum += alloc;
+    return { solWidth: new BN(width.toString()), tokenAlloc: new BN(alloc.toString()) };
+  });
+  assert(tierList.length === 0 || tierAllocSum === SOLD_CAP_TOKENS, "Tier allocations must sum to 800M");
+
+  let vestingArg = null;
+  if (vesting) {
+    const vestedTiers = Number(vesting.vestedTiers);
+    const durationSecs = Number(vesting.durationSecs);
+    const tierCount = tierList.length || 3;
+    assert(Number.isInteger(vestedTiers) && vestedTiers >= 1 && vestedTiers <= tierCount, "Invalid vested tiers");
+    assert(Number.isInteger(durationSecs) && durationSecs > 0, "Invalid vesting duration");
+    vestingArg = { vestedTiers, duration: new BN(durationSecs) };
+  }
 
-  /* ===== UMI setup ===== */
-  const umi = createUmi(DEVNET_URL);
-  umi.use(signerIdentity(createDummySigner(walletAddress)));
-  umi.programs = createDefaultProgramRepository();
-  umi.eddsa = createWeb3JsEddsa();
-  umi.rpc = createWeb3JsRpc({ programs: umi.programs, transactions: umi.transactions }, DEVNET_URL);
-  umi.transactions = createWeb3JsTransactionFactory();
-  umi.use(mplToolbox()).use(mplTokenMetadata());
+  let bonusArg = null;
+  if (tierBonus) {
+    const perTier = BigInt(tierBonus.perTier ?? 0);
+    const tierCount = BigInt(tierList.length || 3);
+    assert(perTier > 0n && perTier * tierCount <= TREASURY_SUPPLY_TOKENS, "Invalid tier bonus");
+    assert(tierBonus.mode === "crossingBuyer" || tierBonus.mode === "proRata", "Invalid tier bonus mode");
+    bonusArg = { perTier: new BN(perTier.toString()), mode: { [tierBonus.mode]: {} } };
+  }
+
+  // LP lock (mirrors LpLockConfig::validate on-chain)
+  let lpLockArg = null;
+  if (lpLock) {
+    const burn = !!lpLock.burn;
+    const lockSlots = BigInt(lpLock.lockSlots ?? 0);
+    assert(lockSlots >= 0n, "Invalid LP lock slots");
+    assert(burn !== !!lpLock.recipient, "LP lock needs a recipient, or burn without one");
+    lpLockArg = {
+      lockSlots: new BN(lockSlots.toString()),
+      recipient: burn ? PublicKey.default : new PublicKey(lpLock.recipient),
+      burn,
+    };
+  }
+
+  // Migration target (mirrors AmmTarget::validate on-chain)
+  const ammKind = amm ?? DEFAULT_AMM;
+  assert(Object.hasOwn(AMM_PROGRAMS, ammKind), "Invalid AMM");
+  const ammArg = { kind: { [ammKind]: {} }, program: AMM_PROGRAMS[ammKind] };
+
+  // Launch deadline (unix secs); must be in the future, as create_pool checks
+  let launchDeadlineArg = null;
+  if (launchDeadline != null) {
+    const deadline = Number(launchDeadline);
+    assert(Number.isInteger(deadline) && deadline > Math.floor(Date.now() / 1000), "Invalid launch deadline");
+    launchDeadlineArg = new BN(deadline);
+  }
+
+  let creatorBonusArg = null;
+  if (creatorBonusBps != null) {
+    const bps = Number(creatorBonusBps);
+    assert(Number.isInteger(bps) && bps >= 0 && bps <= 200, "Invalid creator graduation bonus");
+    creatorBonusArg = bps;
+  }
 
   /* ===== Mint keypair ===== */
   const mint = Keypair.fromSecretKey(Uint8Array.from(mintSecretKey));
   const mintPubkeyObj = mint.publicKey;
-
-  const umiMint = createSignerFromKeypair(umi, {
-    publicKey: mintPubkeyObj.toBytes(),
-    secretKey: Uint8Array.from(mint.secretKey),
-  });
+  const creator = new PublicKey(walletAddress);
 
   /* ===== PDAs ===== */
   const [poolPDA]      = PublicKey.findProgramAddressSync([Buffer.from("liquidity_pool"),      mintPubkeyObj.toBuffer()], PROGRAM_ID);
   const [solVaultPDA]  = PublicKey.findProgramAddressSync([Buffer.from("liquidity_sol_vault"), mintPubkeyObj.toBuffer()], PROGRAM_ID);
   const [dexConfigPDA] = PublicKey.findProgramAddressSync([Buffer.from("CurveConfiguration")], PROGRAM_ID);
   const [treasuryPDA]  = PublicKey.findProgramAddressSync([Buffer.from("treasury"),            mintPubkeyObj.toBuffer()], PROGRAM_ID);
+  const [metadataPDA]  = PublicKey.findProgramAddressSync(
+    [Buffer.from("metadata"), TOKEN_METADATA_PROGRAM_ID.toBuffer(), mintPubkeyObj.toBuffer()],
+    TOKEN_METADATA_PROGRAM_ID
+  );
+  const [vestingEscrow] = PublicKey.findProgramAddressSync(
+    [Buffer.from("vesting_escrow"), poolPDA.toBuffer(), creator.toBuffer()], PROGRAM_ID
+  );
+  const [tierParticipation] = PublicKey.findProgramAddressSync(
+    [Buffer.from("tier_participation"), poolPDA.toBuffer(), creator.toBuffer()], PROGRAM_ID
+  );
 
-  /* ===== Create mint + metadata ===== */
-  const createTokenTx = await createV1(umi, {
-    mint: umiMint,
-    authority: new PublicKey(walletAddress),
-    name: nameClean,
-    symbol: symbolClean,
-    uri: uriClean,
-    sellerFeeBasisPoints: percentAmount(0),
-    decimals: TOKEN_DECIMALS,
-    tokenStandard: TokenStandard.Fungible,
-  });
-
-  /* ===== Anchor program ===== */
-  const program = getProgram(walletAddress);
-
-  // Pool ATA
-  const poolTokenAccount = await anchor.utils.token.associatedAddress({
-    mint: mintPubkeyObj,
-    owner: poolPDA,
-  });
-
-  // Treasury ATA (off-curve owner)
+  // ATAs (pool and treasury are off-curve owners); launch_token creates them
+  const poolTokenAccount = await getAssociatedTokenAddress(
+    mintPubkeyObj, poolPDA, true, TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID
+  );
   const treasuryAta = await getAssociatedTokenAddress(
-    mintPubkeyObj,
-    treasuryPDA,
-    true,
-    TOKEN_PROGRAM_ID,
-    ASSOCIATED_TOKEN_PROGRAM_ID
+    mintPubkeyObj, treasuryPDA, true, TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID
   );
-  const treasuryAtaInfo = await connection.getAccountInfo(treasuryAta);
-  const ensureTreasuryAtaIx = !treasuryAtaInfo
-    ? createAssociatedTokenAccountInstruction(
-        new PublicKey(walletAddress),
-        treasuryAta,
-        treasuryPDA,
-        mintPubkeyObj,
-        TOKEN_PROGRAM_ID,
-        ASSOCIATED_TOKEN_PROGRAM_ID
-      )
+  const userTokenAccount = await getAssociatedTokenAddress(
+    mintPubkeyObj, creator, false, TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID
+  );
+  const vestingTokenAccount = vestingArg
+    ? await getAssociatedTokenAddress(mintPubkeyObj, vestingEscrow, true, TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID)
     : null;
 
-  // Create Pool ix
-  const poolIx = await program.methods
-    .createPool(MIGRATION_AUTHORITY)
+  /* ===== launch_token: mint, metadata, pool, 800M/200M split, revokes, first buy ===== */
+  const initialBuy = initialBuyLamports && Number(initialBuyLamports) > 0 ? new BN(initialBuyLamports) : null;
+  const program = getProgram(walletAddress);
+  const launchIx = await program.methods
+    .launchToken(
+      { name: nameClean, symbol: symbolClean, uri: uriClean },
+      MIGRATION_AUTHORITY,
+      tierArgs,
+      !!tierRatchet,
+      vestingArg,
+      bonusArg,
+      lpLockArg,
+      ammArg,
+      launchDeadlineArg,
+      creatorBonusArg,
+      initialBuy
+    )
     .accounts({
+      dexConfigurationAccount: dexConfigPDA,
       pool: poolPDA,
       tokenMint: mintPubkeyObj,
+      metadata: metadataPDA,
       poolTokenAccount,
+      treasuryPda: treasuryPDA,
+      treasuryTokenAccount: treasuryAta,
       poolSolVault: solVaultPDA,
-      payer: new PublicKey(walletAddress),
-      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
-      associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+      userTokenAccount: initialBuy ? userTokenAccount : null,
+      vestingEscrow: initialBuy && vestingArg ? vestingEscrow : null,
+      vestingTokenAccount: initialBuy ? vestingTokenAccount : null,
+      tierParticipation: initialBuy && bonusArg?.mode?.proRata ? tierParticipation : null,
+      creator,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
       rent: anchor.web3.SYSVAR_RENT_PUBKEY,
       systemProgram: anchor.web3.SystemProgram.programId,
     })
     .instruction();
 
-  /* ===== Supply math sanity ===== */
-  const DEC      = BigInt(TOKEN_DECIMALS);
-  const POW10    = 10n ** DEC;
-  const CAP_BASE = 800_000_000n * POW10;   // 800M
-  const TOTAL    = 1_000_000_000n * POW10; // 1B
-  const REM_BASE = TOTAL - CAP_BASE;       // 200M
-
-  assert(CAP_BASE > 0n && TOTAL > 0n && CAP_BASE < TOTAL, "Invalid supply configuration");
-  assert(REM_BASE > 0n, "Remainder supply must be positive");
-
-  // Seed supply
-  const mintToPoolIx = createMintToInstruction(
-    mintPubkeyObj, poolTokenAccount, new PublicKey(walletAddress), CAP_BASE, [], TOKEN_PROGRAM_ID
-  );
-  const mintToTreasuryIx = createMintToInstruction(
-    mintPubkeyObj, treasuryAta, new PublicKey(walletAddress), REM_BASE, [], TOKEN_PROGRAM_ID
-  );
-
-  // Optional initial buy
-  let ensureUserAtaIx = null;
-  let buyIx = null;
-  if (initialBuyLamports && Number(initialBuyLamports) > 0) {
-    const user = new PublicKey(walletAddress);
-    const userTokenAccount = await getAssociatedTokenAddress(
-      mintPubkeyObj, user, false, TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID
-    );
-    const userAtaInfo = await connection.getAccountInfo(userTokenAccount);
-    if (!userAtaInfo) {
-      ensureUserAtaIx = createAssociatedTokenAccountInstruction(
-        user, userTokenAccount, user, mintPubkeyObj, TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID
-      );
-    }
-
-    buyIx = await program.methods
-      .buy(new BN(initialBuyLamports))
-      .accounts({
-        dexConfigurationAccount: dexConfigPDA,
-        pool: poolPDA,
-        tokenMint: mintPubkeyObj,
-        poolTokenAccount,
-        userTokenAccount,
-        poolSolVault: solVaultPDA,
-        user,
-        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
-        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
-        associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
-        systemProgram: anchor.web3.SystemProgram.programId,
-      })
-      .instruction();
-  }
-
-  // Revoke MINT authority (no more supply can be minted)
-  const revokeMintAuthIx = createSetAuthorityInstruction(
-    mintPubkeyObj,                      // account = the mint
-    new PublicKey(walletAddress),       // current authority (must sign)
-    AuthorityType.MintTokens,           // revoke mint authority
-    null,                               // newAuthority = none
-    [],                                 // multisig not used
-    TOKEN_PROGRAM_ID
-  );
-
-  // Revoke FREEZE authority (no one can freeze token accounts)
-  const revokeFreezeAuthIx = createSetAuthorityInstruction(
-    mintPubkeyObj,
-    new PublicKey(walletAddress),
-    AuthorityType.FreezeAccount,        // revoke freeze authority
-    null,
-    [],
-    TOKEN_PROGRAM_ID
-  );
-
-  /* ===== Build instructions (wrap UMI ixs) ===== */
   const instructions = [
-    ...createTokenTx.getInstructions().map(ix => new TransactionInstruction({
-      programId: new PublicKey(ix.programId),
-      keys: ix.keys.map(k => ({
-        pubkey: new PublicKey(k.pubkey),
-        isSigner:
-          k.pubkey === walletAddress ||
-          k.pubkey === mintPubkeyObj.toBase58() ||
-          !!k.isSigner,
-        isWritable: k.isWritable,
-      })),
-      data: Buffer.from(ix.data),
-    })),
-    poolIx,
-    ...(ensureTreasuryAtaIx ? [ensureTreasuryAtaIx] : []),
-    mintToPoolIx,
-    mintToTreasuryIx,
-    revokeMintAuthIx,
-    revokeFreezeAuthIx,
-    ...(ensureUserAtaIx ? [ensureUserAtaIx] : []),
-    ...(buyIx ? [buyIx] : []),
+    anchor.web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 }),
+    launchIx,
   ];
 
   /* ===== Compile & partial sign (mint) ===== */
   const { blockhash } = await connection.getLatestBlockhash();
   const msgV0 = new TransactionMessage({
-    payerKey: new PublicKey(walletAddress),
+    payerKey: creator,
     recentBlockhash: blockhash,
     instructions,
   }).compileToV0Message();
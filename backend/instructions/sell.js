@@ -5,11 +5,16 @@ import * as anchor from "@coral-xyz/anchor";
 
 export async function buildSellTxBase64({ walletAddress, mintPubkey, amountLamports }) {
   const program = getProgram(walletAddress);
-  const { mintPk, poolPDA, solVault, solVaultBump, dexConfigPDA, poolTokenAccount, userTokenAccount, userPk } =
-    await deriveForMint(mintPubkey, walletAddress);
+  const {
+    mintPk, poolPDA, solVault, dexConfigPDA, poolTokenAccount, userTokenAccount, userPk, tierParticipation,
+  } = await deriveForMint(mintPubkey, walletAddress);
+
+  // Pro-rata bonus pools take the seller's tier weights back
+  const pool = await program.account.liquidityPool.fetch(poolPDA);
+  const proRata = Number(pool.bonusPerTier) > 0 && !!pool.bonusMode?.proRata;
 
   const sellIx = await program.methods
-    .sell(new BN(amountLamports), solVaultBump)
+    .sellV2(new BN(amountLamports))
     .accounts({
       dexConfigurationAccount: dexConfigPDA,
       pool: poolPDA,
@@ -17,6 +22,7 @@ export async function buildSellTxBase64({ walletAddress, mintPubkey, amountLampo
       poolTokenAccount,
       poolSolVault: solVault,
       userTokenAccount,
+      tierParticipation: proRata ? tierParticipation : null,
       user: userPk,
       rent: anchor.web3.SYSVAR_RENT_PUBKEY,
       tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
@@ -1,6 +1,11 @@
 import express from "express";
 import * as anchor from "@coral-xyz/anchor";
-import { migrateIfReady, autoScanAndMigrateAll } from "../instructions/migrate.js";
+import {
+  migrateIfReady,
+  autoScanAndMigrateAll,
+  abortMigration,
+  closeGraduatedPool,
+} from "../instructions/migrate.js";
 import { PublicKey } from "@solana/web3.js";
 import {
   connection,
@@ -14,7 +19,7 @@ const router = express.Router();
 
 /** Match your program’s Phase struct to a simple label. */
 function phaseName(p) {
-  return p?.migrating ? "Migrating" : p?.raydiumLive ? "RaydiumLive" : "Active";
+  return p?.migrating ? "Migrating" : p?.graduated ? "Graduated" : p?.expired ? "Expired" : "Active";
 }
 
 /** Minimal read-only wallet for AnchorProvider (no tx signing). */
@@ -36,6 +41,30 @@ router.post("/migrate/one", async (req, res) => {
   }
 });
 
+router.post("/migrate/abort", async (req, res) => {
+  try {
+    const { mint } = req.body || {};
+    if (!mint) return res.status(400).json({ error: "mint required" });
+    const result = await abortMigration(mint);
+    res.json(result);
+  } catch (err) {
+    console.error("POST /migrate/abort error:", err);
+    res.status(500).json({ error: err.message });
+  }
+});
+
+router.post("/migrate/close", async (req, res) => {
+  try {
+    const { mint } = req.body || {};
+    if (!mint) return res.status(400).json({ error: "mint required" });
+    const result = await closeGraduatedPool(mint);
+    res.json(result);
+  } catch (err) {
+    console.error("POST /migrate/close error:", err);
+    res.status(500).json({ error: err.message });
+  }
+});
+
 router.post("/migrate/scan", async (_req, res) => {
   try {
     const summary = await autoScanAndMigrateAll();
@@ -64,11 +93,13 @@ router.get("/pool-info", async (req, res) => {
     });
     const program = new anchor.Program(idl, provider);
 
-    const pool = await program.account.liquidityPool.fetch(poolPDA);
-    const phase = phaseName(pool?.phase);
-    const raydiumPool =
-      pool?.raydiumPool && typeof pool.raydiumPool.toBase58 === "function"
-        ? pool.raydiumPool.toBase58()
+    // Closed graduated pools leave a GraduatedPool record at the same PDA
+    const closed = await program.account.graduatedPool.fetchNullable(poolPDA).catch(() => null);
+    const pool = closed ?? (await program.account.liquidityPool.fetch(poolPDA));
+    const phase = closed ? "Graduated" : phaseName(pool?.phase);
+    const ammPool =
+      pool?.ammPool && typeof pool.ammPool.toBase58 === "function"
+        ? pool.ammPool.toBase58()
         : null;
 
     return res.json({
@@ -76,7 +107,9 @@ router.get("/pool-info", async (req, res) => {
       mint: mintPk.toBase58(),
       poolPDA: poolPDA.toBase58(),
       phase,
-      raydiumPool,
+      ammKind: Object.keys(pool?.ammKind || {})[0] || null,
+      ammProgram: pool?.ammProgram?.toBase58?.() ?? null,
+      ammPool,
     });
   } catch (err) {
     console.error("GET /pool-info error:", err);
@@ -84,4 +117,46 @@ router.get("/pool-info", async (req, res) => {
   }
 });
 
+router.get("/curve-view", async (req, res) => {
+  try {
+    const mintStr = String(req.query.mint || "").trim();
+    if (!mintStr) return res.status(400).json({ error: "mint required" });
+
+    const mintPk = new PublicKey(mintStr);
+    const [poolPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("liquidity_pool"), mintPk.toBuffer()],
+      PROGRAM_ID
+    );
+
+    const provider = new anchor.AnchorProvider(connection, READONLY_WALLET, {
+      commitment: "confirmed",
+      preflightCommitment: "confirmed",
+    });
+    const program = new anchor.Program(idl, provider);
+
+    // Simulated call; the program computes everything with its trade math
+    const view = await program.methods
+      .getCurveView()
+      .accounts({ pool: poolPDA, tokenMint: mintPk })
+      .view();
+
+    return res.json({
+      ok: true,
+      mint: mintPk.toBase58(),
+      phase: phaseName(view.phase),
+      spotPriceSolPerToken: view.spotPriceSolPerToken,
+      currentTier: view.currentTier,
+      tierCount: view.tierCount,
+      tierProgressBps: view.tierProgressBps,
+      totalSold: view.totalSold.toString(),
+      tokensRemaining: view.tokensRemaining.toString(),
+      lamportsToGraduate: view.lamportsToGraduate.toString(),
+      tierEndPrices: view.tierEndPrices,
+    });
+  } catch (err) {
+    console.error("GET /curve-view error:", err);
+    return res.status(500).json({ error: err.message });
+  }
+});
+
 export default router;
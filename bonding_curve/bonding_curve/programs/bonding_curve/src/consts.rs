@@ -1,25 +1,77 @@
 #![allow(dead_code)]
 
 use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::pubkey;
+
+// Values below marked "GlobalConfig default" seed a fresh config (initialize /
+// upgrade_config); after that the config account is the source of truth.
 
 // Lamports per one token (pre-decimal). Used by your legacy math helper.
 pub const INITIAL_PRICE_DIVIDER: u64 = 800_000;
 
-// Seed SOL in the pool vault at bootstrap (0.01 SOL).
+// Seed SOL in the pool vault at bootstrap (0.01 SOL). GlobalConfig default.
 pub const INITIAL_LAMPORTS_FOR_POOL: u64 = 10_000_000;
 
-// Max percent of tokens a user can sell in one go (basis points).
+// Max percent of tokens a user can sell in one go (basis points). GlobalConfig default.
 pub const TOKEN_SELL_LIMIT_PERCENT: u64 = 8000; // 80%
 
 // Curve tuning note from your repo:
 // 800M tokens sold on 500 SOL => proportion = 1280.
 pub const PROPORTION: u64 = 1280;
 
-// Hard cap for total tokens sold via the curve (base units, no decimals).
+// Hard cap for total tokens sold via the curve (base units, no decimals). GlobalConfig default.
 pub const SOLD_CAP: u64 = 800_000_000;
 
-// (Optional) Defense-in-depth: only accept these Raydium program IDs when finalizing.
-// Leave empty for now; you can fill with known CLMM/AMM IDs later.
-pub const ALLOWLISTED_RAYDIUM_PROGRAMS: &[Pubkey] = &[
-    // Example: Pubkey::new_from_array(*b"................................"),
-];
+// Tokens minted to the treasury PDA ATA at launch (whole tokens).
+pub const TREASURY_SUPPLY: u64 = 200_000_000;
+
+// Max number of tiers a creator can define for one pool.
+pub const MAX_TIERS: usize = 8;
+
+// AMM program IDs that finalize_migration may CPI into, per AmmKind.
+// The migration escrow PDA signs that CPI, so anything else is rejected.
+// GlobalConfig default allowlist.
+pub const RAYDIUM_CPMM_DEVNET: Pubkey = pubkey!("DRaycpLY18LhpbydsBWbVJtxpNv9oXPgjRSfpF2bWpYb");
+pub const RAYDIUM_CPMM_MAINNET: Pubkey = pubkey!("CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C");
+pub const ORCA_WHIRLPOOL: Pubkey = pubkey!("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc"); // same on devnet
+pub const METEORA_DAMM_V2: Pubkey = pubkey!("cpamdpZCGKUy5JxQXB4dcpGPiikHawvSWAd6mEn1sGG"); // same on devnet
+
+pub const ALLOWLISTED_RAYDIUM_PROGRAMS: &[Pubkey] = &[RAYDIUM_CPMM_DEVNET, RAYDIUM_CPMM_MAINNET];
+pub const ALLOWLISTED_ORCA_PROGRAMS: &[Pubkey] = &[ORCA_WHIRLPOOL];
+pub const ALLOWLISTED_METEORA_PROGRAMS: &[Pubkey] = &[METEORA_DAMM_V2];
+
+// Lamports kept in the migration escrow at finalize to pay for AMM account rent and
+// the create-pool fee (the escrow PDA is the AMM "creator"). 0.3 SOL.
+pub const AMM_CREATION_RESERVE_LAMPORTS: u64 = 300_000_000;
+
+// Slots after the cap is reached during which only migration_authority may
+// drive migration; after that anyone can crank it (~1h at 400ms slots).
+pub const MIGRATION_GRACE_SLOTS: u64 = 9_000;
+
+// Paid to a non-authority caller who finalizes, out of the creator's seed in the vault (0.01 SOL).
+pub const MIGRATION_CRANK_REWARD_LAMPORTS: u64 = 10_000_000;
+
+// Slots after the cap is reached before migration_authority or the config
+// admin may abort a migration that hasn't finalized (~2h at 400ms slots).
+// Past MIGRATION_GRACE_SLOTS, so cranks get a full window of their own to
+// finalize before an abort can pre-empt them.
+pub const MIGRATION_ABORT_DELAY_SLOTS: u64 = 2 * MIGRATION_GRACE_SLOTS;
+
+// Protocol cut of the curve's SOL at graduation, paid to the protocol fee PDA
+// before seeding the AMM (basis points; 1%). Fixed per pool at creation. GlobalConfig default.
+pub const MIGRATION_FEE_BPS: u16 = 100;
+
+// Upper bound on the creator's optional graduation bonus (basis points; 2%). GlobalConfig default.
+pub const MAX_CREATOR_GRADUATION_BONUS_BPS: u16 = 200;
+
+// Max AMM programs the GlobalConfig allowlist holds (sizes the account).
+pub const MAX_AMM_ALLOWLIST: usize = 8;
+
+// Slots between an admin's emergency-withdraw request on a pool and the
+// withdrawal itself, so holders can see it coming and exit (~24h at 400ms slots).
+pub const EMERGENCY_WITHDRAW_DELAY_SLOTS: u64 = 216_000;
+
+// Slack for the solvency invariant: buys charge floor(lamports) and the sell
+// path is evaluated in f64, so the vault may trail the exact sell-back value
+// by a few lamports without anything being wrong.
+pub const SOLVENCY_TOLERANCE_LAMPORTS: u64 = 1_000;
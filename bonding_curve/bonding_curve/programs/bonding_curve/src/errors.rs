@@ -63,6 +63,114 @@ pub enum CustomError {
     #[msg("Unauthorized.")]
     Unauthorized,
     
-    #[msg("Raydium program not allowlisted.")]
+    #[msg("AMM program not allowlisted.")]
     InvalidProgram,
+
+    #[msg("Invalid tier schedule.")]
+    InvalidTierSchedule,
+
+    #[msg("Sell would unwind a completed tier.")]
+    TierRatchetFloor,
+
+    #[msg("Invalid vesting configuration.")]
+    InvalidVestingConfig,
+
+    #[msg("Vesting escrow accounts are required for this buy.")]
+    VestingAccountsMissing,
+
+    #[msg("Nothing vested to claim.")]
+    NothingToClaim,
+
+    #[msg("Invalid tier bonus configuration.")]
+    InvalidBonusConfig,
+
+    #[msg("Tier bonus accounts are required for this trade.")]
+    BonusAccountsMissing,
+
+    #[msg("Tier bonus not enabled for this pool.")]
+    BonusNotEnabled,
+
+    #[msg("Tier not completed yet.")]
+    TierNotCompleted,
+
+    #[msg("Tier bonus already claimed.")]
+    BonusAlreadyClaimed,
+
+    #[msg("AMM pool account is not a valid pool state.")]
+    InvalidAmmPool,
+
+    #[msg("AMM pool mints do not match this token and wSOL.")]
+    AmmPoolMintMismatch,
+
+    #[msg("AMM pool holds less than the migration snapshot.")]
+    AmmPoolUnderfunded,
+
+    #[msg("Invalid LP lock configuration.")]
+    InvalidLpLockConfig,
+
+    #[msg("LP tokens are still locked.")]
+    LpStillLocked,
+
+    #[msg("Migration cannot be aborted yet.")]
+    MigrationAbortLocked,
+
+    #[msg("Launch deadline must be in the future.")]
+    InvalidLaunchDeadline,
+
+    #[msg("Launch deadline has passed; the pool only redeems now.")]
+    LaunchExpired,
+
+    #[msg("Launch has not expired.")]
+    LaunchNotExpired,
+
+    #[msg("Creator graduation bonus exceeds the maximum.")]
+    InvalidGraduationBonus,
+
+    #[msg("Invalid config value.")]
+    InvalidConfig,
+
+    #[msg("Config account is already on the current layout.")]
+    ConfigAlreadyUpgraded,
+
+    #[msg("Protocol is paused.")]
+    ProtocolPaused,
+
+    #[msg("Pool is paused.")]
+    PoolPaused,
+
+    #[msg("Liquidity can only be removed before any sale.")]
+    LiquidityLocked,
+
+    #[msg("No emergency withdraw requested, or its timelock has not passed.")]
+    EmergencyWithdrawLocked,
+
+    #[msg("Pool reserve_sol exceeds the SOL vault balance.")]
+    ReserveMismatch,
+
+    #[msg("SOL vault cannot cover selling back every sold token.")]
+    CurveInsolvent,
+
+    #[msg("Mint or freeze authority has not been revoked.")]
+    MintAuthorityNotRevoked,
+
+    #[msg("Mint decimals don't match the curve.")]
+    InvalidMintDecimals,
+
+    #[msg("Supply must be exactly the sold cap in the pool ATA plus the treasury supply in the treasury ATA.")]
+    InvalidSupplyDistribution,
+
+    #[msg("Token name, symbol or URI is empty or too long.")]
+    InvalidTokenMetadata,
+
+    #[msg("Creator token account is required for the launch buy.")]
+    CreatorTokenAccountMissing,
+
+    #[msg("start_migration has not escrowed the curve yet.")]
+    MigrationNotStarted,
+
+    #[msg("Treasury token account is required for the emergency withdraw.")]
+    TreasuryAccountMissing,
+
+    #[msg("Vested tokens or tier bonuses are still unclaimed.")]
+    ClaimsOutstanding,
 }
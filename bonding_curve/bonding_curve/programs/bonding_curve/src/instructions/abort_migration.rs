@@ -0,0 +1,164 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::{
+    associated_token::{get_associated_token_address, AssociatedToken},
+    token::{self, Mint, Token, TokenAccount},
+};
+
+use crate::{
+    errors::CustomError,
+    state::{GlobalConfig, LiquidityPool, PoolPhase},
+    utils::curve::cap_base,
+};
+
+pub fn handle(ctx: Context<AbortMigration>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let clock = Clock::get()?;
+
+    require!(matches!(pool.phase, PoolPhase::Migrating), CustomError::BadPhase);
+    require!(
+        pool.may_abort_migration(
+            &ctx.accounts.authority.key(),
+            &ctx.accounts.dex_configuration_account.admin
+        ),
+        CustomError::Unauthorized
+    );
+    require!(
+        pool.migration_abort_unlocked(clock.slot),
+        CustomError::MigrationAbortLocked
+    );
+
+    let mint_key = ctx.accounts.token_mint.key();
+    let escrow_seeds = &[
+        LiquidityPool::MIGRATION_ESCROW_PREFIX.as_bytes(),
+        mint_key.as_ref(),
+        &[ctx.bumps.migration_escrow],
+    ];
+    let escrow_signer = &[&escrow_seeds[..]];
+
+    // 1) Escrowed curve tokens -> pool ATA. The escrow ATA only exists once
+    //    start_migration has run; the cap-filling buy alone doesn't create it.
+    let token_amount = if ctx.accounts.escrow_token_account.data_is_empty() {
+        0
+    } else {
+        let data = ctx.accounts.escrow_token_account.try_borrow_data()?;
+        TokenAccount::try_deserialize(&mut &data[..])?.amount
+    };
+    if token_amount > 0 {
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.escrow_token_account.to_account_info(),
+                    to: ctx.accounts.pool_token_account.to_account_info(),
+                    authority: ctx.accounts.migration_escrow.to_account_info(),
+                },
+                escrow_signer,
+            ),
+            token_amount,
+        )?;
+    }
+
+    // 2) Escrowed lamports -> SOL vault
+    let sol_amount = ctx.accounts.migration_escrow.lamports();
+    if sol_amount > 0 {
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.migration_escrow.to_account_info(),
+                    to: ctx.accounts.pool_sol_vault.to_account_info(),
+                },
+                escrow_signer,
+            ),
+            sol_amount,
+        )?;
+    }
+
+    // 3) Back to the curve: whatever stayed in place plus what just came back,
+    //    up to what total_sold / total_sol_in say the curve holds (anything
+    //    above that, e.g. lamports sent to the escrow, is a donation)
+    let decimals = ctx.accounts.token_mint.decimals;
+    let curve_tokens = cap_base(decimals).saturating_sub(pool.total_sold);
+    pool.reserve_token = pool
+        .reserve_token
+        .saturating_add(token_amount)
+        .min(curve_tokens);
+    pool.reserve_sol = pool
+        .reserve_sol
+        .saturating_add(sol_amount)
+        .min(pool.total_sol_in);
+    pool.phase = PoolPhase::Active;
+    // The curve is still full: restart the grace clock rather than clear it,
+    // so after MIGRATION_GRACE_SLOTS anyone may start migration again
+    pool.cap_reached_slot = Some(clock.slot);
+    pool.reserve_snapshot_token = 0;
+    pool.reserve_snapshot_sol = 0;
+    pool.check_invariants(ctx.accounts.pool_sol_vault.lamports(), decimals)?;
+
+    emit!(crate::MigrationAborted {
+        pool: pool.key(),
+        slot: clock.slot,
+        reserve_token: pool.reserve_token,
+        reserve_sol: pool.reserve_sol,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AbortMigration<'info> {
+    #[account(
+        seeds = [GlobalConfig::SEED.as_bytes()],
+        bump,
+    )]
+    pub dex_configuration_account: Box<Account<'info, GlobalConfig>>,
+
+    #[account(
+        mut,
+        seeds = [LiquidityPool::POOL_SEED_PREFIX.as_bytes(), token_mint.key().as_ref()],
+        bump = pool.bump,
+    )]
+    pub pool: Box<Account<'info, LiquidityPool>>,
+
+    pub token_mint: Box<Account<'info, Mint>>,
+
+    // pool ATA (tokens owned by the pool PDA)
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = pool
+    )]
+    pub pool_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: SOL vault PDA
+    #[account(
+        mut,
+        seeds = [LiquidityPool::SOL_VAULT_PREFIX.as_bytes(), token_mint.key().as_ref()],
+        bump = pool.vault_bump
+    )]
+    pub pool_sol_vault: AccountInfo<'info>,
+
+    /// CHECK: migration escrow PDA (system-owned, holds the SOL side)
+    #[account(
+        mut,
+        seeds = [LiquidityPool::MIGRATION_ESCROW_PREFIX.as_bytes(), token_mint.key().as_ref()],
+        bump
+    )]
+    pub migration_escrow: AccountInfo<'info>,
+
+    /// CHECK: escrow's token ATA; empty if start_migration never ran
+    #[account(
+        mut,
+        address = get_associated_token_address(&migration_escrow.key(), &token_mint.key())
+    )]
+    pub escrow_token_account: UncheckedAccount<'info>,
+
+    // The pool's migration_authority or the config admin, once
+    // MIGRATION_ABORT_DELAY_SLOTS have passed
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
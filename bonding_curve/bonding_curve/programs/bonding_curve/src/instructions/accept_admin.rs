@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::CustomError, state::GlobalConfig};
+
+/// Step two of an admin handover: the nominated key takes over.
+pub fn handle(ctx: Context<AcceptAdmin>) -> Result<()> {
+    let config = &mut ctx.accounts.dex_configuration_account;
+    let previous = config.admin;
+
+    config.admin = ctx.accounts.pending_admin.key();
+    config.pending_admin = None;
+
+    emit!(crate::AdminTransferred {
+        previous_admin: previous,
+        admin: config.admin,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [GlobalConfig::SEED.as_bytes()],
+        bump,
+        constraint = dex_configuration_account.pending_admin == Some(pending_admin.key())
+            @ CustomError::Unauthorized,
+    )]
+    pub dex_configuration_account: Box<Account<'info, GlobalConfig>>,
+
+    pub pending_admin: Signer<'info>,
+}
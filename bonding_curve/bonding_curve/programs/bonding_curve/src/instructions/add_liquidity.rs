@@ -4,9 +4,13 @@ use anchor_spl::{
     token::{Mint, Token, TokenAccount},
 };
 
-use crate::state::{LiquidityPool, LiquidityPoolAccount};
+use crate::state::{GlobalConfig, LiquidityPool, LiquidityPoolAccount};
 
 pub fn handle(ctx: Context<AddLiquidity>) -> Result<()> {
+    ctx.accounts
+        .dex_configuration_account
+        .require_unpaused(&ctx.accounts.pool)?;
+
     let pool = &mut ctx.accounts.pool;
 
     let token_accounts = (
@@ -18,6 +22,7 @@ pub fn handle(ctx: Context<AddLiquidity>) -> Result<()> {
     pool.add_liquidity(
         token_accounts,
         &mut ctx.accounts.pool_sol_vault,
+        ctx.accounts.dex_configuration_account.initial_lamports_for_pool,
         &ctx.accounts.user,
         &ctx.accounts.token_program,
         &ctx.accounts.system_program,
@@ -27,6 +32,13 @@ pub fn handle(ctx: Context<AddLiquidity>) -> Result<()> {
 
 #[derive(Accounts)]
 pub struct AddLiquidity<'info> {
+    // Global config
+    #[account(
+        seeds = [GlobalConfig::SEED.as_bytes()],
+        bump,
+    )]
+    pub dex_configuration_account: Box<Account<'info, GlobalConfig>>,
+
     #[account(
         mut,
         seeds = [LiquidityPool::POOL_SEED_PREFIX.as_bytes(), token_mint.key().as_ref()],
@@ -55,7 +67,7 @@ pub struct AddLiquidity<'info> {
     #[account(
         mut,
         seeds = [LiquidityPool::SOL_VAULT_PREFIX.as_bytes(), token_mint.key().as_ref()],
-        bump
+        bump = pool.vault_bump
     )]
     pub pool_sol_vault: AccountInfo<'info>,
 
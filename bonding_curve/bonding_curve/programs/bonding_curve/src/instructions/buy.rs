@@ -4,9 +4,19 @@ use anchor_spl::{
     token::{Mint, Token, TokenAccount},
 };
 
-use crate::state::{CurveConfiguration, LiquidityPool, LiquidityPoolAccount};
+use crate::{
+    errors::CustomError,
+    state::{
+        BonusMode, GlobalConfig, LiquidityPool, LiquidityPoolAccount, TierParticipation,
+        VestingEscrow,
+    },
+};
 
 pub fn handle(ctx: Context<Buy>, amount: u64) -> Result<()> {
+    ctx.accounts
+        .dex_configuration_account
+        .require_unpaused(&ctx.accounts.pool)?;
+
     // Debug logs for tracing
     msg!("🛒 [buy] amount (lamports budget): {}", amount);
     msg!(
@@ -23,6 +33,13 @@ pub fn handle(ctx: Context<Buy>, amount: u64) -> Result<()> {
     );
     msg!("🛒 [buy] pool.bump: {}", ctx.accounts.pool.bump);
 
+    init_buyer_pdas(
+        ctx.accounts.pool.key(),
+        ctx.accounts.user.key(),
+        ctx.accounts.vesting_escrow.as_deref_mut().zip(ctx.bumps.vesting_escrow),
+        ctx.accounts.tier_participation.as_deref_mut().zip(ctx.bumps.tier_participation),
+    );
+
     let pool = &mut ctx.accounts.pool;
 
     let token_accounts = (
@@ -32,25 +49,119 @@ pub fn handle(ctx: Context<Buy>, amount: u64) -> Result<()> {
     );
 
     // All gating (phase, cap, snapshot) and exact pricing happen in pool.buy(...)
-    pool.buy(
+    let vesting = ctx
+        .accounts
+        .vesting_escrow
+        .as_deref_mut()
+        .zip(ctx.accounts.vesting_token_account.as_deref_mut());
+
+    let tokens_out = pool.buy(
         token_accounts,
         &mut ctx.accounts.pool_sol_vault,
         amount,
+        vesting,
         &ctx.accounts.user,
         &ctx.accounts.token_program,
         &ctx.accounts.system_program,
+    )?;
+
+    let treasury = match (
+        ctx.accounts.treasury_pda.as_ref(),
+        ctx.bumps.treasury_pda,
+        ctx.accounts.treasury_token_account.as_deref(),
+    ) {
+        (Some(pda), Some(bump), Some(ata)) => Some((pda.as_ref(), bump, ata)),
+        _ => None,
+    };
+    settle_tiers(
+        pool,
+        tokens_out,
+        ctx.accounts.token_mint.decimals,
+        ctx.accounts.tier_participation.as_deref_mut(),
+        treasury,
+        &ctx.accounts.user_token_account,
+        &ctx.accounts.token_program,
     )
 }
 
+/// Fresh per-user PDAs (init_if_needed) get their identity on first use.
+pub(crate) fn init_buyer_pdas(
+    pool_key: Pubkey,
+    user_key: Pubkey,
+    vesting_escrow: Option<(&mut Account<VestingEscrow>, u8)>,
+    tier_participation: Option<(&mut Account<TierParticipation>, u8)>,
+) {
+    if let Some((escrow, bump)) = vesting_escrow {
+        if escrow.owner == Pubkey::default() {
+            escrow.set_inner(VestingEscrow::new(pool_key, user_key, bump));
+        }
+    }
+    if let Some((part, bump)) = tier_participation {
+        if part.owner == Pubkey::default() {
+            part.set_inner(TierParticipation::new(pool_key, user_key, bump));
+        }
+    }
+}
+
+/// 🏆 Tier bookkeeping and completion bonus for a buy of `tokens_out` that
+/// just moved `pool.total_sold`. `treasury` is (treasury PDA, bump, ATA).
+pub(crate) fn settle_tiers<'info>(
+    pool: &mut Account<'info, LiquidityPool>,
+    tokens_out: u64,
+    decimals: u8,
+    participation: Option<&mut Account<'info, TierParticipation>>,
+    treasury: Option<(&AccountInfo<'info>, u8, &Account<'info, TokenAccount>)>,
+    user_token_account: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
+    let y_after = pool.total_sold;
+    let y_before = y_after.saturating_sub(tokens_out);
+    let bonus_on = pool.bonus_per_tier > 0;
+
+    if bonus_on && pool.bonus_mode == BonusMode::ProRata {
+        require!(participation.is_some(), CustomError::BonusAccountsMissing);
+    }
+    // Only tiers completed for the first time pay; re-crossing after a sell-back doesn't
+    let completed = pool.record_tier_buy(y_before, y_after, participation.map(|p| &mut **p))?;
+
+    if bonus_on && pool.bonus_mode == BonusMode::CrossingBuyer && completed > 0 {
+        let Some((treasury_pda, treasury_bump, treasury_token_account)) = treasury else {
+            return err!(CustomError::BonusAccountsMissing);
+        };
+
+        let bonus = pool
+            .bonus_per_tier_base(decimals)
+            .checked_mul(completed)
+            .ok_or_else(|| error!(CustomError::OverflowOrUnderflowOccurred))?
+            .min(pool.bonus_pending(decimals))
+            .min(treasury_token_account.amount);
+
+        pool.transfer_token_from_treasury(
+            treasury_pda,
+            treasury_bump,
+            treasury_token_account,
+            user_token_account,
+            bonus,
+            token_program,
+        )?;
+        pool.bonus_paid = pool
+            .bonus_paid
+            .checked_add(bonus)
+            .ok_or_else(|| error!(CustomError::OverflowOrUnderflowOccurred))?;
+    }
+
+    Ok(())
+}
+
 #[derive(Accounts)]
 pub struct Buy<'info> {
     // Global config (present for future fee usage; currently not read in handler)
     #[account(
         mut,
-        seeds = [CurveConfiguration::SEED.as_bytes()],
+        seeds = [GlobalConfig::SEED.as_bytes()],
         bump,
     )]
-    pub dex_configuration_account: Box<Account<'info, CurveConfiguration>>,
+    pub dex_configuration_account: Box<Account<'info, GlobalConfig>>,
 
     // Pool PDA
     #[account(
@@ -76,7 +187,7 @@ pub struct Buy<'info> {
     #[account(
         mut,
         seeds = [LiquidityPool::SOL_VAULT_PREFIX.as_bytes(), token_mint.key().as_ref()],
-        bump
+        bump = pool.vault_bump
     )]
     /// CHECK: PDA vault holds only lamports; seeds enforced; owner checked at runtime.
     pub pool_sol_vault: AccountInfo<'info>,
@@ -99,4 +210,50 @@ pub struct Buy<'info> {
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub rent: Sysvar<'info, Rent>,
+
+    // --- optional, appended after the original accounts so clients that build
+    //     the list by position still line up; omitted = None ---
+
+    // Vesting escrow; required only when the buy lands inside vested tiers
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = VestingEscrow::ACCOUNT_SIZE,
+        seeds = [VestingEscrow::SEED_PREFIX.as_bytes(), pool.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub vesting_escrow: Option<Box<Account<'info, VestingEscrow>>>,
+
+    // Escrow's token ATA (authority = escrow PDA)
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = token_mint,
+        associated_token::authority = vesting_escrow,
+    )]
+    pub vesting_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    // Tier participation; required only for pro-rata bonus pools
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = TierParticipation::ACCOUNT_SIZE,
+        seeds = [TierParticipation::SEED_PREFIX.as_bytes(), pool.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub tier_participation: Option<Box<Account<'info, TierParticipation>>>,
+
+    /// CHECK: treasury PDA (owner of treasury ATA); crossing-bonus pools only
+    #[account(
+        seeds = [LiquidityPool::TREASURY_SEED_PREFIX.as_bytes(), token_mint.key().as_ref()],
+        bump
+    )]
+    pub treasury_pda: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = treasury_pda,
+    )]
+    pub treasury_token_account: Option<Box<Account<'info, TokenAccount>>>,
 }
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::{
+    errors::CustomError,
+    state::{GlobalConfig, LiquidityPool},
+};
+
+/// Calls off a pending emergency withdraw; a new one starts a fresh timelock.
+pub fn handle(ctx: Context<CancelEmergencyWithdraw>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    require!(pool.emergency_withdraw_slot > 0, CustomError::EmergencyWithdrawLocked);
+    pool.emergency_withdraw_slot = 0;
+
+    emit!(crate::EmergencyWithdrawCancelled {
+        pool: pool.key(),
+        admin: ctx.accounts.admin.key(),
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelEmergencyWithdraw<'info> {
+    #[account(
+        seeds = [GlobalConfig::SEED.as_bytes()],
+        bump,
+        has_one = admin @ CustomError::Unauthorized,
+    )]
+    pub dex_configuration_account: Box<Account<'info, GlobalConfig>>,
+
+    #[account(
+        mut,
+        seeds = [LiquidityPool::POOL_SEED_PREFIX.as_bytes(), token_mint.key().as_ref()],
+        bump = pool.bump,
+    )]
+    pub pool: Box<Account<'info, LiquidityPool>>,
+
+    pub token_mint: Box<Account<'info, Mint>>,
+
+    pub admin: Signer<'info>,
+}
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::{
+    errors::CustomError,
+    state::{LiquidityPool, PoolPhase},
+};
+
+/// Permissionless health check of an Active curve. Never fails on a broken
+/// invariant (trades already refuse to create one); it reports via an event
+/// and the return data so drift from outside the trade paths is visible.
+pub fn handle(ctx: Context<CheckInvariants>) -> Result<crate::InvariantsChecked> {
+    let pool = &ctx.accounts.pool;
+    require!(matches!(pool.phase, PoolPhase::Active), CustomError::BadPhase);
+
+    let decimals = ctx.accounts.token_mint.decimals;
+    let vault_lamports = ctx.accounts.pool_sol_vault.lamports();
+    let curve_lamports = vault_lamports as i64 - pool.vault_floor()? as i64;
+    let report = crate::InvariantsChecked {
+        pool: pool.key(),
+        vault_lamports,
+        reserve_sol: pool.reserve_sol,
+        required_sol: pool.required_sol(decimals)?,
+        reserve_drift: curve_lamports - pool.reserve_sol as i64,
+        ok: pool.check_invariants(vault_lamports, decimals).is_ok(),
+    };
+
+    emit!(report);
+    Ok(report)
+}
+
+#[derive(Accounts)]
+pub struct CheckInvariants<'info> {
+    #[account(
+        seeds = [LiquidityPool::POOL_SEED_PREFIX.as_bytes(), token_mint.key().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Box<Account<'info, LiquidityPool>>,
+
+    pub token_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: SOL vault PDA (read-only)
+    #[account(
+        seeds = [LiquidityPool::SOL_VAULT_PREFIX.as_bytes(), token_mint.key().as_ref()],
+        bump = pool.vault_bump
+    )]
+    pub pool_sol_vault: AccountInfo<'info>,
+}
@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token, TokenAccount},
+};
+
+use crate::{
+    errors::CustomError,
+    state::{BonusMode, LiquidityPool, LiquidityPoolAccount, TierParticipation},
+    utils::curve::tier_ends_base,
+};
+
+pub fn handle(ctx: Context<ClaimTierBonus>, tier: u8) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let part = &mut ctx.accounts.tier_participation;
+    let i = tier as usize;
+
+    require!(
+        pool.bonus_per_tier > 0 && pool.bonus_mode == BonusMode::ProRata,
+        CustomError::BonusNotEnabled
+    );
+
+    // Tier must have been completed (it stays claimable if sold back below its end)
+    let decimals = ctx.accounts.token_mint.decimals;
+    require!(i < tier_ends_base(&pool.tiers).len(), CustomError::InvalidTierSchedule);
+    require!(pool.bonus_paid_tiers & (1u8 << i) != 0, CustomError::TierNotCompleted);
+
+    require!(part.claimed_mask & (1u8 << i) == 0, CustomError::BonusAlreadyClaimed);
+    require!(part.bought[i] > 0 && pool.tier_bought[i] > 0, CustomError::NothingToClaim);
+
+    // Pro-rata share of this tier's bonus, rounded on the running total of
+    // claimed weight so the shares add up to the whole bonus
+    let share_of = |weight: u64| {
+        ((pool.bonus_per_tier_base(decimals) as u128) * (weight as u128)
+            / (pool.tier_bought[i] as u128)) as u64
+    };
+    let claimed_weight = pool.tier_bonus_claimed[i];
+    let amount = share_of(claimed_weight.saturating_add(part.bought[i]))
+        .saturating_sub(share_of(claimed_weight))
+        .min(pool.bonus_pending(decimals))
+        .min(ctx.accounts.treasury_token_account.amount);
+    require!(amount > 0, CustomError::NothingToClaim);
+
+    part.claimed_mask |= 1u8 << i;
+    pool.tier_bonus_claimed[i] = claimed_weight.saturating_add(part.bought[i]);
+    pool.bonus_paid = pool
+        .bonus_paid
+        .checked_add(amount)
+        .ok_or_else(|| error!(CustomError::OverflowOrUnderflowOccurred))?;
+
+    pool.transfer_token_from_treasury(
+        &ctx.accounts.treasury_pda.to_account_info(),
+        ctx.bumps.treasury_pda,
+        &ctx.accounts.treasury_token_account,
+        &ctx.accounts.user_token_account,
+        amount,
+        &ctx.accounts.token_program,
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimTierBonus<'info> {
+    // Pool PDA (bonus config and per-tier totals)
+    #[account(
+        mut,
+        seeds = [LiquidityPool::POOL_SEED_PREFIX.as_bytes(), token_mint.key().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Box<Account<'info, LiquidityPool>>,
+
+    pub token_mint: Box<Account<'info, Mint>>,
+
+    // Buyer's participation record (seeds bind it to this pool and this user)
+    #[account(
+        mut,
+        seeds = [TierParticipation::SEED_PREFIX.as_bytes(), pool.key().as_ref(), user.key().as_ref()],
+        bump = tier_participation.bump
+    )]
+    pub tier_participation: Box<Account<'info, TierParticipation>>,
+
+    /// CHECK: treasury PDA (owner of treasury ATA)
+    #[account(
+        seeds = [LiquidityPool::TREASURY_SEED_PREFIX.as_bytes(), token_mint.key().as_ref()],
+        bump
+    )]
+    pub treasury_pda: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = treasury_pda,
+    )]
+    pub treasury_token_account: Box<Account<'info, TokenAccount>>,
+
+    // User's token ATA (auto-create if missing)
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = token_mint,
+        associated_token::authority = user,
+    )]
+    pub user_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
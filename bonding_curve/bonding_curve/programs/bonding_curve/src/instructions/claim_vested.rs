@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, Token, TokenAccount},
+};
+
+use crate::{
+    errors::CustomError,
+    state::{LiquidityPool, VestingEscrow},
+};
+
+pub fn handle(ctx: Context<ClaimVested>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let escrow = &mut ctx.accounts.vesting_escrow;
+
+    let amount = escrow.claimable(now, ctx.accounts.pool.vesting_duration);
+    require!(amount > 0, CustomError::NothingToClaim);
+
+    escrow.claimed = escrow
+        .claimed
+        .checked_add(amount)
+        .ok_or_else(|| error!(CustomError::OverflowOrUnderflowOccurred))?;
+    ctx.accounts.pool.vesting_unclaimed = ctx.accounts.pool.vesting_unclaimed.saturating_sub(amount);
+
+    // Escrow PDA signs for its ATA
+    let pool_key = ctx.accounts.pool.key();
+    let owner_key = escrow.owner;
+    let seeds = &[
+        VestingEscrow::SEED_PREFIX.as_bytes(),
+        pool_key.as_ref(),
+        owner_key.as_ref(),
+        &[escrow.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.vesting_token_account.to_account_info(),
+                to: ctx.accounts.user_token_account.to_account_info(),
+                authority: escrow.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimVested<'info> {
+    // Pool PDA (vesting duration lives here)
+    #[account(
+        mut,
+        seeds = [LiquidityPool::POOL_SEED_PREFIX.as_bytes(), token_mint.key().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Box<Account<'info, LiquidityPool>>,
+
+    pub token_mint: Box<Account<'info, Mint>>,
+
+    // Buyer's escrow (seeds bind it to this pool and this user)
+    #[account(
+        mut,
+        seeds = [VestingEscrow::SEED_PREFIX.as_bytes(), pool.key().as_ref(), user.key().as_ref()],
+        bump = vesting_escrow.bump
+    )]
+    pub vesting_escrow: Box<Account<'info, VestingEscrow>>,
+
+    // Escrow's token ATA (authority = escrow PDA)
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = vesting_escrow,
+    )]
+    pub vesting_token_account: Box<Account<'info, TokenAccount>>,
+
+    // User's token ATA (auto-create if missing)
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = token_mint,
+        associated_token::authority = user,
+    )]
+    pub user_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
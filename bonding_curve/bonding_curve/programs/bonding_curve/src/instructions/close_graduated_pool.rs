@@ -0,0 +1,223 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::{
+    token::{self, Mint, Token, TokenAccount},
+    token_interface::TokenAccount as LpTokenAccount,
+};
+
+use crate::{
+    errors::CustomError,
+    state::{GraduatedPool, LiquidityPool, PoolPhase},
+};
+
+/// Permissionless cleanup once a pool has graduated and nothing still reads
+/// it: claim_vested, claim_tier_bonus and release_lp all load the full pool,
+/// so every vested token and tier bonus must be claimed and the LP burned,
+/// locked for good or released. Closes the pool ATA, empties the SOL vault
+/// and any lamports sent to the migration escrow since finalize swept it, and
+/// shrinks the pool PDA into a `GraduatedPool`. Pool/ATA/vault rent goes to
+/// the creator who paid it; dust to the protocol.
+pub fn handle(ctx: Context<CloseGraduatedPool>) -> Result<()> {
+    let pool_info = ctx.accounts.pool.to_account_info();
+    require_keys_eq!(*pool_info.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+
+    let pool = {
+        let data = pool_info.try_borrow_data()?;
+        LiquidityPool::try_deserialize(&mut &data[..])?
+    };
+    require!(matches!(pool.phase, PoolPhase::Graduated), CustomError::BadPhase);
+    require_keys_eq!(pool.creator, ctx.accounts.creator.key(), CustomError::NotCreator);
+
+    // 1) Claims must be settled, and the LP: burned, locked for good, or already released
+    require!(
+        pool.vesting_unclaimed == 0 && pool.bonus_pending(ctx.accounts.token_mint.decimals) == 0,
+        CustomError::ClaimsOutstanding
+    );
+    if !pool.lp_burn && pool.lp_recipient != Pubkey::default() {
+        let released = ctx
+            .accounts
+            .lp_escrow_token_account
+            .as_ref()
+            .is_some_and(|a| pool.lp_timelock == Some(a.key()) && a.amount == 0);
+        require!(released, CustomError::LpStillLocked);
+    }
+
+    let mint_key = ctx.accounts.token_mint.key();
+    let pool_seeds = &[
+        LiquidityPool::POOL_SEED_PREFIX.as_bytes(),
+        mint_key.as_ref(),
+        &[pool.bump],
+    ];
+    let pool_signer = &[&pool_seeds[..]];
+
+    // 2) Pool ATA: burn anything sent there after migration, then close it
+    let stray = ctx.accounts.pool_token_account.amount;
+    if stray > 0 {
+        token::burn(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::Burn {
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    from: ctx.accounts.pool_token_account.to_account_info(),
+                    authority: pool_info.clone(),
+                },
+                pool_signer,
+            ),
+            stray,
+        )?;
+    }
+    token::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        token::CloseAccount {
+            account: ctx.accounts.pool_token_account.to_account_info(),
+            destination: ctx.accounts.creator.to_account_info(),
+            authority: pool_info.clone(),
+        },
+        pool_signer,
+    ))?;
+
+    // 3) SOL vault: its rent back to the creator, any dust -> protocol fee PDA.
+    //    The migration escrow's lamports (finalize left it empty) are dust too.
+    let vault_lamports = ctx.accounts.pool_sol_vault.lamports();
+    let vault_rent = vault_lamports.min(Rent::get()?.minimum_balance(0));
+    let escrow_dust = ctx.accounts.migration_escrow.lamports();
+    let dust = vault_lamports - vault_rent + escrow_dust;
+    let vault_seeds = &[
+        LiquidityPool::SOL_VAULT_PREFIX.as_bytes(),
+        mint_key.as_ref(),
+        &[ctx.bumps.pool_sol_vault],
+    ];
+    let escrow_seeds = &[
+        LiquidityPool::MIGRATION_ESCROW_PREFIX.as_bytes(),
+        mint_key.as_ref(),
+        &[ctx.bumps.migration_escrow],
+    ];
+    for (from, seeds, to, amount) in [
+        (
+            &ctx.accounts.pool_sol_vault,
+            &vault_seeds[..],
+            ctx.accounts.protocol_fee_vault.to_account_info(),
+            vault_lamports - vault_rent,
+        ),
+        (
+            &ctx.accounts.pool_sol_vault,
+            &vault_seeds[..],
+            ctx.accounts.creator.to_account_info(),
+            vault_rent,
+        ),
+        (
+            &ctx.accounts.migration_escrow,
+            &escrow_seeds[..],
+            ctx.accounts.protocol_fee_vault.to_account_info(),
+            escrow_dust,
+        ),
+    ] {
+        if amount > 0 {
+            system_program::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: from.to_account_info(),
+                        to,
+                    },
+                    &[seeds],
+                ),
+                amount,
+            )?;
+        }
+    }
+
+    // 4) Rewrite the pool PDA as a GraduatedPool and hand back the freed rent
+    let record = GraduatedPool {
+        token: pool.token,
+        creator: pool.creator,
+        amm_kind: pool.amm_kind,
+        amm_program: pool.amm_program,
+        amm_pool: pool.amm_pool.ok_or_else(|| error!(CustomError::InvalidAmmPool))?,
+        lp_mint: pool.lp_mint,
+        lp_timelock: pool.lp_timelock,
+        lp_unlock_slot: pool.lp_unlock_slot,
+        graduation_slot: pool.graduation_slot,
+        bump: pool.bump,
+    };
+    pool_info.realloc(GraduatedPool::ACCOUNT_SIZE, false)?;
+    {
+        let mut data = pool_info.try_borrow_mut_data()?;
+        record.try_serialize(&mut &mut data[..])?;
+    }
+
+    let refund = pool_info
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(GraduatedPool::ACCOUNT_SIZE));
+    **pool_info.try_borrow_mut_lamports()? -= refund;
+    **ctx.accounts.creator.try_borrow_mut_lamports()? += refund;
+    let refund = refund + vault_rent;
+
+    emit!(crate::GraduatedPoolClosed {
+        pool: pool_info.key(),
+        amm_pool: record.amm_pool,
+        rent_refunded: refund,
+        dust_swept: dust,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseGraduatedPool<'info> {
+    /// CHECK: pool PDA; decoded by hand since it is rewritten as a GraduatedPool
+    #[account(
+        mut,
+        seeds = [LiquidityPool::POOL_SEED_PREFIX.as_bytes(), token_mint.key().as_ref()],
+        bump
+    )]
+    pub pool: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub token_mint: Box<Account<'info, Mint>>,
+
+    // pool ATA, empty since start_migration
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = pool
+    )]
+    pub pool_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: SOL vault PDA
+    #[account(
+        mut,
+        seeds = [LiquidityPool::SOL_VAULT_PREFIX.as_bytes(), token_mint.key().as_ref()],
+        bump
+    )]
+    pub pool_sol_vault: AccountInfo<'info>,
+
+    /// CHECK: migration escrow PDA (system-owned); finalize emptied it, anything since is dust
+    #[account(
+        mut,
+        seeds = [LiquidityPool::MIGRATION_ESCROW_PREFIX.as_bytes(), token_mint.key().as_ref()],
+        bump
+    )]
+    pub migration_escrow: AccountInfo<'info>,
+
+    /// CHECK: protocol fee PDA (system-owned, lamports only); receives the vault dust
+    #[account(
+        mut,
+        seeds = [LiquidityPool::PROTOCOL_FEE_PREFIX.as_bytes()],
+        bump
+    )]
+    pub protocol_fee_vault: AccountInfo<'info>,
+
+    /// CHECK: pool creator (checked after decoding); receives the pool and ATA rent
+    #[account(mut)]
+    pub creator: AccountInfo<'info>,
+
+    // The pool's LP escrow ATA; only needed for a timed lock, to show it was released
+    pub lp_escrow_token_account: Option<InterfaceAccount<'info, LpTokenAccount>>,
+
+    // Anyone
+    pub caller: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
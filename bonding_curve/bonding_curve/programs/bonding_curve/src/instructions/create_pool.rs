@@ -1,30 +1,192 @@
-use crate::state::*;
+use crate::{consts::{SOLD_CAP, TREASURY_SUPPLY}, errors::CustomError, state::*, utils::curve::LUT_DECIMALS};
 use anchor_lang::prelude::*;
+use anchor_lang::system_program;
 use anchor_spl::{
     associated_token::AssociatedToken,
     token::{Mint, Token, TokenAccount},
 };
 
+#[allow(clippy::too_many_arguments)]
 pub fn handle(
     ctx: Context<CreatePool>,            // ⬅️ rename
     migration_authority: Pubkey,        // ⬅️ arg
+    tiers: Vec<Tier>,                   // empty = legacy three equal tiers
+    tier_ratchet: bool,                 // sells cannot unwind completed tiers
+    vesting: Option<VestingConfig>,     // vest buys in early tiers
+    bonus: Option<TierBonusConfig>,     // tier-completion bonus from the treasury
+    lp_lock: Option<LpLockConfig>,      // LP release/burn after migration (None = locked for good)
+    amm: AmmTarget,                     // AMM the pool graduates to
+    launch_deadline: Option<i64>,       // unix time the cap must be reached by (None = no expiry)
+    creator_bonus_bps: Option<u16>,     // creator's cut of the curve SOL at graduation
 ) -> Result<()> {
+    let config = &ctx.accounts.dex_configuration_account;
+    validate_pool_params(
+        config,
+        &tiers,
+        vesting.as_ref(),
+        bonus.as_ref(),
+        lp_lock.as_ref(),
+        &amm,
+        launch_deadline,
+        creator_bonus_bps,
+    )?;
+    validate_mint(
+        &ctx.accounts.token_mint,
+        &ctx.accounts.pool_token_account,
+        &ctx.accounts.treasury_token_account,
+    )?;
+
+    let migration_fee_bps = config.migration_fee_bps;
     let pool = &mut ctx.accounts.pool;
 
     pool.set_inner(LiquidityPool::new(
         ctx.accounts.payer.key(),
         ctx.accounts.token_mint.key(),
         ctx.bumps.pool,
+        ctx.bumps.pool_sol_vault,
+        tiers,
+        tier_ratchet,
+        vesting,
+        bonus,
+        lp_lock,
+        amm,
+        launch_deadline,
+        migration_fee_bps,
+        creator_bonus_bps,
     ));
 
     // store backend key
     pool.migration_authority = migration_authority;
 
+    // the curve starts from the whole sold cap, already sitting in the pool ATA
+    pool.total_supply = ctx.accounts.token_mint.supply;
+    pool.update_reserves(ctx.accounts.pool_token_account.amount, 0)?;
+
+    init_sol_vault(
+        &ctx.accounts.pool_sol_vault,
+        ctx.bumps.pool_sol_vault,
+        &ctx.accounts.token_mint.key(),
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+    )
+}
+
+/// Pool parameters as chosen by the creator, checked against the config.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn validate_pool_params(
+    config: &GlobalConfig,
+    tiers: &[Tier],
+    vesting: Option<&VestingConfig>,
+    bonus: Option<&TierBonusConfig>,
+    lp_lock: Option<&LpLockConfig>,
+    amm: &AmmTarget,
+    launch_deadline: Option<i64>,
+    creator_bonus_bps: Option<u16>,
+) -> Result<()> {
+    Tier::validate_schedule(tiers)?;
+    if let Some(v) = vesting {
+        v.validate(tiers)?;
+    }
+    if let Some(b) = bonus {
+        b.validate(tiers)?;
+    }
+    if let Some(l) = lp_lock {
+        l.validate()?;
+    }
+    amm.validate(config)?;
+    if let Some(d) = launch_deadline {
+        require!(d > Clock::get()?.unix_timestamp, CustomError::InvalidLaunchDeadline);
+    }
+    if let Some(b) = creator_bonus_bps {
+        require!(b <= config.max_creator_bonus_bps, CustomError::InvalidGraduationBonus);
+    }
+    Ok(())
+}
+
+/// SOL vault: a rent-exempt system account from the start, payer funds the
+/// rent. Lamports someone sent there beforehand only top it up (and are
+/// donations past the rent).
+pub(crate) fn init_sol_vault<'info>(
+    vault: &AccountInfo<'info>,
+    vault_bump: u8,
+    mint_key: &Pubkey,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(0);
+    if vault.lamports() == 0 {
+        let vault_seeds = &[
+            LiquidityPool::SOL_VAULT_PREFIX.as_bytes(),
+            mint_key.as_ref(),
+            &[vault_bump],
+        ];
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.to_account_info(),
+                system_program::CreateAccount {
+                    from: payer.to_account_info(),
+                    to: vault.to_account_info(),
+                },
+                &[&vault_seeds[..]],
+            ),
+            rent,
+            0,
+            &system_program::ID,
+        )?;
+    } else if vault.lamports() < rent {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: vault.to_account_info(),
+                },
+            ),
+            rent - vault.lamports(),
+        )?;
+    }
+    Ok(())
+}
+
+/// Fixed supply only: both authorities revoked, the curve's decimals, and the
+/// whole supply split exactly between the pool ATA (SOLD_CAP) and the
+/// treasury ATA (TREASURY_SUPPLY).
+pub(crate) fn validate_mint(
+    mint: &Mint,
+    pool_token_account: &TokenAccount,
+    treasury_token_account: &TokenAccount,
+) -> Result<()> {
+    require!(
+        mint.mint_authority.is_none() && mint.freeze_authority.is_none(),
+        CustomError::MintAuthorityNotRevoked
+    );
+    require!(mint.decimals == LUT_DECIMALS, CustomError::InvalidMintDecimals);
+
+    let unit = 10u64.pow(mint.decimals as u32);
+    let pool_base = SOLD_CAP
+        .checked_mul(unit)
+        .ok_or(CustomError::OverflowOrUnderflowOccurred)?;
+    let treasury_base = TREASURY_SUPPLY
+        .checked_mul(unit)
+        .ok_or(CustomError::OverflowOrUnderflowOccurred)?;
+    require!(
+        pool_token_account.amount == pool_base
+            && treasury_token_account.amount == treasury_base
+            && mint.supply == pool_base + treasury_base,
+        CustomError::InvalidSupplyDistribution
+    );
     Ok(())
 }
 
 #[derive(Accounts)]
 pub struct CreatePool<'info> {          // ⬅️ rename
+    // Global config
+    #[account(
+        seeds = [GlobalConfig::SEED.as_bytes()],
+        bump,
+    )]
+    pub dex_configuration_account: Box<Account<'info, GlobalConfig>>,
+
     #[account(
         init,
         space = LiquidityPool::ACCOUNT_SIZE,
@@ -34,18 +196,31 @@ pub struct CreatePool<'info> {          // ⬅️ rename
     )]
     pub pool: Box<Account<'info, LiquidityPool>>,
 
-    #[account(mut)]
+    // Read-only: validate_mint only inspects it
     pub token_mint: Box<Account<'info, Mint>>,
 
+    // Created and funded with the sold cap before this call
     #[account(
-        init,
-        payer = payer,
         associated_token::mint = token_mint,
         associated_token::authority = pool
     )]
     pub pool_token_account: Box<Account<'info, TokenAccount>>,
 
-    // Derived PDA (system-owned lamport vault); not created here
+    /// CHECK: treasury PDA (owner of treasury ATA)
+    #[account(
+        seeds = [LiquidityPool::TREASURY_SEED_PREFIX.as_bytes(), token_mint.key().as_ref()],
+        bump
+    )]
+    pub treasury_pda: AccountInfo<'info>,
+
+    // Holds TREASURY_SUPPLY
+    #[account(
+        associated_token::mint = token_mint,
+        associated_token::authority = treasury_pda
+    )]
+    pub treasury_token_account: Box<Account<'info, TokenAccount>>,
+
+    // Derived PDA (system-owned lamport vault); created rent-exempt here
     #[account(
         mut,
         seeds = [LiquidityPool::SOL_VAULT_PREFIX.as_bytes(), token_mint.key().as_ref()],
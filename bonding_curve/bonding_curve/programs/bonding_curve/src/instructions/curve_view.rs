@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+use anchor_spl::token::Mint;
+
+use crate::{
+    state::{LiquidityPool, PoolPhase},
+    utils::curve::{
+        cap_base, spot_price_sol_per_token, tier_ends_base, tier_ends_x, tier_index_for_y, x_from_y,
+        x_max,
+    },
+};
+
+/// Snapshot of the pool's position on its curve, returned via return data.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CurveView {
+    pub phase: PoolPhase,
+    pub spot_price_sol_per_token: f64,
+    pub current_tier: u8,
+    pub tier_count: u8,
+    pub tier_progress_bps: u16,     // tokens sold inside the current tier / its allocation
+    pub total_sold: u64,            // base units
+    pub tokens_remaining: u64,      // base units left until the cap
+    pub lamports_to_graduate: u64,  // SOL still needed to reach the cap
+    pub tier_end_prices: Vec<f64>,  // spot price at each tier boundary
+}
+
+pub fn handle(ctx: Context<GetCurveView>) -> Result<CurveView> {
+    let pool = &ctx.accounts.pool;
+    let tiers = &pool.tiers;
+    let decimals = ctx.accounts.token_mint.decimals;
+
+    // Same position the trade paths use
+    let y_sold = pool.total_sold;
+    let x = x_from_y(tiers, y_sold);
+
+    let ends = tier_ends_base(tiers);
+    let current = tier_index_for_y(tiers, y_sold);
+    let tier_start = if current == 0 { 0 } else { ends[current - 1] };
+    let tier_len = ends[current].saturating_sub(tier_start).max(1);
+    let in_tier = y_sold.saturating_sub(tier_start).min(tier_len);
+    let tier_progress_bps = ((in_tier as u128) * 10_000 / (tier_len as u128)) as u16;
+
+    let sol_left = (x_max(tiers) - x).max(0.0);
+    let lamports_to_graduate = (sol_left * (LAMPORTS_PER_SOL as f64)).ceil() as u64;
+
+    let tier_end_prices = tier_ends_x(tiers)
+        .into_iter()
+        .map(|xe| spot_price_sol_per_token(tiers, xe))
+        .collect();
+
+    Ok(CurveView {
+        phase: pool.phase,
+        spot_price_sol_per_token: spot_price_sol_per_token(tiers, x),
+        current_tier: current as u8,
+        tier_count: ends.len() as u8,
+        tier_progress_bps,
+        total_sold: y_sold,
+        tokens_remaining: cap_base(decimals).saturating_sub(y_sold),
+        lamports_to_graduate,
+        tier_end_prices,
+    })
+}
+
+#[derive(Accounts)]
+pub struct GetCurveView<'info> {
+    // Pool PDA (read-only)
+    #[account(
+        seeds = [LiquidityPool::POOL_SEED_PREFIX.as_bytes(), token_mint.key().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Box<Account<'info, LiquidityPool>>,
+
+    // Token mint (seeds + decimals)
+    pub token_mint: Box<Account<'info, Mint>>,
+}
@@ -1,32 +1,316 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::Mint;
+use anchor_lang::system_program;
+use anchor_spl::{
+    associated_token::{self, get_associated_token_address_with_program_id, AssociatedToken},
+    token::{self, spl_token::native_mint, Mint, Token, TokenAccount},
+    token_interface::{self, TokenInterface},
+};
 
 use crate::{
+    consts::{AMM_CREATION_RESERVE_LAMPORTS, MIGRATION_CRANK_REWARD_LAMPORTS},
     errors::CustomError,
-    state::{LiquidityPool, PoolPhase},
+    state::{GlobalConfig, LiquidityPool, LiquidityPoolAccount, PoolPhase},
+    utils::amm,
 };
 
-pub fn handle(ctx: Context<FinalizeMigration>) -> Result<()> {
+pub fn handle<'info>(ctx: Context<'_, '_, 'info, 'info, FinalizeMigration<'info>>) -> Result<()> {
+    ctx.accounts
+        .dex_configuration_account
+        .require_unpaused(&ctx.accounts.pool)?;
+
     let pool = &mut ctx.accounts.pool;
 
-    // Must be in Migrating phase to finalize
+    let clock = Clock::get()?;
+
+    // Must be in Migrating phase, with the curve escrowed by start_migration
     require!(matches!(pool.phase, PoolPhase::Migrating), CustomError::BadPhase);
+    require!(pool.reserve_snapshot_sol > 0, CustomError::MigrationNotStarted);
+
+    // Authority any time; anyone once the grace window has passed
+    let caller_key = ctx.accounts.caller.key();
+    require!(
+        pool.may_drive_migration(&caller_key, clock.slot),
+        CustomError::Unauthorized
+    );
+
+    let decimals = ctx.accounts.token_mint.decimals;
+    let mint_key = ctx.accounts.token_mint.key();
+    let escrow_seeds = &[
+        LiquidityPool::MIGRATION_ESCROW_PREFIX.as_bytes(),
+        mint_key.as_ref(),
+        &[ctx.bumps.migration_escrow],
+    ];
+    let escrow_signer = &[&escrow_seeds[..]];
+
+    // 1) Treasury (minus unclaimed bonuses) joins the curve tokens start_migration escrowed
+    let treasury_token_amount = ctx
+        .accounts
+        .treasury_token_account
+        .amount
+        .saturating_sub(pool.bonus_pending(decimals));
+    if treasury_token_amount > 0 {
+        pool.transfer_token_from_treasury(
+            &ctx.accounts.treasury_pda.to_account_info(),
+            ctx.bumps.treasury_pda,
+            &ctx.accounts.treasury_token_account,
+            &ctx.accounts.escrow_token_account,
+            treasury_token_amount,
+            &ctx.accounts.token_program,
+        )?;
+    }
+    let token_amount = ctx
+        .accounts
+        .escrow_token_account
+        .amount
+        .checked_add(treasury_token_amount)
+        .ok_or_else(|| error!(CustomError::OverflowOrUnderflowOccurred))?;
+
+    // 2) Graduation cuts of the curve SOL start_migration escrowed (the same
+    //    figures its MigrationStarted event reported): protocol fee PDA, then creator
+    let (migration_fee, creator_bonus) = pool.graduation_cuts(pool.reserve_snapshot_sol);
+    for (to, amount) in [
+        (ctx.accounts.protocol_fee_vault.to_account_info(), migration_fee),
+        (ctx.accounts.creator.to_account_info(), creator_bonus),
+    ] {
+        if amount > 0 {
+            system_program::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.migration_escrow.to_account_info(),
+                        to,
+                    },
+                    escrow_signer,
+                ),
+                amount,
+            )?;
+        }
+    }
+
+    // 3) The vault: once start_migration escrowed the curve SOL it holds only
+    //    its rent and the creator's seed, which never joined the curve. A
+    //    non-authority crank is paid its bounty out of that seed, so the AMM
+    //    still gets everything escrowed; the rest of the seed goes back.
+    let crank_reward = if caller_key == pool.migration_authority {
+        0
+    } else {
+        MIGRATION_CRANK_REWARD_LAMPORTS.min(pool.seed_sol)
+    };
+    let seed_returned = pool.seed_sol - crank_reward;
+    let vault_seeds = &[
+        LiquidityPool::SOL_VAULT_PREFIX.as_bytes(),
+        mint_key.as_ref(),
+        &[pool.vault_bump],
+    ];
+    for (to, amount) in [
+        (ctx.accounts.caller.to_account_info(), crank_reward),
+        (ctx.accounts.creator.to_account_info(), seed_returned),
+    ] {
+        if amount > 0 {
+            system_program::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.pool_sol_vault.to_account_info(),
+                        to,
+                    },
+                    &[&vault_seeds[..]],
+                ),
+                amount,
+            )?;
+        }
+    }
+    pool.seed_sol = 0;
+
+    // 4) Remaining escrow lamports (minus AMM creation costs) -> escrow's wSOL ATA
+    let escrow_lamports = ctx.accounts.migration_escrow.lamports();
+    let sol_amount = escrow_lamports
+        .checked_sub(AMM_CREATION_RESERVE_LAMPORTS)
+        .filter(|&v| v > 0)
+        .ok_or_else(|| error!(CustomError::NotEnoughSolInVault))?;
+
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.migration_escrow.to_account_info(),
+                to: ctx.accounts.escrow_wsol_account.to_account_info(),
+            },
+            escrow_signer,
+        ),
+        sol_amount,
+    )?;
+    token::sync_native(CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        token::SyncNative {
+            account: ctx.accounts.escrow_wsol_account.to_account_info(),
+        },
+    ))?;
+
+    // 5) CPI: seed the pool's AMM with the escrow PDA as creator
+    let kind = pool.amm_kind;
+    let amm_pool = ctx.accounts.amm_pool.to_account_info();
+    let accs = amm::SeedAccounts {
+        amm_program: &ctx.accounts.amm_program.to_account_info(),
+        creator: &ctx.accounts.migration_escrow.to_account_info(),
+        token_mint: &ctx.accounts.token_mint.to_account_info(),
+        wsol_mint: &ctx.accounts.wsol_mint.to_account_info(),
+        creator_token: &ctx.accounts.escrow_token_account.to_account_info(),
+        creator_wsol: &ctx.accounts.escrow_wsol_account.to_account_info(),
+        amm_pool: &amm_pool,
+        lp_mint: &ctx.accounts.amm_lp_mint.to_account_info(),
+        creator_lp: &ctx.accounts.escrow_lp_account.to_account_info(),
+        token_program: &ctx.accounts.token_program.to_account_info(),
+        lp_token_program: &ctx.accounts.lp_token_program.to_account_info(),
+        associated_token_program: &ctx.accounts.associated_token_program.to_account_info(),
+        system_program: &ctx.accounts.system_program.to_account_info(),
+        rent: &ctx.accounts.rent.to_account_info(),
+        extra: ctx.remaining_accounts,
+    };
+    amm::seed_pool(kind, &accs, token_amount, sol_amount, escrow_signer)?;
+
+    // 6) Verify the pool before trusting it: allowlisted owner, our two mints,
+    //    and at least the snapshot reserves (SOL side net of the AMM reserve),
+    //    less the adapter's rounding tolerance
+    require!(
+        ctx.accounts.dex_configuration_account.allows_amm(kind, amm_pool.owner)
+            && amm_pool.owner == ctx.accounts.amm_program.key,
+        CustomError::InvalidProgram
+    );
+    let reserves = amm::read_reserves(kind, &accs)?;
+    require!(
+        reserves.token >= amm::deposit_floor(kind, pool.reserve_snapshot_token)
+            && reserves.wsol
+                >= amm::deposit_floor(
+                    kind,
+                    pool.reserve_snapshot_sol
+                        .saturating_sub(AMM_CREATION_RESERVE_LAMPORTS + migration_fee + creator_bonus),
+                ),
+        CustomError::AmmPoolUnderfunded
+    );
+
+    // 7) LP: burn it, or park it in the program-owned escrow until lp_unlock_slot
+    let lp_amount = {
+        let data = ctx.accounts.escrow_lp_account.try_borrow_data()?;
+        token_interface::TokenAccount::try_deserialize(&mut &data[..])?.amount
+    };
+    let lp_decimals = {
+        let data = ctx.accounts.amm_lp_mint.try_borrow_data()?;
+        token_interface::Mint::try_deserialize(&mut &data[..])?.decimals
+    };
+    pool.lp_mint = Some(ctx.accounts.amm_lp_mint.key());
 
-    // Record the Raydium pool id
-    pool.raydium_pool = Some(ctx.accounts.raydium_pool.key());
+    if pool.lp_burn {
+        token_interface::burn(
+            CpiContext::new_with_signer(
+                ctx.accounts.lp_token_program.to_account_info(),
+                token_interface::Burn {
+                    mint: ctx.accounts.amm_lp_mint.to_account_info(),
+                    from: ctx.accounts.escrow_lp_account.to_account_info(),
+                    authority: ctx.accounts.migration_escrow.to_account_info(),
+                },
+                escrow_signer,
+            ),
+            lp_amount,
+        )?;
+        pool.lp_timelock = None;
+    } else {
+        // LP mint only exists after the CPI, so the escrow ATA is created here
+        associated_token::create(CpiContext::new(
+            ctx.accounts.associated_token_program.to_account_info(),
+            associated_token::Create {
+                payer: ctx.accounts.caller.to_account_info(),
+                associated_token: ctx.accounts.lp_escrow_token_account.to_account_info(),
+                authority: ctx.accounts.lp_escrow.to_account_info(),
+                mint: ctx.accounts.amm_lp_mint.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                token_program: ctx.accounts.lp_token_program.to_account_info(),
+            },
+        ))?;
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.lp_token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.escrow_lp_account.to_account_info(),
+                    mint: ctx.accounts.amm_lp_mint.to_account_info(),
+                    to: ctx.accounts.lp_escrow_token_account.to_account_info(),
+                    authority: ctx.accounts.migration_escrow.to_account_info(),
+                },
+                escrow_signer,
+            ),
+            lp_amount,
+            lp_decimals,
+        )?;
+        pool.lp_unlock_slot = clock.slot.saturating_add(pool.lp_lock_slots);
+        pool.lp_timelock = Some(ctx.accounts.lp_escrow_token_account.key());
+    }
 
-    // Optional LP timelock can be passed as the first remaining account
-    if let Some(acc) = ctx.remaining_accounts.get(0) {
-        pool.lp_timelock = Some(acc.key());
+    // 8) Sweep the escrow: burn the token dust the AMM's rounding left, close
+    //    both seed ATAs, and hand what the AMM didn't spend of its creation
+    //    reserve (plus the rent) to the creator
+    ctx.accounts.escrow_token_account.reload()?;
+    let token_dust = ctx.accounts.escrow_token_account.amount;
+    if token_dust > 0 {
+        token::burn(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::Burn {
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    from: ctx.accounts.escrow_token_account.to_account_info(),
+                    authority: ctx.accounts.migration_escrow.to_account_info(),
+                },
+                escrow_signer,
+            ),
+            token_dust,
+        )?;
+    }
+    for account in [
+        ctx.accounts.escrow_token_account.to_account_info(),
+        ctx.accounts.escrow_wsol_account.to_account_info(),
+    ] {
+        token::close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::CloseAccount {
+                account,
+                destination: ctx.accounts.creator.to_account_info(),
+                authority: ctx.accounts.migration_escrow.to_account_info(),
+            },
+            escrow_signer,
+        ))?;
     }
+    let reserve_returned = ctx.accounts.migration_escrow.lamports();
+    if reserve_returned > 0 {
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.migration_escrow.to_account_info(),
+                    to: ctx.accounts.creator.to_account_info(),
+                },
+                escrow_signer,
+            ),
+            reserve_returned,
+        )?;
+    }
+
+    // Record the AMM pool id
+    pool.amm_pool = Some(ctx.accounts.amm_pool.key());
 
-    // Flip to RaydiumLive
-    pool.phase = PoolPhase::RaydiumLive;
+    // Flip to Graduated
+    pool.phase = PoolPhase::Graduated;
+    pool.graduation_slot = clock.slot;
 
     emit!(crate::MigrationFinalized {
         pool: pool.key(),
-        raydium_pool: ctx.accounts.raydium_pool.key(),
+        amm_pool: ctx.accounts.amm_pool.key(),
         lp_timelock: pool.lp_timelock,
+        migration_fee,
+        creator_bonus,
+        seed_returned,
+        crank_reward,
+        reserve_returned,
+        token_seeded: token_amount,
+        sol_seeded: sol_amount,
     });
 
     Ok(())
@@ -34,21 +318,131 @@ pub fn handle(ctx: Context<FinalizeMigration>) -> Result<()> {
 
 #[derive(Accounts)]
 pub struct FinalizeMigration<'info> {
+    // Global config
+    #[account(
+        seeds = [GlobalConfig::SEED.as_bytes()],
+        bump,
+    )]
+    pub dex_configuration_account: Box<Account<'info, GlobalConfig>>,
+
     // Pool PDA
     #[account(
         mut,
         seeds = [LiquidityPool::POOL_SEED_PREFIX.as_bytes(), token_mint.key().as_ref()],
         bump = pool.bump,
-        has_one = migration_authority @ CustomError::Unauthorized,
     )]
     pub pool: Box<Account<'info, LiquidityPool>>,
 
     // Token mint (used in seeds)
     pub token_mint: Box<Account<'info, Mint>>,
 
-    // Authority allowed to finalize
-    pub migration_authority: Signer<'info>,
+    #[account(address = native_mint::ID)]
+    pub wsol_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: treasury PDA (owner of treasury ATA)
+    #[account(
+        seeds = [LiquidityPool::TREASURY_SEED_PREFIX.as_bytes(), token_mint.key().as_ref()],
+        bump
+    )]
+    pub treasury_pda: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = treasury_pda
+    )]
+    pub treasury_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: migration escrow PDA; acts as the AMM creator (pays AMM rent, owns the seed ATAs)
+    #[account(
+        mut,
+        seeds = [LiquidityPool::MIGRATION_ESCROW_PREFIX.as_bytes(), token_mint.key().as_ref()],
+        bump
+    )]
+    pub migration_escrow: AccountInfo<'info>,
+
+    // Escrow's token ATA; filled by start_migration, seeds the AMM's token side
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = migration_escrow
+    )]
+    pub escrow_token_account: Box<Account<'info, TokenAccount>>,
+
+    // Escrow's wSOL ATA that seeds the AMM's SOL side
+    #[account(
+        init_if_needed,
+        payer = caller,
+        associated_token::mint = wsol_mint,
+        associated_token::authority = migration_escrow
+    )]
+    pub escrow_wsol_account: Box<Account<'info, TokenAccount>>,
+
+    // migration_authority, or anyone after MIGRATION_GRACE_SLOTS (pays for the ATAs)
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    /// CHECK: protocol fee PDA (system-owned, lamports only); receives the migration fee
+    #[account(
+        mut,
+        seeds = [LiquidityPool::PROTOCOL_FEE_PREFIX.as_bytes()],
+        bump
+    )]
+    pub protocol_fee_vault: AccountInfo<'info>,
+
+    /// CHECK: pool creator; receives the graduation bonus, the SOL seed and the escrow's leftovers
+    #[account(mut, address = pool.creator)]
+    pub creator: AccountInfo<'info>,
+
+    /// CHECK: SOL vault PDA; only the creator's seed leaves it here
+    #[account(
+        mut,
+        seeds = [LiquidityPool::SOL_VAULT_PREFIX.as_bytes(), token_mint.key().as_ref()],
+        bump = pool.vault_bump
+    )]
+    pub pool_sol_vault: AccountInfo<'info>,
+
+    /// CHECK: the pool's AMM program; must be the one chosen at create_pool since the escrow PDA signs the CPI
+    #[account(
+        address = pool.amm_program @ CustomError::InvalidProgram,
+        constraint = dex_configuration_account.allows_amm(pool.amm_kind, &amm_program.key()) @ CustomError::InvalidProgram
+    )]
+    pub amm_program: UncheckedAccount<'info>,
+
+    /// CHECK: AMM pool state, created by the CPI; owner + contents verified after it
+    #[account(mut)]
+    pub amm_pool: UncheckedAccount<'info>,
+
+    /// CHECK: LP mint (or position NFT mint), created by the CPI
+    #[account(mut)]
+    pub amm_lp_mint: UncheckedAccount<'info>,
+
+    /// CHECK: where the AMM puts the escrow PDA's LP; the adapter checks its address
+    #[account(mut)]
+    pub escrow_lp_account: UncheckedAccount<'info>,
+
+    /// CHECK: LP escrow PDA; authority of the escrow LP account, only release_lp signs for it
+    #[account(
+        seeds = [LiquidityPool::LP_ESCROW_PREFIX.as_bytes(), token_mint.key().as_ref()],
+        bump
+    )]
+    pub lp_escrow: UncheckedAccount<'info>,
+
+    /// CHECK: escrow's LP ATA, created here after the CPI (unused when the LP is burned)
+    #[account(
+        mut,
+        address = get_associated_token_address_with_program_id(
+            &lp_escrow.key(),
+            &amm_lp_mint.key(),
+            &lp_token_program.key()
+        )
+    )]
+    pub lp_escrow_token_account: UncheckedAccount<'info>,
 
-    /// CHECK: recorded only; the Raydium AMM/CLMM pool account id
-    pub raydium_pool: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+    #[account(address = pool.amm_kind.lp_token_program() @ CustomError::InvalidProgram)]
+    pub lp_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
 }
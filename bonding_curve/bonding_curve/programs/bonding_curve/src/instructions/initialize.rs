@@ -1,4 +1,4 @@
-use crate::{errors::CustomError, state::*};
+use crate::state::*;
 use anchor_lang::prelude::*;
 
 pub fn handle(
@@ -7,11 +7,9 @@ pub fn handle(
 ) -> Result<()> {
     let dex_config = &mut ctx.accounts.dex_configuration_account;
 
-    if fees < 0_f64 || fees > 100_f64 {
-        return err!(CustomError::InvalidFee);
-    }
-
-    dex_config.set_inner(CurveConfiguration::new(fees));
+    let config = GlobalConfig::new(ctx.accounts.admin.key(), fees);
+    config.validate()?;
+    dex_config.set_inner(config);
 
     Ok(())
 }
@@ -20,15 +18,16 @@ pub fn handle(
 pub struct InitializeCurveConfiguration<'info> {
     #[account(
         init,
-        space = CurveConfiguration::ACCOUNT_SIZE,
+        space = GlobalConfig::ACCOUNT_SIZE,
         payer = admin,
-        seeds = [CurveConfiguration::SEED.as_bytes()],
+        seeds = [GlobalConfig::SEED.as_bytes()],
         bump,
     )]
-    pub dex_configuration_account: Box<Account<'info, CurveConfiguration>>,
+    pub dex_configuration_account: Box<Account<'info, GlobalConfig>>,
 
+    // Becomes the config admin
     #[account(mut)]
     pub admin: Signer<'info>,
     pub rent: Sysvar<'info, Rent>,
     pub system_program: Program<'info, System>,
-}
\ No newline at end of file
+}
@@ -0,0 +1,349 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    metadata::{
+        create_metadata_accounts_v3, mpl_token_metadata::types::DataV2, CreateMetadataAccountsV3,
+        Metadata,
+    },
+    token::{self, spl_token::instruction::AuthorityType, Mint, Token, TokenAccount},
+};
+
+use crate::{
+    consts::{SOLD_CAP, TREASURY_SUPPLY},
+    errors::CustomError,
+    instructions::{
+        buy::{init_buyer_pdas, settle_tiers},
+        create_pool::{init_sol_vault, validate_mint, validate_pool_params},
+    },
+    state::*,
+    utils::curve::LUT_DECIMALS,
+};
+
+/// create_pool + add_liquidity + the creator's first buy in one instruction,
+/// over a mint this instruction creates: the pool PDA is its mint authority
+/// just long enough to write the metadata and mint the 800M/200M split, then
+/// the authority is revoked (freeze authority is never set) and the mint is
+/// held to the same validate_mint rules create_pool applies.
+///
+/// The metadata is deliberately left mutable with the creator as update
+/// authority, so name, symbol and URI (socials, image) can be corrected
+/// after launch. Supply is what holders rely on and that stays fixed;
+/// clients should not treat the metadata as immutable.
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    ctx: Context<LaunchToken>,
+    metadata: TokenMetadataArgs,        // Metaplex name / symbol / uri
+    migration_authority: Pubkey,
+    tiers: Vec<Tier>,                   // empty = legacy three equal tiers
+    tier_ratchet: bool,                 // sells cannot unwind completed tiers
+    vesting: Option<VestingConfig>,     // vest buys in early tiers
+    bonus: Option<TierBonusConfig>,     // tier-completion bonus from the treasury
+    lp_lock: Option<LpLockConfig>,      // LP release/burn after migration (None = locked for good)
+    amm: AmmTarget,                     // AMM the pool graduates to
+    launch_deadline: Option<i64>,       // unix time the cap must be reached by (None = no expiry)
+    creator_bonus_bps: Option<u16>,     // creator's cut of the curve SOL at graduation
+    initial_buy: Option<u64>,           // creator's first buy, lamports budget (None = no buy)
+) -> Result<()> {
+    let config = &ctx.accounts.dex_configuration_account;
+    metadata.validate()?;
+    validate_pool_params(
+        config,
+        &tiers,
+        vesting.as_ref(),
+        bonus.as_ref(),
+        lp_lock.as_ref(),
+        &amm,
+        launch_deadline,
+        creator_bonus_bps,
+    )?;
+
+    let initial_lamports = config.initial_lamports_for_pool;
+    let migration_fee_bps = config.migration_fee_bps;
+    let mint_key = ctx.accounts.token_mint.key();
+    let creator_key = ctx.accounts.creator.key();
+
+    // 1) Pool + SOL vault
+    let pool = &mut ctx.accounts.pool;
+    pool.set_inner(LiquidityPool::new(
+        creator_key,
+        mint_key,
+        ctx.bumps.pool,
+        ctx.bumps.pool_sol_vault,
+        tiers,
+        tier_ratchet,
+        vesting,
+        bonus,
+        lp_lock,
+        amm,
+        launch_deadline,
+        migration_fee_bps,
+        creator_bonus_bps,
+    ));
+    pool.migration_authority = migration_authority;
+
+    init_sol_vault(
+        &ctx.accounts.pool_sol_vault,
+        ctx.bumps.pool_sol_vault,
+        &mint_key,
+        &ctx.accounts.creator,
+        &ctx.accounts.system_program,
+    )?;
+
+    // 2) Metadata and supply, signed by the pool as mint authority
+    let pool_seeds = &[
+        LiquidityPool::POOL_SEED_PREFIX.as_bytes(),
+        mint_key.as_ref(),
+        &[ctx.bumps.pool],
+    ];
+    let signer = &[&pool_seeds[..]];
+
+    create_metadata_accounts_v3(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_metadata_program.to_account_info(),
+            CreateMetadataAccountsV3 {
+                metadata: ctx.accounts.metadata.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                mint_authority: ctx.accounts.pool.to_account_info(),
+                payer: ctx.accounts.creator.to_account_info(),
+                update_authority: ctx.accounts.creator.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                rent: ctx.accounts.rent.to_account_info(),
+            },
+            signer,
+        ),
+        DataV2 {
+            name: metadata.name,
+            symbol: metadata.symbol,
+            uri: metadata.uri,
+            seller_fee_basis_points: 0,
+            creators: None,
+            collection: None,
+            uses: None,
+        },
+        true, // is_mutable: creator may update name / symbol / uri (see above)
+        true, // update_authority_is_signer
+        None,
+    )?;
+
+    let unit = 10u64.pow(LUT_DECIMALS as u32);
+    for (to, whole_tokens) in [
+        (ctx.accounts.pool_token_account.to_account_info(), SOLD_CAP),
+        (ctx.accounts.treasury_token_account.to_account_info(), TREASURY_SUPPLY),
+    ] {
+        token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::MintTo {
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to,
+                    authority: ctx.accounts.pool.to_account_info(),
+                },
+                signer,
+            ),
+            whole_tokens
+                .checked_mul(unit)
+                .ok_or(CustomError::OverflowOrUnderflowOccurred)?,
+        )?;
+    }
+
+    token::set_authority(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::SetAuthority {
+                current_authority: ctx.accounts.pool.to_account_info(),
+                account_or_mint: ctx.accounts.token_mint.to_account_info(),
+            },
+            signer,
+        ),
+        AuthorityType::MintTokens,
+        None,
+    )?;
+
+    // 3) Curve starts from the whole sold cap; creator seeds the vault
+    ctx.accounts.token_mint.reload()?;
+    ctx.accounts.pool_token_account.reload()?;
+    ctx.accounts.treasury_token_account.reload()?;
+    validate_mint(
+        &ctx.accounts.token_mint,
+        &ctx.accounts.pool_token_account,
+        &ctx.accounts.treasury_token_account,
+    )?;
+    let pool = &mut ctx.accounts.pool;
+    pool.total_supply = ctx.accounts.token_mint.supply;
+    pool.update_reserves(ctx.accounts.pool_token_account.amount, 0)?;
+
+    pool.transfer_sol_to_pool(
+        &ctx.accounts.creator,
+        &mut ctx.accounts.pool_sol_vault,
+        initial_lamports,
+        &ctx.accounts.system_program,
+    )?;
+    pool.seed_sol = initial_lamports;
+
+    // 4) Optional first buy, same path as `buy`
+    let Some(amount) = initial_buy else {
+        return Ok(());
+    };
+    ctx.accounts.dex_configuration_account.require_unpaused(pool)?;
+    let Some(user_token_account) = ctx.accounts.user_token_account.as_deref_mut() else {
+        return err!(CustomError::CreatorTokenAccountMissing);
+    };
+
+    init_buyer_pdas(
+        pool.key(),
+        creator_key,
+        ctx.accounts.vesting_escrow.as_deref_mut().zip(ctx.bumps.vesting_escrow),
+        ctx.accounts.tier_participation.as_deref_mut().zip(ctx.bumps.tier_participation),
+    );
+
+    let vesting = ctx
+        .accounts
+        .vesting_escrow
+        .as_deref_mut()
+        .zip(ctx.accounts.vesting_token_account.as_deref_mut());
+
+    let tokens_out = pool.buy(
+        (
+            &mut *ctx.accounts.token_mint,
+            &mut *ctx.accounts.pool_token_account,
+            &mut *user_token_account,
+        ),
+        &mut ctx.accounts.pool_sol_vault,
+        amount,
+        vesting,
+        &ctx.accounts.creator,
+        &ctx.accounts.token_program,
+        &ctx.accounts.system_program,
+    )?;
+
+    settle_tiers(
+        pool,
+        tokens_out,
+        ctx.accounts.token_mint.decimals,
+        ctx.accounts.tier_participation.as_deref_mut(),
+        Some((
+            &ctx.accounts.treasury_pda,
+            ctx.bumps.treasury_pda,
+            &*ctx.accounts.treasury_token_account,
+        )),
+        user_token_account,
+        &ctx.accounts.token_program,
+    )
+}
+
+#[derive(Accounts)]
+pub struct LaunchToken<'info> {
+    // Global config
+    #[account(
+        seeds = [GlobalConfig::SEED.as_bytes()],
+        bump,
+    )]
+    pub dex_configuration_account: Box<Account<'info, GlobalConfig>>,
+
+    #[account(
+        init,
+        space = LiquidityPool::ACCOUNT_SIZE,
+        payer = creator,
+        seeds = [LiquidityPool::POOL_SEED_PREFIX.as_bytes(), token_mint.key().as_ref()],
+        bump
+    )]
+    pub pool: Box<Account<'info, LiquidityPool>>,
+
+    // Fresh mint keypair (signs); the pool is its mint authority until revoked below
+    #[account(
+        init,
+        payer = creator,
+        mint::decimals = LUT_DECIMALS,
+        mint::authority = pool,
+    )]
+    pub token_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: Metaplex metadata PDA; created by the metadata program
+    #[account(
+        mut,
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), token_mint.key().as_ref()],
+        seeds::program = token_metadata_program.key(),
+        bump
+    )]
+    pub metadata: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = token_mint,
+        associated_token::authority = pool
+    )]
+    pub pool_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: treasury PDA (owner of treasury ATA)
+    #[account(
+        seeds = [LiquidityPool::TREASURY_SEED_PREFIX.as_bytes(), token_mint.key().as_ref()],
+        bump
+    )]
+    pub treasury_pda: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = token_mint,
+        associated_token::authority = treasury_pda
+    )]
+    pub treasury_token_account: Box<Account<'info, TokenAccount>>,
+
+    // Derived PDA (system-owned lamport vault); created rent-exempt here
+    #[account(
+        mut,
+        seeds = [LiquidityPool::SOL_VAULT_PREFIX.as_bytes(), token_mint.key().as_ref()],
+        bump
+    )]
+    /// CHECK: PDA vault is system-owned and only holds lamports
+    pub pool_sol_vault: AccountInfo<'info>,
+
+    // Creator's token ATA; required only with an initial buy
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = token_mint,
+        associated_token::authority = creator,
+    )]
+    pub user_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    // Vesting escrow; required only when the initial buy lands inside vested tiers
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = VestingEscrow::ACCOUNT_SIZE,
+        seeds = [VestingEscrow::SEED_PREFIX.as_bytes(), pool.key().as_ref(), creator.key().as_ref()],
+        bump
+    )]
+    pub vesting_escrow: Option<Box<Account<'info, VestingEscrow>>>,
+
+    // Escrow's token ATA (authority = escrow PDA)
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = token_mint,
+        associated_token::authority = vesting_escrow,
+    )]
+    pub vesting_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    // Tier participation; required only for an initial buy on pro-rata bonus pools
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = TierParticipation::ACCOUNT_SIZE,
+        seeds = [TierParticipation::SEED_PREFIX.as_bytes(), pool.key().as_ref(), creator.key().as_ref()],
+        bump
+    )]
+    pub tier_participation: Option<Box<Account<'info, TierParticipation>>>,
+
+    // Creator: pays for everything, metadata update authority
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+}
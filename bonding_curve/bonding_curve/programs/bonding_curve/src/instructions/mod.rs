@@ -6,3 +6,23 @@ pub mod buy;
 pub mod sell;
 pub mod start_migration;
 pub mod finalize_migration;
+pub mod claim_vested;
+pub mod claim_tier_bonus;
+pub mod curve_view;
+pub mod release_lp;
+pub mod upgrade_pool_account;
+pub mod abort_migration;
+pub mod redeem;
+pub mod reclaim_expired_pool;
+pub mod close_graduated_pool;
+pub mod update_config;
+pub mod transfer_admin;
+pub mod accept_admin;
+pub mod upgrade_config;
+pub mod set_paused;
+pub mod set_pool_paused;
+pub mod request_emergency_withdraw;
+pub mod cancel_emergency_withdraw;
+pub mod sweep_donations;
+pub mod check_invariants;
+pub mod launch_token;
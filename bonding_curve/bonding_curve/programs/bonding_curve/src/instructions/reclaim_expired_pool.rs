@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::{
+    errors::CustomError,
+    state::{LiquidityPool, LiquidityPoolAccount, PoolPhase},
+};
+
+/// The creator's way out of an Expired pool. The SOL seed never joined the
+/// curve, so it comes back as soon as the pool has expired. The vault's rent
+/// and the rounding dust of the pro-rata split follow once every outstanding
+/// token has been redeemed, which closes the vault; until then holders'
+/// SOL is never touched.
+pub fn handle(ctx: Context<ReclaimExpiredPool>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    require!(matches!(pool.phase, PoolPhase::Expired), CustomError::BadPhase);
+
+    let fully_redeemed = pool.tokens_redeemed >= pool.reserve_snapshot_token;
+    let lamports = if fully_redeemed {
+        ctx.accounts.pool_sol_vault.lamports()
+    } else {
+        pool.seed_sol
+    };
+    require!(lamports > 0, CustomError::InvalidAmount);
+
+    pool.transfer_sol_from_pool(
+        &mut ctx.accounts.pool_sol_vault,
+        &ctx.accounts.creator,
+        lamports,
+        &ctx.accounts.system_program,
+    )?;
+    pool.seed_sol = 0;
+    if fully_redeemed {
+        pool.reserve_sol = 0;
+    }
+
+    emit!(crate::ExpiredPoolReclaimed {
+        pool: pool.key(),
+        lamports,
+        vault_closed: fully_redeemed,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ReclaimExpiredPool<'info> {
+    #[account(
+        mut,
+        seeds = [LiquidityPool::POOL_SEED_PREFIX.as_bytes(), token_mint.key().as_ref()],
+        bump = pool.bump,
+        has_one = creator @ CustomError::NotCreator,
+    )]
+    pub pool: Box<Account<'info, LiquidityPool>>,
+
+    pub token_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: SOL vault PDA
+    #[account(
+        mut,
+        seeds = [LiquidityPool::SOL_VAULT_PREFIX.as_bytes(), token_mint.key().as_ref()],
+        bump = pool.vault_bump
+    )]
+    pub pool_sol_vault: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,131 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, Token, TokenAccount},
+};
+
+use crate::{
+    errors::CustomError,
+    state::{LiquidityPool, LiquidityPoolAccount, PoolPhase},
+};
+
+pub fn handle(ctx: Context<Redeem>, amount: u64) -> Result<()> {
+    require!(amount > 0, CustomError::InvalidAmount);
+
+    let pool = &mut ctx.accounts.pool;
+    let clock = Clock::get()?;
+
+    // 1) First redeem after the deadline flips the pool and fixes the split
+    //    (an emergency withdraw may have flipped it already)
+    if pool.launch_expired(clock.unix_timestamp) {
+        let held = pool.tokens_outstanding(
+            ctx.accounts.token_mint.supply,
+            ctx.accounts.pool_token_account.amount,
+            ctx.accounts.treasury_token_account.amount,
+            ctx.accounts.token_mint.decimals,
+        );
+        pool.expire(held);
+
+        emit!(crate::PoolExpired {
+            pool: pool.key(),
+            slot: clock.slot,
+            tokens_outstanding: pool.reserve_snapshot_token,
+            sol_redeemable: pool.reserve_snapshot_sol,
+        });
+    }
+    require!(matches!(pool.phase, PoolPhase::Expired), CustomError::LaunchNotExpired);
+
+    // 2) Pro-rata share of the vault as it stood at expiry
+    let lamports = (pool.reserve_snapshot_sol as u128)
+        .checked_mul(amount as u128)
+        .and_then(|v| v.checked_div(pool.reserve_snapshot_token as u128))
+        .and_then(|v| u64::try_from(v).ok())
+        .ok_or_else(|| error!(CustomError::OverflowOrUnderflowOccurred))?;
+    require!(lamports > 0, CustomError::InvalidAmount);
+
+    // 3) Burn the holder's tokens, then pay out of the vault
+    token::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token::Burn {
+                mint: ctx.accounts.token_mint.to_account_info(),
+                from: ctx.accounts.user_token_account.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    pool.transfer_sol_from_pool(
+        &mut ctx.accounts.pool_sol_vault,
+        &ctx.accounts.user,
+        lamports,
+        &ctx.accounts.system_program,
+    )?;
+    pool.reserve_sol = pool.reserve_sol.saturating_sub(lamports);
+    pool.tokens_redeemed = pool.tokens_redeemed.saturating_add(amount);
+
+    emit!(crate::Redeemed {
+        pool: pool.key(),
+        user: ctx.accounts.user.key(),
+        amount,
+        lamports,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct Redeem<'info> {
+    #[account(
+        mut,
+        seeds = [LiquidityPool::POOL_SEED_PREFIX.as_bytes(), token_mint.key().as_ref()],
+        bump = pool.bump,
+    )]
+    pub pool: Box<Account<'info, LiquidityPool>>,
+
+    #[account(mut)]
+    pub token_mint: Box<Account<'info, Mint>>,
+
+    // pool ATA (unsold curve tokens, excluded from the holder count)
+    #[account(
+        associated_token::mint = token_mint,
+        associated_token::authority = pool
+    )]
+    pub pool_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: treasury PDA (owner of treasury ATA)
+    #[account(
+        seeds = [LiquidityPool::TREASURY_SEED_PREFIX.as_bytes(), token_mint.key().as_ref()],
+        bump
+    )]
+    pub treasury_pda: AccountInfo<'info>,
+
+    #[account(
+        associated_token::mint = token_mint,
+        associated_token::authority = treasury_pda
+    )]
+    pub treasury_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: SOL vault PDA
+    #[account(
+        mut,
+        seeds = [LiquidityPool::SOL_VAULT_PREFIX.as_bytes(), token_mint.key().as_ref()],
+        bump = pool.vault_bump
+    )]
+    pub pool_sol_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = user
+    )]
+    pub user_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::Mint,
+    token_interface::{self, Mint as LpMint, TokenAccount as LpTokenAccount, TokenInterface},
+};
+
+use crate::{
+    errors::CustomError,
+    state::{LiquidityPool, PoolPhase},
+};
+
+pub fn handle(ctx: Context<ReleaseLp>) -> Result<()> {
+    let pool = &ctx.accounts.pool;
+
+    require!(matches!(pool.phase, PoolPhase::Graduated), CustomError::BadPhase);
+    require!(
+        Clock::get()?.slot >= pool.lp_unlock_slot,
+        CustomError::LpStillLocked
+    );
+
+    let amount = ctx.accounts.lp_escrow_token_account.amount;
+    require!(amount > 0, CustomError::NothingToClaim);
+
+    // Escrow PDA signs for its LP ATA
+    let mint_key = ctx.accounts.token_mint.key();
+    let seeds = &[
+        LiquidityPool::LP_ESCROW_PREFIX.as_bytes(),
+        mint_key.as_ref(),
+        &[ctx.bumps.lp_escrow],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.lp_token_program.to_account_info(),
+            token_interface::TransferChecked {
+                from: ctx.accounts.lp_escrow_token_account.to_account_info(),
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                to: ctx.accounts.recipient_lp_account.to_account_info(),
+                authority: ctx.accounts.lp_escrow.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+        ctx.accounts.lp_mint.decimals,
+    )?;
+
+    emit!(crate::LpReleased {
+        pool: pool.key(),
+        recipient: ctx.accounts.recipient.key(),
+        amount,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ReleaseLp<'info> {
+    // Pool PDA (lock terms live here)
+    #[account(
+        seeds = [LiquidityPool::POOL_SEED_PREFIX.as_bytes(), token_mint.key().as_ref()],
+        bump = pool.bump,
+        constraint = pool.lp_recipient != Pubkey::default() @ CustomError::Unauthorized,
+        constraint = pool.lp_recipient == recipient.key() @ CustomError::Unauthorized,
+    )]
+    pub pool: Box<Account<'info, LiquidityPool>>,
+
+    pub token_mint: Box<Account<'info, Mint>>,
+
+    // LP mint or position NFT; SPL Token or Token-2022 depending on the AMM
+    #[account(
+        constraint = pool.lp_mint == Some(lp_mint.key()) @ CustomError::InvalidAmmPool,
+        mint::token_program = lp_token_program,
+    )]
+    pub lp_mint: Box<InterfaceAccount<'info, LpMint>>,
+
+    /// CHECK: LP escrow PDA (authority of the escrow LP account)
+    #[account(
+        seeds = [LiquidityPool::LP_ESCROW_PREFIX.as_bytes(), token_mint.key().as_ref()],
+        bump
+    )]
+    pub lp_escrow: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = lp_mint,
+        associated_token::authority = lp_escrow,
+        associated_token::token_program = lp_token_program,
+    )]
+    pub lp_escrow_token_account: Box<InterfaceAccount<'info, LpTokenAccount>>,
+
+    // Recipient's LP ATA (auto-create if missing)
+    #[account(
+        init_if_needed,
+        payer = recipient,
+        associated_token::mint = lp_mint,
+        associated_token::authority = recipient,
+        associated_token::token_program = lp_token_program,
+    )]
+    pub recipient_lp_account: Box<InterfaceAccount<'info, LpTokenAccount>>,
+
+    // Designated at create_pool
+    #[account(mut)]
+    pub recipient: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    #[account(address = pool.amm_kind.lp_token_program() @ CustomError::InvalidProgram)]
+    pub lp_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
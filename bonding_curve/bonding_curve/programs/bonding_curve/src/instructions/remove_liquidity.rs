@@ -4,21 +4,109 @@ use anchor_spl::{
     token::{Mint, Token, TokenAccount},
 };
 use crate::{
-    consts::TOKEN_SELL_LIMIT_PERCENT,
     errors::CustomError,
-    state::{LiquidityPool, LiquidityPoolAccount},
+    state::{GlobalConfig, LiquidityPool, LiquidityPoolAccount, PoolPhase},
 };
 
-pub fn handle(ctx: Context<RemoveLiquidity>, bump: u8) -> Result<()> {
-    let pool = &mut ctx.accounts.pool;
-    if pool.creator.key() != ctx.accounts.user.key() {
-        return Err(CustomError::NotCreator.into());
+/// Two ways out, and no others:
+/// - the creator, while the curve is Active and nothing has been sold
+///   (only their own bootstrap tokens and SOL are inside);
+/// - the config admin, once an emergency withdraw requested through
+///   request_emergency_withdraw has sat out its timelock. Nothing goes to
+///   the admin: the curve ends in Expired and holders redeem the vault SOL
+///   pro rata, as after a missed deadline. Works while paused.
+pub fn handle(ctx: Context<RemoveLiquidity>) -> Result<()> {
+    let user_key = ctx.accounts.user.key();
+    let emergency = user_key == ctx.accounts.dex_configuration_account.admin
+        && user_key != ctx.accounts.pool.creator;
+
+    if emergency {
+        let clock = Clock::get()?;
+        let unlock = ctx.accounts.pool.emergency_withdraw_slot;
+        require!(
+            unlock > 0 && clock.slot >= unlock,
+            CustomError::EmergencyWithdrawLocked
+        );
+        require!(
+            matches!(ctx.accounts.pool.phase, PoolPhase::Active),
+            CustomError::BadPhase
+        );
+        let treasury_token_account = ctx
+            .accounts
+            .treasury_token_account
+            .as_deref()
+            .ok_or_else(|| error!(CustomError::TreasuryAccountMissing))?;
+
+        let held = ctx.accounts.pool.tokens_outstanding(
+            ctx.accounts.token_mint.supply,
+            ctx.accounts.pool_token_account.amount,
+            treasury_token_account.amount,
+            ctx.accounts.token_mint.decimals,
+        );
+        let pool = &mut ctx.accounts.pool;
+        pool.expire(held);
+        pool.emergency_withdraw_slot = 0;
+
+        emit!(crate::PoolExpired {
+            pool: pool.key(),
+            slot: clock.slot,
+            tokens_outstanding: pool.reserve_snapshot_token,
+            sol_redeemable: pool.reserve_snapshot_sol,
+        });
+        return Ok(());
     }
 
-    // if pool.total_supply.checked_div(10000).ok_or(CustomError::OverflowOrUnderflowOccurred)?
-    // .checked_mul(TOKEN_SELL_LIMIT_PERCENT) > Some(pool.reserve_token) {
-    //     return Err(CustomError::NotEnoughToRemove.into());
-    // }
+    require_keys_eq!(ctx.accounts.pool.creator, user_key, CustomError::NotCreator);
+    ctx.accounts
+        .dex_configuration_account
+        .require_unpaused(&ctx.accounts.pool)?;
+    require!(
+        matches!(ctx.accounts.pool.phase, PoolPhase::Active) && ctx.accounts.pool.total_sold == 0,
+        CustomError::LiquidityLocked
+    );
+
+    let tokens = ctx.accounts.pool_token_account.amount;
+    let lamports = ctx.accounts.pool.reserve_sol + ctx.accounts.pool.seed_sol;
+    let pool = &mut ctx.accounts.pool;
+
+    let token_accounts = (
+        &mut *ctx.accounts.token_mint,
+        &mut *ctx.accounts.pool_token_account,
+        &mut *ctx.accounts.user_token_account,
+    );
+
+    pool.remove_liquidity(
+        token_accounts,
+        &mut ctx.accounts.pool_sol_vault,
+        &ctx.accounts.user,
+        &ctx.accounts.token_program,
+        &ctx.accounts.system_program,
+    )?;
+
+    emit!(crate::LiquidityRemoved {
+        pool: pool.key(),
+        by: user_key,
+        tokens,
+        lamports,
+    });
+    Ok(())
+}
+
+/// The deprecated `remove_liquidity`: the original account list, so only the
+/// creator's way out. That list has no config account, so only the pool's
+/// own pause switch is seen; the emergency path needs remove_liquidity_v2.
+pub fn handle_legacy(ctx: Context<LegacyRemoveLiquidity>) -> Result<()> {
+    let user_key = ctx.accounts.user.key();
+    require_keys_eq!(ctx.accounts.pool.creator, user_key, CustomError::NotCreator);
+    require!(!ctx.accounts.pool.paused, CustomError::PoolPaused);
+    require!(
+        matches!(ctx.accounts.pool.phase, PoolPhase::Active) && ctx.accounts.pool.total_sold == 0,
+        CustomError::LiquidityLocked
+    );
+
+    let tokens = ctx.accounts.pool_token_account.amount;
+    let lamports = ctx.accounts.pool.reserve_sol + ctx.accounts.pool.seed_sol;
+    let pool = &mut ctx.accounts.pool;
 
     let token_accounts = (
         &mut *ctx.accounts.token_mint,
@@ -30,15 +118,28 @@ pub fn handle(ctx: Context<RemoveLiquidity>, bump: u8) -> Result<()> {
         token_accounts,
         &mut ctx.accounts.pool_sol_vault,
         &ctx.accounts.user,
-        bump,
         &ctx.accounts.token_program,
         &ctx.accounts.system_program,
     )?;
+
+    emit!(crate::LiquidityRemoved {
+        pool: pool.key(),
+        by: user_key,
+        tokens,
+        lamports,
+    });
     Ok(())
 }
 
 #[derive(Accounts)]
 pub struct RemoveLiquidity<'info> {
+    // Global config (pause switch, admin for the emergency path)
+    #[account(
+        seeds = [GlobalConfig::SEED.as_bytes()],
+        bump,
+    )]
+    pub dex_configuration_account: Box<Account<'info, GlobalConfig>>,
+
     #[account(
         mut,
        seeds = [LiquidityPool::POOL_SEED_PREFIX.as_bytes(), token_mint.key().as_ref()],
@@ -67,8 +168,66 @@ pub struct RemoveLiquidity<'info> {
     #[account(
         mut,
         seeds = [LiquidityPool::SOL_VAULT_PREFIX.as_bytes(), token_mint.key().as_ref()],
+        bump = pool.vault_bump
+    )]
+    pub pool_sol_vault: AccountInfo<'info>,
+
+    /// CHECK: treasury PDA (owner of treasury ATA); emergency path only
+    #[account(
+        seeds = [LiquidityPool::TREASURY_SEED_PREFIX.as_bytes(), token_mint.key().as_ref()],
         bump
     )]
+    pub treasury_pda: Option<UncheckedAccount<'info>>,
+
+    // Treasury ATA, to count the tokens holders redeem against
+    #[account(
+        associated_token::mint = token_mint,
+        associated_token::authority = treasury_pda,
+    )]
+    pub treasury_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    // Pool creator, or the config admin on the emergency path
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+// The original remove_liquidity accounts, in their original order
+#[derive(Accounts)]
+pub struct LegacyRemoveLiquidity<'info> {
+    #[account(
+        mut,
+        seeds = [LiquidityPool::POOL_SEED_PREFIX.as_bytes(), token_mint.key().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Box<Account<'info, LiquidityPool>>,
+
+    #[account(mut)]
+    pub token_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = pool
+    )]
+    pub pool_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = user,
+    )]
+    pub user_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: SOL vault PDA; seeds and stored bump enforced
+    #[account(
+        mut,
+        seeds = [LiquidityPool::SOL_VAULT_PREFIX.as_bytes(), token_mint.key().as_ref()],
+        bump = pool.vault_bump
+    )]
     pub pool_sol_vault: AccountInfo<'info>,
 
     #[account(mut)]
@@ -77,4 +236,4 @@ pub struct RemoveLiquidity<'info> {
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
-}
\ No newline at end of file
+}
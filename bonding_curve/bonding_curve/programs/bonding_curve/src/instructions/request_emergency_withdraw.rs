@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::{
+    consts::EMERGENCY_WITHDRAW_DELAY_SLOTS,
+    errors::CustomError,
+    state::{GlobalConfig, LiquidityPool, PoolPhase},
+};
+
+/// Starts the timelock after which the admin may wind an Active curve down
+/// through remove_liquidity (it ends Expired, holders redeem). The event is
+/// the holders' warning; cancel_emergency_withdraw calls it off.
+pub fn handle(ctx: Context<RequestEmergencyWithdraw>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    require!(matches!(pool.phase, PoolPhase::Active), CustomError::BadPhase);
+    let unlock_slot = Clock::get()?
+        .slot
+        .saturating_add(EMERGENCY_WITHDRAW_DELAY_SLOTS);
+    pool.emergency_withdraw_slot = unlock_slot;
+
+    emit!(crate::EmergencyWithdrawRequested {
+        pool: pool.key(),
+        admin: ctx.accounts.admin.key(),
+        unlock_slot,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RequestEmergencyWithdraw<'info> {
+    #[account(
+        seeds = [GlobalConfig::SEED.as_bytes()],
+        bump,
+        has_one = admin @ CustomError::Unauthorized,
+    )]
+    pub dex_configuration_account: Box<Account<'info, GlobalConfig>>,
+
+    #[account(
+        mut,
+        seeds = [LiquidityPool::POOL_SEED_PREFIX.as_bytes(), token_mint.key().as_ref()],
+        bump = pool.bump,
+    )]
+    pub pool: Box<Account<'info, LiquidityPool>>,
+
+    pub token_mint: Box<Account<'info, Mint>>,
+
+    pub admin: Signer<'info>,
+}
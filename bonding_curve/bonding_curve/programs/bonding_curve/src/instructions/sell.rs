@@ -4,16 +4,35 @@ use anchor_spl::{
     token::{Mint, Token, TokenAccount},
 };
 
-use crate::state::{CurveConfiguration, LiquidityPool, LiquidityPoolAccount};
+use crate::{
+    errors::CustomError,
+    instructions::buy::init_buyer_pdas,
+    state::{BonusMode, GlobalConfig, LiquidityPool, LiquidityPoolAccount, TierParticipation},
+};
+
+pub fn handle(ctx: Context<Sell>, amount: u64) -> Result<()> {
+    ctx.accounts
+        .dex_configuration_account
+        .require_unpaused(&ctx.accounts.pool)?;
 
-pub fn handle(ctx: Context<Sell>, amount: u64, bump: u8) -> Result<()> {
     // Trace logs
     msg!("💸 [sell] amount (tokens in): {}", amount);
     msg!("💸 [sell] user token ATA: {}", ctx.accounts.user_token_account.amount);
     msg!("💸 [sell] pool token ATA: {}", ctx.accounts.pool_token_account.amount);
     msg!("💸 [sell] pool SOL vault lamports: {}", ctx.accounts.pool_sol_vault.lamports());
 
+    init_buyer_pdas(
+        ctx.accounts.pool.key(),
+        ctx.accounts.user.key(),
+        None,
+        ctx.accounts.tier_participation.as_deref_mut().zip(ctx.bumps.tier_participation),
+    );
+
     let pool = &mut ctx.accounts.pool;
+    if pool.bonus_per_tier > 0 && pool.bonus_mode == BonusMode::ProRata {
+        require!(ctx.accounts.tier_participation.is_some(), CustomError::BonusAccountsMissing);
+    }
+    let y_before = pool.total_sold;
 
     let token_accounts = (
         &mut *ctx.accounts.token_mint,
@@ -25,11 +44,52 @@ pub fn handle(ctx: Context<Sell>, amount: u64, bump: u8) -> Result<()> {
         token_accounts,
         &mut ctx.accounts.pool_sol_vault,
         amount,
-        bump,
         &ctx.accounts.user,
         &ctx.accounts.token_program,
         &ctx.accounts.system_program,
-    )
+    )?;
+
+    // Selling back gives up bonus weight, so sell-and-rebuy can't stack it
+    let y_after = pool.total_sold;
+    pool.record_tier_sell(
+        y_after,
+        y_before,
+        ctx.accounts.tier_participation.as_deref_mut().map(|p| &mut **p),
+    );
+    Ok(())
+}
+
+/// The deprecated `sell`: the original account list, so no tier participation
+/// (pro-rata bonus pools need sell_v2).
+pub fn handle_legacy(ctx: Context<LegacySell>, amount: u64) -> Result<()> {
+    ctx.accounts
+        .dex_configuration_account
+        .require_unpaused(&ctx.accounts.pool)?;
+
+    let pool = &mut ctx.accounts.pool;
+    if pool.bonus_per_tier > 0 && pool.bonus_mode == BonusMode::ProRata {
+        return err!(CustomError::BonusAccountsMissing);
+    }
+    let y_before = pool.total_sold;
+
+    let token_accounts = (
+        &mut *ctx.accounts.token_mint,
+        &mut *ctx.accounts.pool_token_account,
+        &mut *ctx.accounts.user_token_account,
+    );
+
+    pool.sell(
+        token_accounts,
+        &mut ctx.accounts.pool_sol_vault,
+        amount,
+        &ctx.accounts.user,
+        &ctx.accounts.token_program,
+        &ctx.accounts.system_program,
+    )?;
+
+    let y_after = pool.total_sold;
+    pool.record_tier_sell(y_after, y_before, None);
+    Ok(())
 }
 
 #[derive(Accounts)]
@@ -37,10 +97,10 @@ pub struct Sell<'info> {
     // Global config (present for future fee handling)
     #[account(
         mut,
-        seeds = [CurveConfiguration::SEED.as_bytes()],
+        seeds = [GlobalConfig::SEED.as_bytes()],
         bump,
     )]
-    pub dex_configuration_account: Box<Account<'info, CurveConfiguration>>,
+    pub dex_configuration_account: Box<Account<'info, GlobalConfig>>,
 
     // Pool PDA
     #[account(
@@ -66,7 +126,7 @@ pub struct Sell<'info> {
     #[account(
         mut,
         seeds = [LiquidityPool::SOL_VAULT_PREFIX.as_bytes(), token_mint.key().as_ref()],
-        bump
+        bump = pool.vault_bump
     )]
     /// CHECK: PDA vault holds only lamports; seeds enforced; owner checked at runtime.
     pub pool_sol_vault: AccountInfo<'info>,
@@ -89,4 +149,69 @@ pub struct Sell<'info> {
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub rent: Sysvar<'info, Rent>,
+
+    // --- optional, appended after the original accounts so clients that build
+    //     the list by position still line up; omitted = None ---
+
+    // Seller's tier participation; required only for pro-rata bonus pools
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = TierParticipation::ACCOUNT_SIZE,
+        seeds = [TierParticipation::SEED_PREFIX.as_bytes(), pool.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub tier_participation: Option<Box<Account<'info, TierParticipation>>>,
+}
+
+// The original sell accounts, in their original order
+#[derive(Accounts)]
+pub struct LegacySell<'info> {
+    #[account(
+        mut,
+        seeds = [GlobalConfig::SEED.as_bytes()],
+        bump,
+    )]
+    pub dex_configuration_account: Box<Account<'info, GlobalConfig>>,
+
+    #[account(
+        mut,
+        seeds = [LiquidityPool::POOL_SEED_PREFIX.as_bytes(), token_mint.key().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Box<Account<'info, LiquidityPool>>,
+
+    #[account(mut)]
+    pub token_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = pool
+    )]
+    pub pool_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [LiquidityPool::SOL_VAULT_PREFIX.as_bytes(), token_mint.key().as_ref()],
+        bump = pool.vault_bump
+    )]
+    /// CHECK: PDA vault holds only lamports; seeds enforced; owner checked at runtime.
+    pub pool_sol_vault: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = token_mint,
+        associated_token::authority = user,
+    )]
+    pub user_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
 }
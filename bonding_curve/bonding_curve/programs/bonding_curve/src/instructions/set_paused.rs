@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::CustomError, state::GlobalConfig};
+
+/// Guardian switch for the whole protocol.
+pub fn handle(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+    let config = &mut ctx.accounts.dex_configuration_account;
+    config.paused = paused;
+
+    let guardian = ctx.accounts.guardian.key();
+    if paused {
+        emit!(crate::Paused { pool: None, guardian });
+    } else {
+        emit!(crate::Unpaused { pool: None, guardian });
+    }
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    #[account(
+        mut,
+        seeds = [GlobalConfig::SEED.as_bytes()],
+        bump,
+        has_one = guardian @ CustomError::Unauthorized,
+    )]
+    pub dex_configuration_account: Box<Account<'info, GlobalConfig>>,
+
+    pub guardian: Signer<'info>,
+}
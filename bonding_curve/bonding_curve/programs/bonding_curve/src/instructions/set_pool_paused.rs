@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::{
+    errors::CustomError,
+    state::{GlobalConfig, LiquidityPool},
+};
+
+/// Guardian switch for a single pool.
+pub fn handle(ctx: Context<SetPoolPaused>, paused: bool) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    pool.paused = paused;
+
+    let guardian = ctx.accounts.guardian.key();
+    if paused {
+        emit!(crate::Paused { pool: Some(pool.key()), guardian });
+    } else {
+        emit!(crate::Unpaused { pool: Some(pool.key()), guardian });
+    }
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetPoolPaused<'info> {
+    #[account(
+        seeds = [GlobalConfig::SEED.as_bytes()],
+        bump,
+        has_one = guardian @ CustomError::Unauthorized,
+    )]
+    pub dex_configuration_account: Box<Account<'info, GlobalConfig>>,
+
+    #[account(
+        mut,
+        seeds = [LiquidityPool::POOL_SEED_PREFIX.as_bytes(), token_mint.key().as_ref()],
+        bump = pool.bump,
+    )]
+    pub pool: Box<Account<'info, LiquidityPool>>,
+
+    pub token_mint: Box<Account<'info, Mint>>,
+
+    pub guardian: Signer<'info>,
+}
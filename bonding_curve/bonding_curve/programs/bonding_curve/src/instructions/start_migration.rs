@@ -1,177 +1,158 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Mint};
+use anchor_lang::system_program;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token, TokenAccount},
+};
 use crate::{
     errors::CustomError,
-    state::{LiquidityPool, PoolPhase},
-    utils::curve::{cap_base, y_sold_from_pool},
+    state::{GlobalConfig, LiquidityPool, LiquidityPoolAccount, PoolPhase},
+    utils::curve::cap_base,
 };
 
 pub fn handle(ctx: Context<StartMigration>) -> Result<()> {
+    ctx.accounts
+        .dex_configuration_account
+        .require_unpaused(&ctx.accounts.pool)?;
+
     let pool = &mut ctx.accounts.pool;
+    let clock = Clock::get()?;
+
+    // Authority any time; anyone once the grace window has passed
+    require!(
+        pool.may_drive_migration(&ctx.accounts.caller.key(), clock.slot),
+        CustomError::Unauthorized
+    );
+    require!(!matches!(pool.phase, PoolPhase::Graduated), CustomError::BadPhase);
 
     // 1) re-check cap
     let decimals = ctx.accounts.token_mint.decimals;
     let cap = cap_base(decimals);
-    let y_sold = y_sold_from_pool(pool.reserve_token, decimals);
-    require!(y_sold >= cap, CustomError::CapNotReached);
+    require!(pool.total_sold >= cap, CustomError::CapNotReached);
 
-    // --- NEW: read live balances from accounts ---
-    let vault_lamports: u64 = ctx.accounts.pool_sol_vault.lamports();
+    // --- curve SOL (vault rent and seed stay behind) and live token balance ---
+    let curve_lamports: u64 = pool.reserve_sol;
     let pool_token_amount: u64 = ctx.accounts.pool_token_account.amount;
-    let treasury_token_amount: u64 = ctx.accounts.treasury_token_account.amount;
-
-    // 2) flip to Migrating + snapshot (idempotent)
-    // If we are NOT Migrating/RaydiumLive yet -> flip and take snapshots.
-    // If we ARE Migrating but snapshots are zero (legacy) -> take snapshots once (no flip).
-    let need_initial_flip = !matches!(pool.phase, PoolPhase::Migrating | PoolPhase::RaydiumLive);
-    let snapshots_missing =
-        (pool.reserve_snapshot_token == 0) || (pool.reserve_snapshot_sol == 0);
 
-    if need_initial_flip {
-        let clock = Clock::get()?;
+    // 2) flip to Migrating (the cap-filling buy normally already has)
+    if !matches!(pool.phase, PoolPhase::Migrating) {
         pool.phase = PoolPhase::Migrating;
         pool.cap_reached_slot = Some(clock.slot);
-
-        // --- NEW: snapshot from live account balances ---
-        pool.reserve_snapshot_token = pool_token_amount;
-        pool.reserve_snapshot_sol = vault_lamports;
-
-        emit!(crate::MigrationStarted {
-            pool: pool.key(),
-            slot: clock.slot,
-            reserve_token: pool.reserve_snapshot_token,
-            reserve_sol: pool.reserve_snapshot_sol,
-        });
-    } else if matches!(pool.phase, PoolPhase::Migrating) && snapshots_missing {
-        // Backfill snapshots exactly once if they were never recorded properly.
-        // Do NOT change phase or cap_reached_slot here.
-        if pool.reserve_snapshot_token == 0 {
-            pool.reserve_snapshot_token = pool_token_amount;
-        }
-        if pool.reserve_snapshot_sol == 0 {
-            pool.reserve_snapshot_sol = vault_lamports;
-        }
     }
 
-    // 3) drain pool tokens -> authority ATA
+    // 3) Curve reserves -> migration escrow. From here they only leave through
+    //    finalize_migration (AMM deposit) or abort_migration (back to the pool).
     if pool_token_amount > 0 {
-        let token_key = pool.token.key();
-        let seeds = &[
-            LiquidityPool::POOL_SEED_PREFIX.as_bytes(),
-            token_key.as_ref(),
-            &[pool.bump],
-        ];
-        let signer_seeds = &[&seeds[..]];
-        token::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                token::Transfer {
-                    from: ctx.accounts.pool_token_account.to_account_info(),
-                    to: ctx.accounts.dest_token_account.to_account_info(),
-                    authority: pool.to_account_info(),
-                },
-                signer_seeds,
-            ),
+        pool.transfer_token_from_pool(
+            &ctx.accounts.pool_token_account,
+            &ctx.accounts.escrow_token_account,
             pool_token_amount,
+            &ctx.accounts.token_program,
         )?;
-        // bookkeeping for the pool's internal counter
         pool.reserve_token = pool.reserve_token.saturating_sub(pool_token_amount);
     }
 
-    // 4) drain TREASURY (whatever is there, typically 200M) -> authority ATA
-    if treasury_token_amount > 0 {
+    if curve_lamports > 0 {
         let mint_key = ctx.accounts.token_mint.key();
-        let treasury_seeds = &[
-            b"treasury".as_ref(),
+        let vault_seeds = &[
+            LiquidityPool::SOL_VAULT_PREFIX.as_bytes(),
             mint_key.as_ref(),
-            &[ctx.bumps.treasury_pda],
+            &[pool.vault_bump],
         ];
-        let treasury_signer = &[&treasury_seeds[..]];
-        token::transfer(
+        system_program::transfer(
             CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                token::Transfer {
-                    from: ctx.accounts.treasury_token_account.to_account_info(),
-                    to: ctx.accounts.dest_token_account.to_account_info(),
-                    authority: ctx.accounts.treasury_pda.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.pool_sol_vault.to_account_info(),
+                    to: ctx.accounts.migration_escrow.to_account_info(),
                 },
-                treasury_signer,
+                &[&vault_seeds[..]],
             ),
-            treasury_token_amount,
+            curve_lamports,
         )?;
-        // pool.reserve_token only tracks the pool ATA; no change here.
-    }
-
-    // 5) drain SOL vault -> authority wallet
-    if vault_lamports > 0 {
-        let token_key = pool.token.key();
-        let sol_seeds = &[
-            LiquidityPool::SOL_VAULT_PREFIX.as_bytes(),
-            token_key.as_ref(),
-            &[ctx.bumps.pool_sol_vault],
-        ];
-        let sol_signer = &[&sol_seeds[..]];
-        let ix = anchor_lang::solana_program::system_instruction::transfer(
-            &ctx.accounts.pool_sol_vault.key(),
-            &ctx.accounts.migration_authority.key(),
-            vault_lamports,
-        );
-        anchor_lang::solana_program::program::invoke_signed(
-            &ix,
-            &[
-                ctx.accounts.pool_sol_vault.to_account_info(),
-                ctx.accounts.migration_authority.to_account_info(),
-                ctx.accounts.system_program.to_account_info(),
-            ],
-            sol_signer,
-        )?;
-        // bookkeeping for the pool's internal counter
         pool.reserve_sol = 0;
     }
 
+    // 4) Snapshot what the escrow now holds for the curve. A repeated call
+    //    only moves what arrived since, so it adds to the snapshot.
+    pool.reserve_snapshot_token = pool
+        .reserve_snapshot_token
+        .checked_add(pool_token_amount)
+        .ok_or_else(|| error!(CustomError::OverflowOrUnderflowOccurred))?;
+    pool.reserve_snapshot_sol = pool
+        .reserve_snapshot_sol
+        .checked_add(curve_lamports)
+        .ok_or_else(|| error!(CustomError::OverflowOrUnderflowOccurred))?;
+
+    let (migration_fee, creator_bonus) = pool.graduation_cuts(pool.reserve_snapshot_sol);
+    emit!(crate::MigrationStarted {
+        pool: pool.key(),
+        slot: clock.slot,
+        reserve_token: pool.reserve_snapshot_token,
+        reserve_sol: pool.reserve_snapshot_sol,
+        migration_fee,
+        creator_bonus,
+    });
+
     Ok(())
 }
 
 #[derive(Accounts)]
 pub struct StartMigration<'info> {
+    // Global config (pause switch)
+    #[account(
+        seeds = [GlobalConfig::SEED.as_bytes()],
+        bump,
+    )]
+    pub dex_configuration_account: Box<Account<'info, GlobalConfig>>,
+
     #[account(
         mut,
         seeds = [LiquidityPool::POOL_SEED_PREFIX.as_bytes(), token_mint.key().as_ref()],
         bump = pool.bump,
-        has_one = migration_authority @ CustomError::Unauthorized,
     )]
     pub pool: Box<Account<'info, LiquidityPool>>,
 
     pub token_mint: Box<Account<'info, Mint>>,
 
     // pool ATA (tokens owned by the pool PDA)
-    #[account(mut)]
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = pool
+    )]
     pub pool_token_account: Box<Account<'info, TokenAccount>>,
 
     /// CHECK: SOL vault PDA
     #[account(
         mut,
         seeds = [LiquidityPool::SOL_VAULT_PREFIX.as_bytes(), token_mint.key().as_ref()],
-        bump
+        bump = pool.vault_bump
     )]
     pub pool_sol_vault: AccountInfo<'info>,
 
-    /// CHECK: treasury PDA (owner of treasury ATA)
+    /// CHECK: migration escrow PDA (system-owned, holds the SOL side)
     #[account(
-        seeds = [b"treasury", token_mint.key().as_ref()],
+        mut,
+        seeds = [LiquidityPool::MIGRATION_ESCROW_PREFIX.as_bytes(), token_mint.key().as_ref()],
         bump
     )]
-    pub treasury_pda: AccountInfo<'info>,
+    pub migration_escrow: AccountInfo<'info>,
 
-    #[account(mut)]
-    pub treasury_token_account: Box<Account<'info, TokenAccount>>,
+    // Escrow's token ATA (holds the token side)
+    #[account(
+        init_if_needed,
+        payer = caller,
+        associated_token::mint = token_mint,
+        associated_token::authority = migration_escrow
+    )]
+    pub escrow_token_account: Box<Account<'info, TokenAccount>>,
 
-    // destination (authority's ATA for token_mint)
+    // migration_authority, or anyone after MIGRATION_GRACE_SLOTS (pays for the escrow ATA)
     #[account(mut)]
-    pub dest_token_account: Box<Account<'info, TokenAccount>>,
-
-    pub migration_authority: Signer<'info>,
+    pub caller: Signer<'info>,
 
     pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+
+use crate::{
+    errors::CustomError,
+    state::{LiquidityPool, PoolPhase},
+    utils::curve::cap_base,
+};
+
+/// Permissionless. Whatever sits in the pool ATA or SOL vault beyond the
+/// pool's accounted reserves was donated: tokens are burned, lamports go to
+/// the protocol fee PDA. The curve position (total_sold) is never touched.
+/// Only while the reserves describe the balances: Active, or Graduated once
+/// finalize has emptied both. Migrating and Expired pools are moving or
+/// paying out what they hold.
+pub fn handle(ctx: Context<SweepDonations>) -> Result<()> {
+    let pool = &ctx.accounts.pool;
+    let mint_key = ctx.accounts.token_mint.key();
+    require!(
+        matches!(pool.phase, PoolPhase::Active | PoolPhase::Graduated),
+        CustomError::BadPhase
+    );
+
+    // 1) Surplus tokens -> burned; never below what the curve position says
+    //    the pool still has to sell
+    let unsold = cap_base(ctx.accounts.token_mint.decimals).saturating_sub(pool.total_sold);
+    let tokens = ctx
+        .accounts
+        .pool_token_account
+        .amount
+        .saturating_sub(pool.reserve_token.max(unsold));
+    if tokens > 0 {
+        let pool_seeds = &[
+            LiquidityPool::POOL_SEED_PREFIX.as_bytes(),
+            mint_key.as_ref(),
+            &[pool.bump],
+        ];
+        token::burn(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::Burn {
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    from: ctx.accounts.pool_token_account.to_account_info(),
+                    authority: pool.to_account_info(),
+                },
+                &[&pool_seeds[..]],
+            ),
+            tokens,
+        )?;
+    }
+
+    // 2) Surplus lamports -> protocol fee PDA; the vault keeps reserves, seed and rent
+    let keep = pool.reserve_sol.saturating_add(pool.vault_floor()?);
+    let lamports = ctx.accounts.pool_sol_vault.lamports().saturating_sub(keep);
+    if lamports > 0 {
+        let vault_seeds = &[
+            LiquidityPool::SOL_VAULT_PREFIX.as_bytes(),
+            mint_key.as_ref(),
+            &[pool.vault_bump],
+        ];
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.pool_sol_vault.to_account_info(),
+                    to: ctx.accounts.protocol_fee_vault.to_account_info(),
+                },
+                &[&vault_seeds[..]],
+            ),
+            lamports,
+        )?;
+    }
+
+    emit!(crate::DonationsSwept {
+        pool: pool.key(),
+        tokens_burned: tokens,
+        lamports,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SweepDonations<'info> {
+    #[account(
+        seeds = [LiquidityPool::POOL_SEED_PREFIX.as_bytes(), token_mint.key().as_ref()],
+        bump = pool.bump,
+    )]
+    pub pool: Box<Account<'info, LiquidityPool>>,
+
+    #[account(mut)]
+    pub token_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = pool
+    )]
+    pub pool_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: SOL vault PDA
+    #[account(
+        mut,
+        seeds = [LiquidityPool::SOL_VAULT_PREFIX.as_bytes(), token_mint.key().as_ref()],
+        bump = pool.vault_bump
+    )]
+    pub pool_sol_vault: AccountInfo<'info>,
+
+    /// CHECK: protocol fee PDA (system-owned, lamports only); receives donated lamports
+    #[account(
+        mut,
+        seeds = [LiquidityPool::PROTOCOL_FEE_PREFIX.as_bytes()],
+        bump
+    )]
+    pub protocol_fee_vault: AccountInfo<'info>,
+
+    // Anyone
+    pub caller: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
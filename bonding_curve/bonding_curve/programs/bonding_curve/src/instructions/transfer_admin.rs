@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::CustomError, state::GlobalConfig};
+
+/// Step one of an admin handover: nominate `new_admin`, who must then call
+/// accept_admin. Nominating again replaces the pending admin.
+pub fn handle(ctx: Context<TransferAdmin>, new_admin: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.dex_configuration_account;
+    require!(new_admin != Pubkey::default(), CustomError::InvalidConfig);
+
+    config.pending_admin = Some(new_admin);
+
+    emit!(crate::AdminTransferProposed {
+        admin: config.admin,
+        pending_admin: new_admin,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct TransferAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [GlobalConfig::SEED.as_bytes()],
+        bump,
+        has_one = admin @ CustomError::Unauthorized,
+    )]
+    pub dex_configuration_account: Box<Account<'info, GlobalConfig>>,
+
+    pub admin: Signer<'info>,
+}
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::CustomError,
+    state::{ConfigUpdate, GlobalConfig},
+};
+
+pub fn handle(ctx: Context<UpdateConfig>, update: ConfigUpdate) -> Result<()> {
+    let config = &mut ctx.accounts.dex_configuration_account;
+
+    if let Some(v) = update.fees {
+        config.fees = v;
+    }
+    if let Some(v) = update.initial_lamports_for_pool {
+        config.initial_lamports_for_pool = v;
+    }
+    if let Some(v) = update.migration_fee_bps {
+        config.migration_fee_bps = v;
+    }
+    if let Some(v) = update.max_creator_bonus_bps {
+        config.max_creator_bonus_bps = v;
+    }
+    if let Some(v) = update.guardian {
+        config.guardian = v;
+    }
+    if let Some(v) = update.amm_allowlist {
+        config.amm_allowlist = v;
+    }
+    config.validate()?;
+
+    emit!(crate::ConfigUpdated {
+        admin: config.admin,
+        fees: config.fees,
+        initial_lamports_for_pool: config.initial_lamports_for_pool,
+        migration_fee_bps: config.migration_fee_bps,
+        max_creator_bonus_bps: config.max_creator_bonus_bps,
+        guardian: config.guardian,
+        amm_programs: config.amm_allowlist.len() as u8,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        mut,
+        seeds = [GlobalConfig::SEED.as_bytes()],
+        bump,
+        has_one = admin @ CustomError::Unauthorized,
+    )]
+    pub dex_configuration_account: Box<Account<'info, GlobalConfig>>,
+
+    pub admin: Signer<'info>,
+}
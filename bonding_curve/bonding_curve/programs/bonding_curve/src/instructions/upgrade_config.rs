@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use anchor_lang::system_program;
+use anchor_lang::Discriminator;
+
+use crate::{
+    errors::CustomError,
+    state::{CurveConfiguration, GlobalConfig},
+};
+
+/// Rewrites a deployment's old `CurveConfiguration` (fees only) in place as a
+/// `GlobalConfig`: same PDA, grown to the new size, fees carried over, the
+/// rest from the compiled-in defaults. The old account had no admin, so only
+/// the program's upgrade authority may do this, and it becomes the admin.
+pub fn handle(ctx: Context<UpgradeConfig>) -> Result<()> {
+    let config_info = ctx.accounts.dex_configuration_account.to_account_info();
+    require_keys_eq!(*config_info.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+
+    // 1) Only the legacy layout is upgraded
+    let fees = {
+        let data = config_info.try_borrow_data()?;
+        require!(
+            data.starts_with(CurveConfiguration::DISCRIMINATOR),
+            CustomError::ConfigAlreadyUpgraded
+        );
+        CurveConfiguration::try_deserialize(&mut &data[..])?.fees
+    };
+
+    // 2) Grow the account, authority pays the extra rent
+    let needed = Rent::get()?
+        .minimum_balance(GlobalConfig::ACCOUNT_SIZE)
+        .saturating_sub(config_info.lamports());
+    if needed > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: config_info.clone(),
+                },
+            ),
+            needed,
+        )?;
+    }
+    config_info.realloc(GlobalConfig::ACCOUNT_SIZE, true)?;
+
+    // 3) Write the new layout
+    let config = GlobalConfig::new(ctx.accounts.authority.key(), fees);
+    config.validate()?;
+    let mut data = config_info.try_borrow_mut_data()?;
+    config.try_serialize(&mut &mut data[..])?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpgradeConfig<'info> {
+    /// CHECK: config PDA in the legacy CurveConfiguration layout; decoded by hand
+    #[account(
+        mut,
+        seeds = [GlobalConfig::SEED.as_bytes()],
+        bump
+    )]
+    pub dex_configuration_account: UncheckedAccount<'info>,
+
+    // This program's ProgramData: the loader derives it from the program id
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(authority.key()) @ CustomError::Unauthorized
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    // Program upgrade authority; becomes the config admin and pays the extra rent
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
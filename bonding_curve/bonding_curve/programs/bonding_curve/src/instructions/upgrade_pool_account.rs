@@ -0,0 +1,162 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+use anchor_lang::system_program;
+use anchor_spl::token::{Mint, TokenAccount};
+
+use crate::{
+    errors::CustomError,
+    state::{AmmKind, GlobalConfig, LiquidityPool, PoolPhase},
+    utils::curve::{tier_ends_base, x_from_y, y_sold_from_pool},
+};
+
+/// Brings a pool in the original 221-byte layout up to `LiquidityPool::VERSION`:
+/// grows the account to `LiquidityPool::ACCOUNT_SIZE` (the new tail reads as
+/// zero = defaults, version 0) and backfills what the zeroed fields can't
+/// express. Idempotent; a current pool is left untouched.
+pub fn handle(ctx: Context<UpgradePoolAccount>, amm_program: Option<Pubkey>) -> Result<()> {
+    let pool_info = ctx.accounts.pool.to_account_info();
+    require_keys_eq!(*pool_info.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+
+    // 1) Grow the account, authority pays the extra rent
+    let current_len = pool_info.data_len();
+    if current_len < LiquidityPool::ACCOUNT_SIZE {
+        let needed = Rent::get()?
+            .minimum_balance(LiquidityPool::ACCOUNT_SIZE)
+            .saturating_sub(pool_info.lamports());
+        if needed > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.authority.to_account_info(),
+                        to: pool_info.clone(),
+                    },
+                ),
+                needed,
+            )?;
+        }
+        pool_info.realloc(LiquidityPool::ACCOUNT_SIZE, true)?;
+    }
+
+    // 2) Decode with the current layout
+    let mut pool = {
+        let data = pool_info.try_borrow_data()?;
+        LiquidityPool::try_deserialize(&mut &data[..])?
+    };
+    let from_version = pool.version;
+
+    // Pools from before the migration fields have no migration authority;
+    // create_pool has always made it the creator
+    if from_version < 1 && pool.migration_authority == Pubkey::default() {
+        pool.migration_authority = pool.creator;
+    }
+    let authority = ctx.accounts.authority.key();
+    require!(
+        authority == pool.migration_authority
+            || authority == ctx.accounts.dex_configuration_account.admin,
+        CustomError::Unauthorized
+    );
+
+    if from_version >= LiquidityPool::VERSION {
+        return Ok(());
+    }
+
+    // 3) v0 -> v1: everything past the original layout
+    if from_version < 1 {
+        // Everything before the AMM choice graduated to Raydium CPMM
+        pool.amm_kind = AmmKind::RaydiumCpmm;
+        pool.amm_program = amm_program.ok_or_else(|| error!(CustomError::InvalidProgram))?;
+        require!(
+            ctx.accounts
+                .dex_configuration_account
+                .allows_amm(pool.amm_kind, &pool.amm_program),
+            CustomError::InvalidProgram
+        );
+
+        // The vault used to sign with whatever bump it was handed
+        pool.vault_bump = ctx.bumps.pool_sol_vault;
+
+        // Explicit curve position, seeded from the balance-derived one
+        pool.total_sold = y_sold_from_pool(pool.reserve_token, ctx.accounts.token_mint.decimals);
+        pool.total_sol_in =
+            (x_from_y(&pool.tiers, pool.total_sold) * LAMPORTS_PER_SOL as f64) as u64;
+
+        // reserve_sol used to count the whole vault; whatever of it the
+        // trades didn't bring in is rent + seed
+        let non_curve = pool.reserve_sol.saturating_sub(pool.total_sol_in);
+        pool.seed_sol = non_curve.saturating_sub(Rent::get()?.minimum_balance(0));
+        pool.reserve_sol -= non_curve;
+
+        // buy used to snapshot the reserves before the cap-filling trade; the
+        // snapshot is what the migration escrow holds (nothing until
+        // start_migration runs, which then records it)
+        if matches!(pool.phase, PoolPhase::Migrating) {
+            pool.reserve_snapshot_token = ctx
+                .accounts
+                .escrow_token_account
+                .as_ref()
+                .map_or(0, |a| a.amount);
+            pool.reserve_snapshot_sol = ctx.accounts.migration_escrow.lamports();
+        }
+
+        // Tiers below the curve position have been completed already
+        for (i, end) in tier_ends_base(&pool.tiers).into_iter().enumerate() {
+            if end <= pool.total_sold {
+                pool.bonus_paid_tiers |= 1u8 << i;
+            }
+        }
+    }
+    pool.version = LiquidityPool::VERSION;
+
+    let mut data = pool_info.try_borrow_mut_data()?;
+    pool.try_serialize(&mut &mut data[..])?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpgradePoolAccount<'info> {
+    /// CHECK: pool PDA in a possibly older layout; decoded by hand after the resize
+    #[account(
+        mut,
+        seeds = [LiquidityPool::POOL_SEED_PREFIX.as_bytes(), token_mint.key().as_ref()],
+        bump
+    )]
+    pub pool: UncheckedAccount<'info>,
+
+    pub token_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: SOL vault PDA; only its canonical bump is read
+    #[account(
+        seeds = [LiquidityPool::SOL_VAULT_PREFIX.as_bytes(), token_mint.key().as_ref()],
+        bump
+    )]
+    pub pool_sol_vault: AccountInfo<'info>,
+
+    /// CHECK: migration escrow PDA (read-only); snapshot backfill
+    #[account(
+        seeds = [LiquidityPool::MIGRATION_ESCROW_PREFIX.as_bytes(), token_mint.key().as_ref()],
+        bump
+    )]
+    pub migration_escrow: AccountInfo<'info>,
+
+    // Escrow's token ATA, if start_migration created it
+    #[account(
+        associated_token::mint = token_mint,
+        associated_token::authority = migration_escrow
+    )]
+    pub escrow_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(
+        seeds = [GlobalConfig::SEED.as_bytes()],
+        bump,
+    )]
+    pub dex_configuration_account: Box<Account<'info, GlobalConfig>>,
+
+    // The pool's migration_authority (the creator, for pools that predate
+    // it) or the config admin, checked after decoding; pays the extra rent
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
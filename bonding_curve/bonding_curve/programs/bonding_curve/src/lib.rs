@@ -6,16 +6,39 @@ pub mod instructions;
 pub mod state;
 pub mod utils;
 
+use instructions::abort_migration::*;
+use instructions::accept_admin::*;
 use instructions::add_liquidity::*;
 use instructions::buy::*;
+use instructions::check_invariants::*;
+use instructions::claim_tier_bonus::*;
+use instructions::claim_vested::*;
+use instructions::close_graduated_pool::*;
 use instructions::create_pool::*; 
+use instructions::curve_view::*;
 use instructions::finalize_migration::*;
 use instructions::initialize::*;
+use instructions::launch_token::*;
+use instructions::reclaim_expired_pool::*;
+use instructions::redeem::*;
+use instructions::release_lp::*;
 use instructions::remove_liquidity::*;
+use instructions::request_emergency_withdraw::*;
+use instructions::cancel_emergency_withdraw::*;
 use instructions::sell::*;
+use instructions::set_paused::*;
+use instructions::set_pool_paused::*;
 use instructions::start_migration::*;
+use instructions::sweep_donations::*;
+use instructions::transfer_admin::*;
+use instructions::update_config::*;
+use instructions::upgrade_config::*;
+use instructions::upgrade_pool_account::*;
 
 use instructions::create_pool::CreatePool;
+use state::{
+    AmmTarget, ConfigUpdate, LpLockConfig, Tier, TierBonusConfig, TokenMetadataArgs, VestingConfig,
+};
 
 declare_id!("EcmMaHYxoz3VhNg8M8TBFVAc7Xy4VHW6nBBWhPyE8HrP");
 
@@ -27,38 +50,259 @@ pub mod bonding_curve {
         crate::instructions::initialize::handle(ctx, fee)
     }
 
+    pub fn upgrade_config(ctx: Context<UpgradeConfig>) -> Result<()> {
+        crate::instructions::upgrade_config::handle(ctx)
+    }
+
+    pub fn update_config(ctx: Context<UpdateConfig>, update: ConfigUpdate) -> Result<()> {
+        crate::instructions::update_config::handle(ctx, update)
+    }
+
+    pub fn transfer_admin(ctx: Context<TransferAdmin>, new_admin: Pubkey) -> Result<()> {
+        crate::instructions::transfer_admin::handle(ctx, new_admin)
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        crate::instructions::accept_admin::handle(ctx)
+    }
+
+    pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+        crate::instructions::set_paused::handle(ctx, paused)
+    }
+
+    pub fn set_pool_paused(ctx: Context<SetPoolPaused>, paused: bool) -> Result<()> {
+        crate::instructions::set_pool_paused::handle(ctx, paused)
+    }
+
+    pub fn request_emergency_withdraw(ctx: Context<RequestEmergencyWithdraw>) -> Result<()> {
+        crate::instructions::request_emergency_withdraw::handle(ctx)
+    }
+
+    pub fn cancel_emergency_withdraw(ctx: Context<CancelEmergencyWithdraw>) -> Result<()> {
+        crate::instructions::cancel_emergency_withdraw::handle(ctx)
+    }
+
+    pub fn sweep_donations(ctx: Context<SweepDonations>) -> Result<()> {
+        crate::instructions::sweep_donations::handle(ctx)
+    }
+
+    pub fn check_invariants(ctx: Context<CheckInvariants>) -> Result<InvariantsChecked> {
+        crate::instructions::check_invariants::handle(ctx)
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn create_pool(
         ctx: Context<CreatePool>, // ✅ Now this resolves cleanly
         migration_authority: Pubkey,
+        tiers: Vec<Tier>,
+        tier_ratchet: bool,
+        vesting: Option<VestingConfig>,
+        bonus: Option<TierBonusConfig>,
+        lp_lock: Option<LpLockConfig>,
+        amm: AmmTarget,
+        launch_deadline: Option<i64>,
+        creator_bonus_bps: Option<u16>,
+    ) -> Result<()> {
+        instructions::create_pool::handle(
+            ctx,
+            migration_authority,
+            tiers,
+            tier_ratchet,
+            vesting,
+            bonus,
+            lp_lock,
+            amm,
+            launch_deadline,
+            creator_bonus_bps,
+        )
+    }
+
+    /// Mint, metadata, pool, vault, treasury, supply split, authority revoke
+    /// and an optional first buy, all or nothing.
+    #[allow(clippy::too_many_arguments)]
+    pub fn launch_token(
+        ctx: Context<LaunchToken>,
+        metadata: TokenMetadataArgs,
+        migration_authority: Pubkey,
+        tiers: Vec<Tier>,
+        tier_ratchet: bool,
+        vesting: Option<VestingConfig>,
+        bonus: Option<TierBonusConfig>,
+        lp_lock: Option<LpLockConfig>,
+        amm: AmmTarget,
+        launch_deadline: Option<i64>,
+        creator_bonus_bps: Option<u16>,
+        initial_buy: Option<u64>,
     ) -> Result<()> {
-        instructions::create_pool::handle(ctx, migration_authority)
+        instructions::launch_token::handle(
+            ctx,
+            metadata,
+            migration_authority,
+            tiers,
+            tier_ratchet,
+            vesting,
+            bonus,
+            lp_lock,
+            amm,
+            launch_deadline,
+            creator_bonus_bps,
+            initial_buy,
+        )
     }
 
     pub fn add_liquidity(ctx: Context<AddLiquidity>) -> Result<()> {
         crate::instructions::add_liquidity::handle(ctx)
     }
 
-    pub fn remove_liquidity(ctx: Context<RemoveLiquidity>, bump: u8) -> Result<()> {
-        crate::instructions::remove_liquidity::handle(ctx, bump)
+    /// Deprecated: use remove_liquidity_v2. Takes the original account list;
+    /// `bump` is ignored, the vault signs with the bump stored on the pool.
+    pub fn remove_liquidity(ctx: Context<LegacyRemoveLiquidity>, bump: u8) -> Result<()> {
+        let _ = bump;
+        crate::instructions::remove_liquidity::handle_legacy(ctx)
+    }
+
+    pub fn remove_liquidity_v2(ctx: Context<RemoveLiquidity>) -> Result<()> {
+        crate::instructions::remove_liquidity::handle(ctx)
     }
 
     pub fn buy(ctx: Context<Buy>, amount: u64) -> Result<()> {
         crate::instructions::buy::handle(ctx, amount)
     }
 
-    pub fn sell(ctx: Context<Sell>, amount: u64, bump: u8) -> Result<()> {
-        crate::instructions::sell::handle(ctx, amount, bump)
+    /// Deprecated: use sell_v2. Takes the original account list; `bump` is
+    /// ignored, the vault signs with the bump stored on the pool.
+    pub fn sell(ctx: Context<LegacySell>, amount: u64, bump: u8) -> Result<()> {
+        let _ = bump;
+        crate::instructions::sell::handle_legacy(ctx, amount)
+    }
+
+    pub fn sell_v2(ctx: Context<Sell>, amount: u64) -> Result<()> {
+        crate::instructions::sell::handle(ctx, amount)
+    }
+
+    pub fn claim_vested(ctx: Context<ClaimVested>) -> Result<()> {
+        crate::instructions::claim_vested::handle(ctx)
+    }
+
+    pub fn claim_tier_bonus(ctx: Context<ClaimTierBonus>, tier: u8) -> Result<()> {
+        crate::instructions::claim_tier_bonus::handle(ctx, tier)
+    }
+
+    pub fn get_curve_view(ctx: Context<GetCurveView>) -> Result<CurveView> {
+        crate::instructions::curve_view::handle(ctx)
+    }
+
+    pub fn release_lp(ctx: Context<ReleaseLp>) -> Result<()> {
+        crate::instructions::release_lp::handle(ctx)
+    }
+
+    pub fn upgrade_pool_account(
+        ctx: Context<UpgradePoolAccount>,
+        amm_program: Option<Pubkey>,
+    ) -> Result<()> {
+        crate::instructions::upgrade_pool_account::handle(ctx, amm_program)
     }
 
     pub fn start_migration(ctx: Context<StartMigration>) -> Result<()> {
         crate::instructions::start_migration::handle(ctx)
     }
 
-    pub fn finalize_migration(ctx: Context<FinalizeMigration>) -> Result<()> {
+    pub fn abort_migration(ctx: Context<AbortMigration>) -> Result<()> {
+        crate::instructions::abort_migration::handle(ctx)
+    }
+
+    pub fn redeem(ctx: Context<Redeem>, amount: u64) -> Result<()> {
+        crate::instructions::redeem::handle(ctx, amount)
+    }
+
+    pub fn reclaim_expired_pool(ctx: Context<ReclaimExpiredPool>) -> Result<()> {
+        crate::instructions::reclaim_expired_pool::handle(ctx)
+    }
+
+    pub fn finalize_migration<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FinalizeMigration<'info>>,
+    ) -> Result<()> {
         crate::instructions::finalize_migration::handle(ctx)
     }
+
+    pub fn close_graduated_pool(ctx: Context<CloseGraduatedPool>) -> Result<()> {
+        crate::instructions::close_graduated_pool::handle(ctx)
+    }
+}
+
+
+#[event]
+pub struct ConfigUpdated {
+    pub admin: Pubkey,
+    pub fees: f64,
+    pub initial_lamports_for_pool: u64,
+    pub migration_fee_bps: u16,
+    pub max_creator_bonus_bps: u16,
+    pub guardian: Pubkey,
+    pub amm_programs: u8,
+}
+
+#[event]
+pub struct Paused {
+    pub pool: Option<Pubkey>, // None = the whole protocol
+    pub guardian: Pubkey,
+}
+
+#[event]
+pub struct Unpaused {
+    pub pool: Option<Pubkey>, // None = the whole protocol
+    pub guardian: Pubkey,
+}
+
+#[event]
+pub struct AdminTransferProposed {
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+}
+
+#[event]
+pub struct AdminTransferred {
+    pub previous_admin: Pubkey,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct EmergencyWithdrawRequested {
+    pub pool: Pubkey,
+    pub admin: Pubkey,
+    pub unlock_slot: u64,
+}
+
+#[event]
+pub struct EmergencyWithdrawCancelled {
+    pub pool: Pubkey,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct LiquidityRemoved {
+    pub pool: Pubkey,
+    pub by: Pubkey,
+    pub tokens: u64,
+    pub lamports: u64,
+}
+
+#[event]
+pub struct DonationsSwept {
+    pub pool: Pubkey,
+    pub tokens_burned: u64,
+    pub lamports: u64,
 }
 
+#[event]
+pub struct InvariantsChecked {
+    pub pool: Pubkey,
+    pub vault_lamports: u64,
+    pub reserve_sol: u64,
+    pub required_sol: u64,    // rent + seed + sell-back value of total_sold
+    pub reserve_drift: i64,   // vault_lamports - vault_floor - reserve_sol; > 0 is donations, < 0 a deficit
+    pub ok: bool,
+}
 
 #[event]
 pub struct CapReached {
@@ -75,11 +319,66 @@ pub struct MigrationStarted {
     pub slot: u64,
     pub reserve_token: u64,
     pub reserve_sol: u64,
+    pub migration_fee: u64,   // protocol cut finalize will take from the escrowed reserve_sol
+    pub creator_bonus: u64,   // creator cut finalize will take from the escrowed reserve_sol
+}
+
+#[event]
+pub struct MigrationAborted {
+    pub pool: Pubkey,
+    pub slot: u64,
+    pub reserve_token: u64,
+    pub reserve_sol: u64,
+}
+
+#[event]
+pub struct PoolExpired {
+    pub pool: Pubkey,
+    pub slot: u64,
+    pub tokens_outstanding: u64,
+    pub sol_redeemable: u64,
+}
+
+#[event]
+pub struct Redeemed {
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub lamports: u64,
+}
+
+#[event]
+pub struct ExpiredPoolReclaimed {
+    pub pool: Pubkey,
+    pub lamports: u64,      // to the creator
+    pub vault_closed: bool, // everything redeemed: rent and dust went too
 }
 
 #[event]
 pub struct MigrationFinalized {
     pub pool: Pubkey,
-    pub raydium_pool: Pubkey,
+    pub amm_pool: Pubkey,
     pub lp_timelock: Option<Pubkey>,
+    pub migration_fee: u64,
+    pub creator_bonus: u64,
+    pub seed_returned: u64,   // creator's SOL seed, out of the vault
+    pub crank_reward: u64,    // to a non-authority caller, out of that seed
+    pub reserve_returned: u64, // unspent AMM creation reserve, out of the escrow
+    pub token_seeded: u64,    // what actually reached the AMM
+    pub sol_seeded: u64,
+}
+
+#[event]
+pub struct GraduatedPoolClosed {
+    pub pool: Pubkey,
+    pub amm_pool: Pubkey,
+    pub rent_refunded: u64,
+    pub dust_swept: u64,
+}
+
+#[event]
+pub struct LpReleased {
+    pub pool: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
 }
\ No newline at end of file
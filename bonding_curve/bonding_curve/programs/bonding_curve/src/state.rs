@@ -3,16 +3,140 @@ use crate::errors::CustomError;
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
 use anchor_spl::token::{self, Mint, Token, TokenAccount};
-use crate::utils::curve::{cap_base, y_sold_from_pool, buy_on_curve, sell_on_curve};
-use crate::consts::{INITIAL_LAMPORTS_FOR_POOL, INITIAL_PRICE_DIVIDER};
-
+use crate::utils::curve::{
+    CAP_TOKENS, LUT_DECIMALS, cap_base, completed_tier_floor, tier_ends_base, buy_on_curve,
+    sell_on_curve, x_from_y,
+};
+use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+use crate::consts::{
+    ALLOWLISTED_METEORA_PROGRAMS, ALLOWLISTED_ORCA_PROGRAMS, ALLOWLISTED_RAYDIUM_PROGRAMS,
+    INITIAL_LAMPORTS_FOR_POOL, MAX_AMM_ALLOWLIST,
+    MAX_CREATOR_GRADUATION_BONUS_BPS, MAX_TIERS, MIGRATION_ABORT_DELAY_SLOTS, MIGRATION_FEE_BPS,
+    MIGRATION_GRACE_SLOTS, SOLD_CAP, SOLVENCY_TOLERANCE_LAMPORTS,
+    TREASURY_SUPPLY,
+};
+
+// The curve LUT is built for CAP_TOKENS; another cap needs another LUT
+const _: () = assert!(SOLD_CAP == CAP_TOKENS);
+
+// Variant order is part of the account layout (Graduated was RaydiumLive).
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
 pub enum PoolPhase {
     Active,        // Bonding curve live
     Migrating,     // Curve locked, awaiting AMM pool creation
-    RaydiumLive,   // Live on Raydium
+    Graduated,     // Live on the pool's AMM
+    Expired,       // Deadline passed below the cap; holders redeem for vault SOL
+}
+
+/// AMM a pool graduates to; each has an adapter in `utils::amm`.
+/// Zero (RaydiumCpmm) is what pools created before the choice existed used.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum AmmKind {
+    RaydiumCpmm,
+    OrcaWhirlpool,
+    MeteoraDamm,
+}
+
+impl AmmKind {
+    /// Programs a fresh GlobalConfig allowlists for this kind.
+    pub fn default_programs(&self) -> &'static [Pubkey] {
+        match self {
+            AmmKind::RaydiumCpmm => ALLOWLISTED_RAYDIUM_PROGRAMS,
+            AmmKind::OrcaWhirlpool => ALLOWLISTED_ORCA_PROGRAMS,
+            AmmKind::MeteoraDamm => ALLOWLISTED_METEORA_PROGRAMS,
+        }
+    }
+
+    /// Token program of the LP mint (or position NFT) the AMM hands back.
+    pub fn lp_token_program(&self) -> Pubkey {
+        match self {
+            AmmKind::RaydiumCpmm | AmmKind::OrcaWhirlpool => token::ID,
+            AmmKind::MeteoraDamm => anchor_spl::token_2022::ID,
+        }
+    }
+}
+
+/// Migration target chosen at create_pool.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct AmmTarget {
+    pub kind: AmmKind,
+    pub program: Pubkey,
+}
+
+impl AmmTarget {
+    // kind (1) + program (32)
+    pub const SIZE: usize = 1 + 32;
+
+    pub fn validate(&self, config: &GlobalConfig) -> Result<()> {
+        require!(
+            config.allows_amm(self.kind, &self.program),
+            CustomError::InvalidProgram
+        );
+        Ok(())
+    }
+}
+
+/// Metaplex metadata for a token minted by launch_token.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct TokenMetadataArgs {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
 }
 
+impl TokenMetadataArgs {
+    // Metaplex limits
+    pub const MAX_NAME_LEN: usize = 32;
+    pub const MAX_SYMBOL_LEN: usize = 10;
+    pub const MAX_URI_LEN: usize = 200;
+
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            !self.name.is_empty()
+                && self.name.len() <= Self::MAX_NAME_LEN
+                && !self.symbol.is_empty()
+                && self.symbol.len() <= Self::MAX_SYMBOL_LEN
+                && !self.uri.is_empty()
+                && self.uri.len() <= Self::MAX_URI_LEN,
+            CustomError::InvalidTokenMetadata
+        );
+        Ok(())
+    }
+}
+
+/// One segment of a pool's tier schedule.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct Tier {
+    pub sol_width: u64,   // lamports of curve SOL spanned by this tier
+    pub token_alloc: u64, // whole tokens sold inside this tier
+}
+
+impl Tier {
+    // sol_width (8) + token_alloc (8)
+    pub const SIZE: usize = 8 + 8;
+
+    /// Empty = legacy three equal tiers. Otherwise 1..=MAX_TIERS non-empty
+    /// tiers whose allocations sum exactly to SOLD_CAP.
+    pub fn validate_schedule(tiers: &[Tier]) -> Result<()> {
+        if tiers.is_empty() {
+            return Ok(());
+        }
+        require!(tiers.len() <= MAX_TIERS, CustomError::InvalidTierSchedule);
+
+        // Widths are free: each tier's price is its own width / allocation
+        let mut total_alloc: u64 = 0;
+        for t in tiers {
+            require!(t.sol_width > 0 && t.token_alloc > 0, CustomError::InvalidTierSchedule);
+            total_alloc = total_alloc
+                .checked_add(t.token_alloc)
+                .ok_or_else(|| error!(CustomError::OverflowOrUnderflowOccurred))?;
+        }
+        require!(total_alloc == SOLD_CAP, CustomError::InvalidTierSchedule);
+        Ok(())
+    }
+}
+
+/// Pre-GlobalConfig layout of the config PDA; only read by upgrade_config.
 #[account]
 #[derive(InitSpace)]
 pub struct CurveConfiguration {
@@ -20,14 +144,103 @@ pub struct CurveConfiguration {
 }
 
 impl CurveConfiguration {
-    pub const SEED: &'static str = "CurveConfiguration";
-
     // Discriminator (8) + (we historically kept 32 here; left as-is for your layout) + f64 (8)
     pub const ACCOUNT_SIZE: usize = 8 + 32 + 8;
+}
+
+/// Program-wide settings, at the PDA CurveConfiguration used to live at.
+#[account]
+pub struct GlobalConfig {
+    pub version: u8,
+    pub admin: Pubkey,
+    pub pending_admin: Option<Pubkey>, // set by transfer_admin, cleared by accept_admin
+
+    pub fees: f64,                      // percent, 0..=100
+    pub initial_lamports_for_pool: u64, // SOL seed add_liquidity puts in the vault
+    pub migration_fee_bps: u16,         // protocol cut at graduation, fixed per pool at creation
+    pub max_creator_bonus_bps: u16,     // cap on a creator's graduation bonus
+
+    // Emergency stop; only the guardian flips it (default = no guardian yet)
+    pub guardian: Pubkey,
+    pub paused: bool,
+
+    // Only variable-length field; keep it last
+    pub amm_allowlist: Vec<AmmTarget>,  // AMM programs pools may graduate to
+}
 
-    pub fn new(fees: f64) -> Self {
-        Self { fees }
+impl GlobalConfig {
+    pub const SEED: &'static str = "CurveConfiguration";
+    pub const VERSION: u8 = 1;
+
+    // 8 discriminator + version(1) + admin(32) + pending_admin(33) + fees(8)
+    // + initial_lamports_for_pool(8) + migration_fee_bps(2) + max_creator_bonus_bps(2)
+    // + guardian(32) + paused(1) + amm_allowlist(4 + MAX_AMM_ALLOWLIST * 33) = 131 + 264 = 395
+    pub const ACCOUNT_SIZE: usize =
+        8 + 1 + 32 + 33 + 8 + 8 + 2 + 2 + 32 + 1 + 4 + MAX_AMM_ALLOWLIST * AmmTarget::SIZE;
+
+    /// Config with the compiled-in defaults from `consts.rs`.
+    pub fn new(admin: Pubkey, fees: f64) -> Self {
+        let amm_allowlist = [AmmKind::RaydiumCpmm, AmmKind::OrcaWhirlpool, AmmKind::MeteoraDamm]
+            .iter()
+            .flat_map(|&kind| {
+                kind.default_programs()
+                    .iter()
+                    .map(move |&program| AmmTarget { kind, program })
+            })
+            .collect();
+        Self {
+            version: Self::VERSION,
+            admin,
+            pending_admin: None,
+            fees,
+            initial_lamports_for_pool: INITIAL_LAMPORTS_FOR_POOL,
+            migration_fee_bps: MIGRATION_FEE_BPS,
+            max_creator_bonus_bps: MAX_CREATOR_GRADUATION_BONUS_BPS,
+            guardian: Pubkey::default(),
+            paused: false,
+            amm_allowlist,
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        require!((0_f64..=100_f64).contains(&self.fees), CustomError::InvalidFee);
+        require!(
+            self.migration_fee_bps as u32 + self.max_creator_bonus_bps as u32 <= 10_000,
+            CustomError::InvalidConfig
+        );
+        require!(
+            self.amm_allowlist.len() <= MAX_AMM_ALLOWLIST
+                && self.amm_allowlist.iter().all(|t| t.program != Pubkey::default()),
+            CustomError::InvalidConfig
+        );
+        // The guardian is a separate, hot key; it must not be the admin
+        require!(self.guardian != self.admin, CustomError::InvalidConfig);
+        Ok(())
     }
+
+    /// Trading, liquidity and migration all stop while either switch is on.
+    pub fn require_unpaused(&self, pool: &LiquidityPool) -> Result<()> {
+        require!(!self.paused, CustomError::ProtocolPaused);
+        require!(!pool.paused, CustomError::PoolPaused);
+        Ok(())
+    }
+
+    pub fn allows_amm(&self, kind: AmmKind, program: &Pubkey) -> bool {
+        self.amm_allowlist
+            .iter()
+            .any(|t| t.kind == kind && t.program == *program)
+    }
+}
+
+/// Fields update_config may change; None leaves the current value.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ConfigUpdate {
+    pub fees: Option<f64>,
+    pub initial_lamports_for_pool: Option<u64>,
+    pub migration_fee_bps: Option<u16>,
+    pub max_creator_bonus_bps: Option<u16>,
+    pub guardian: Option<Pubkey>,
+    pub amm_allowlist: Option<Vec<AmmTarget>>,
 }
 
 #[account]
@@ -42,6 +255,141 @@ impl LiquidityProvider {
     pub const ACCOUNT_SIZE: usize = 8 + 8;
 }
 
+/// Creator-chosen vesting for tokens bought in the first tiers.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct VestingConfig {
+    pub vested_tiers: u8, // buys inside tiers [0, vested_tiers) vest
+    pub duration: i64,    // linear vesting period in seconds
+}
+
+impl VestingConfig {
+    pub fn validate(&self, tiers: &[Tier]) -> Result<()> {
+        let tier_count = tier_ends_base(tiers).len();
+        require!(
+            self.vested_tiers > 0 && (self.vested_tiers as usize) <= tier_count && self.duration > 0,
+            CustomError::InvalidVestingConfig
+        );
+        Ok(())
+    }
+}
+
+/// Per-user escrow holding tokens bought inside vested tiers.
+/// The tokens sit in this PDA's ATA, so they cannot be sold until claimed.
+#[account]
+pub struct VestingEscrow {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub total: u64,    // tokens ever deposited
+    pub claimed: u64,  // tokens already released
+    pub start_ts: i64, // amount-weighted start of the linear schedule
+    pub bump: u8,
+}
+
+impl VestingEscrow {
+    pub const SEED_PREFIX: &'static str = "vesting_escrow";
+
+    // Discriminator (8) + pool (32) + owner (32) + total (8) + claimed (8) + start_ts (8) + bump (1)
+    pub const ACCOUNT_SIZE: usize = 8 + 32 + 32 + 8 + 8 + 8 + 1;
+
+    pub fn new(pool: Pubkey, owner: Pubkey, bump: u8) -> Self {
+        Self { pool, owner, total: 0, claimed: 0, start_ts: 0, bump }
+    }
+
+    /// Adds `amount` to the schedule, moving the start to the amount-weighted
+    /// average so new tokens don't inherit older tokens' progress.
+    pub fn deposit(&mut self, amount: u64, now: i64) -> Result<()> {
+        let new_total = self
+            .total
+            .checked_add(amount)
+            .ok_or_else(|| error!(CustomError::OverflowOrUnderflowOccurred))?;
+        let weighted = (self.start_ts as i128) * (self.total as i128) + (now as i128) * (amount as i128);
+        self.start_ts = (weighted / (new_total as i128)) as i64;
+        self.total = new_total;
+        Ok(())
+    }
+
+    pub fn vested(&self, now: i64, duration: i64) -> u64 {
+        let elapsed = now.saturating_sub(self.start_ts).max(0);
+        if duration <= 0 || elapsed >= duration {
+            return self.total;
+        }
+        ((self.total as u128) * (elapsed as u128) / (duration as u128)) as u64
+    }
+
+    pub fn claimable(&self, now: i64, duration: i64) -> u64 {
+        self.vested(now, duration).saturating_sub(self.claimed)
+    }
+}
+
+/// Who receives a completed tier's bonus.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum BonusMode {
+    CrossingBuyer, // the buy that completes the tier is paid immediately
+    ProRata,       // everyone who bought inside the tier claims a share
+}
+
+/// Creator-chosen share of the treasury paid out as tier-completion bonuses.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct TierBonusConfig {
+    pub per_tier: u64, // whole tokens set aside per tier
+    pub mode: BonusMode,
+}
+
+impl TierBonusConfig {
+    pub fn validate(&self, tiers: &[Tier]) -> Result<()> {
+        let tier_count = tier_ends_base(tiers).len() as u64;
+        let total = self
+            .per_tier
+            .checked_mul(tier_count)
+            .ok_or_else(|| error!(CustomError::OverflowOrUnderflowOccurred))?;
+        require!(
+            self.per_tier > 0 && total <= TREASURY_SUPPLY,
+            CustomError::InvalidBonusConfig
+        );
+        Ok(())
+    }
+}
+
+/// Per-user record of tokens bought inside each tier (pro-rata bonus weights).
+#[account]
+pub struct TierParticipation {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub bought: [u64; MAX_TIERS],
+    pub claimed_mask: u8, // bit i set = tier i bonus claimed
+    pub bump: u8,
+}
+
+impl TierParticipation {
+    pub const SEED_PREFIX: &'static str = "tier_participation";
+
+    // Discriminator (8) + pool (32) + owner (32) + bought (8 * MAX_TIERS) + claimed_mask (1) + bump (1)
+    pub const ACCOUNT_SIZE: usize = 8 + 32 + 32 + 8 * MAX_TIERS + 1 + 1;
+
+    pub fn new(pool: Pubkey, owner: Pubkey, bump: u8) -> Self {
+        Self { pool, owner, bought: [0; MAX_TIERS], claimed_mask: 0, bump }
+    }
+}
+
+/// What happens to the AMM LP tokens minted at migration.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct LpLockConfig {
+    pub lock_slots: u64,   // slots after finalize before release_lp opens
+    pub recipient: Pubkey, // who may call release_lp
+    pub burn: bool,        // burn the LP at finalize instead of locking it
+}
+
+impl LpLockConfig {
+    pub fn validate(&self) -> Result<()> {
+        // A burn has nothing to release; a lock needs someone to release to
+        require!(
+            self.burn != (self.recipient != Pubkey::default()),
+            CustomError::InvalidLpLockConfig
+        );
+        Ok(())
+    }
+}
+
 #[account]
 pub struct LiquidityPool {
     // --- existing fields you already rely on ---
@@ -55,34 +403,195 @@ pub struct LiquidityPool {
     // --- new fields for migration flow (append-only to keep layout compatibility) ---
     pub phase: PoolPhase,
     pub cap_reached_slot: Option<u64>,
-    pub raydium_pool: Option<Pubkey>,    // AMM/CLMM pool id (once created)
-    pub migration_authority: Pubkey,     // who can start/finalize migration
+    pub amm_pool: Option<Pubkey>,        // AMM pool id (once created)
+    pub migration_authority: Pubkey,     // may start/finalize migration any time
 
-    // Snapshots at the moment we enter Migrating
+    // What start_migration moved into the migration escrow (or, once Expired,
+    // the redeem split)
     pub reserve_snapshot_token: u64,
     pub reserve_snapshot_sol: u64,
 
-    // Optional locker address for LP tokens (record-only)
+    // LP escrow token account (program-owned) once migrated; None if burned
     pub lp_timelock: Option<Pubkey>,
+
+    // Tier schedule (empty = legacy three equal tiers over the full LUT)
+    pub tiers: Vec<Tier>,
+
+    // Ratchet: sells may not push cumulative sold below a completed tier's end
+    pub tier_ratchet: bool,
+
+    // Vesting of tokens bought in early tiers (vested_tiers = 0 means off)
+    pub vested_tiers: u8,
+    pub vesting_duration: i64,
+
+    // Tier completion bonus from the treasury (bonus_per_tier = 0 means off)
+    pub bonus_per_tier: u64,
+    pub bonus_mode: BonusMode,
+    pub bonus_paid: u64,               // base units already paid out
+    pub tier_bought: [u64; MAX_TIERS], // tokens bought inside each tier
+
+    // LP lock (no recipient = locked for good; lp_burn = burned at finalize)
+    pub lp_mint: Option<Pubkey>,       // AMM LP mint, set at finalize
+    pub lp_recipient: Pubkey,
+    pub lp_lock_slots: u64,
+    pub lp_unlock_slot: u64,           // finalize slot + lp_lock_slots
+    pub lp_burn: bool,
+
+    // Migration target (see AmmKind); program is checked against the kind's allowlist
+    pub amm_kind: AmmKind,
+    pub amm_program: Pubkey,
+
+    // Unix time after which an uncapped launch expires (0 = never)
+    pub launch_deadline: i64,
+
+    // Graduation cuts of the curve SOL, taken at finalize before AMM seeding
+    pub migration_fee_bps: u16,        // to the protocol fee PDA
+    pub creator_bonus_bps: u16,        // to the creator
+
+    // Slot finalize_migration ran (0 = not graduated yet)
+    pub graduation_slot: u64,
+
+    // Per-pool emergency stop, set by the config guardian
+    pub paused: bool,
+
+    // Slot from which the admin may emergency-withdraw (0 = not requested)
+    pub emergency_withdraw_slot: u64,
+
+    // Layout version (0 = created before versioning, see upgrade_pool_account)
+    pub version: u8,
+
+    // Canonical bump of the SOL vault PDA; the only bump the vault signs with
+    pub vault_bump: u8,
+
+    // Curve position; only buy/sell move it, so donations to the pool ATA or
+    // vault don't (see sweep_donations)
+    pub total_sold: u64,               // base units sold, net of sells
+    pub total_sol_in: u64,             // lamports taken in by the curve, net of sells
+
+    // Creator's SOL seed from add_liquidity; sits in the vault next to the
+    // rent, outside reserve_sol, and goes back to the creator at finalize
+    pub seed_sol: u64,
+
+    // Bit i set = tier i has been completed; its bonus is paid (or claimable)
+    // once and its pro-rata weights are frozen, however often it is re-crossed
+    pub bonus_paid_tiers: u8,
+
+    // Tokens burned through redeem; once it reaches reserve_snapshot_token the
+    // creator may close the Expired pool's vault (see reclaim_expired_pool)
+    pub tokens_redeemed: u64,
+
+    // Tokens sitting in vesting escrows, not yet claimed, and per tier the
+    // participation weight whose pro-rata bonus has been claimed. Both must
+    // settle before close_graduated_pool may retire the pool account
+    pub vesting_unclaimed: u64,
+    pub tier_bonus_claimed: [u64; MAX_TIERS],
+
+    // New fields go above this and shrink it by their size, so ACCOUNT_SIZE
+    // and existing accounts stay valid; bump VERSION if they need a backfill
+    pub reserved: [u8; LiquidityPool::RESERVED_BYTES],
+}
+
+/// What close_graduated_pool leaves at a graduated pool's PDA: where the
+/// token trades now and how its LP was locked.
+#[account]
+pub struct GraduatedPool {
+    pub token: Pubkey,
+    pub creator: Pubkey,
+    pub amm_kind: AmmKind,
+    pub amm_program: Pubkey,
+    pub amm_pool: Pubkey,
+    pub lp_mint: Option<Pubkey>,
+    pub lp_timelock: Option<Pubkey>,   // program-owned LP escrow ATA; None if burned
+    pub lp_unlock_slot: u64,
+    pub graduation_slot: u64,
+    pub bump: u8,
+}
+
+impl GraduatedPool {
+    // 8 discriminator + token(32) + creator(32) + amm_kind(1) + amm_program(32)
+    // + amm_pool(32) + lp_mint(33) + lp_timelock(33) + lp_unlock_slot(8)
+    // + graduation_slot(8) + bump(1) = 220
+    pub const ACCOUNT_SIZE: usize = 8 + 32 + 32 + 1 + 32 + 32 + 33 + 33 + 8 + 8 + 1;
 }
 
 impl LiquidityPool {
     pub const POOL_SEED_PREFIX: &'static str = "liquidity_pool";
     pub const SOL_VAULT_PREFIX: &'static str = "liquidity_sol_vault";
+    pub const TREASURY_SEED_PREFIX: &'static str = "treasury";
+    pub const LP_ESCROW_PREFIX: &'static str = "lp_escrow";
+    // Holds the curve's tokens + SOL between start_migration and finalize/abort;
+    // only those two sign for it
+    pub const MIGRATION_ESCROW_PREFIX: &'static str = "migration_escrow";
+    // Program-wide PDA collecting migration fees (no mint in the seeds)
+    pub const PROTOCOL_FEE_PREFIX: &'static str = "protocol_fee";
+
+    // 0: the original 221-byte layout; 1: everything since (see upgrade_pool_account)
+    pub const VERSION: u8 = 1;
+    pub const RESERVED_BYTES: usize = 22;
 
     // Total serialized size INCLUDING the 8-byte discriminator.
     // Base (your original layout): 8(discriminator)+32(creator)+32(token)+8(total_supply)+8(reserve_token)+8(reserve_sol)+1(bump) = 97
     // Added for migration:
     //   + phase(1)
     //   + cap_reached_slot Option<u64>(1 tag + 8 data) = 9
-    //   + raydium_pool Option<Pubkey>(1 tag + 32 data) = 33
+    //   + amm_pool Option<Pubkey>(1 tag + 32 data) = 33
     //   + migration_authority(32)
     //   + reserve_snapshot_token(8) + reserve_snapshot_sol(8) = 16
     //   + lp_timelock Option<Pubkey>(1 tag + 32 data) = 33
     // 97 + (1+9+33+32+16+33) = 221
-    pub const ACCOUNT_SIZE: usize = 221;
-
-    pub fn new(creator: Pubkey, token: Pubkey, bump: u8) -> Self {
+    // Added for tiers:
+    //   + tiers Vec<Tier>(4 len + MAX_TIERS * 16) = 132
+    //   + tier_ratchet(1)
+    //   + vested_tiers(1) + vesting_duration(8) = 9
+    //   + bonus_per_tier(8) + bonus_mode(1) + bonus_paid(8) + tier_bought(8 * MAX_TIERS) = 81
+    // 221 + 132 + 1 + 9 + 81 = 444
+    // Added for the LP lock:
+    //   + lp_mint Option<Pubkey>(33) + lp_recipient(32) + lp_lock_slots(8)
+    //   + lp_unlock_slot(8) + lp_burn(1) = 82
+    // 444 + 82 = 526
+    // Added for AMM adapters:
+    //   + amm_kind(1) + amm_program(32) = 33
+    // 526 + 33 = 559
+    // Added for launch expiry:
+    //   + launch_deadline(8)
+    // 559 + 8 = 567
+    // Added for graduation cuts:
+    //   + migration_fee_bps(2) + creator_bonus_bps(2) = 4
+    // 567 + 4 = 571
+    // Added for closing graduated pools:
+    //   + graduation_slot(8)
+    // 571 + 8 = 579
+    // Added for pausing:
+    //   + paused(1)
+    // 579 + 1 = 580
+    // Added for the emergency-withdraw timelock:
+    //   + emergency_withdraw_slot(8)
+    // 580 + 8 = 588
+    // Added for versioning:
+    //   + version(1) + vault_bump(1) + total_sold(8) + total_sol_in(8) + seed_sol(8)
+    //   + bonus_paid_tiers(1) + tokens_redeemed(8) + vesting_unclaimed(8)
+    //   + tier_bonus_claimed(8 * MAX_TIERS) + reserved(RESERVED_BYTES = 22) = 129
+    // 588 + 129 = 717
+    pub const ACCOUNT_SIZE: usize =
+        221 + 4 + MAX_TIERS * Tier::SIZE + 1 + 9 + 17 + 8 * MAX_TIERS + 33 + 32 + 8 + 8 + 1 + 33 + 8 + 4 + 8 + 1 + 8
+            + 1 + 1 + 16 + 8 + 1 + 8 + 8 + 8 * MAX_TIERS + Self::RESERVED_BYTES;
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        creator: Pubkey,
+        token: Pubkey,
+        bump: u8,
+        vault_bump: u8,
+        tiers: Vec<Tier>,
+        tier_ratchet: bool,
+        vesting: Option<VestingConfig>,
+        bonus: Option<TierBonusConfig>,
+        lp_lock: Option<LpLockConfig>,
+        amm: AmmTarget,
+        launch_deadline: Option<i64>,
+        migration_fee_bps: u16,
+        creator_bonus_bps: Option<u16>,
+    ) -> Self {
         Self {
             creator,
             token,
@@ -93,15 +602,218 @@ impl LiquidityPool {
             // --- migration fields (defaults) ---
             phase: PoolPhase::Active,
             cap_reached_slot: None,
-            raydium_pool: None,
+            amm_pool: None,
             migration_authority: creator,
             reserve_snapshot_token: 0,
             reserve_snapshot_sol: 0,
             lp_timelock: None,
+            tiers,
+            tier_ratchet,
+            vested_tiers: vesting.map_or(0, |v| v.vested_tiers),
+            vesting_duration: vesting.map_or(0, |v| v.duration),
+            bonus_per_tier: bonus.map_or(0, |b| b.per_tier),
+            bonus_mode: bonus.map_or(BonusMode::CrossingBuyer, |b| b.mode),
+            bonus_paid: 0,
+            tier_bought: [0; MAX_TIERS],
+            lp_mint: None,
+            lp_recipient: lp_lock.map_or(Pubkey::default(), |l| l.recipient),
+            lp_lock_slots: lp_lock.map_or(0, |l| l.lock_slots),
+            lp_unlock_slot: 0,
+            lp_burn: lp_lock.is_some_and(|l| l.burn),
+            amm_kind: amm.kind,
+            amm_program: amm.program,
+            launch_deadline: launch_deadline.unwrap_or(0),
+            migration_fee_bps,
+            creator_bonus_bps: creator_bonus_bps.unwrap_or(0),
+            graduation_slot: 0,
+            paused: false,
+            emergency_withdraw_slot: 0,
+            version: Self::VERSION,
+            vault_bump,
+            total_sold: 0,
+            total_sol_in: 0,
+            seed_sol: 0,
+            bonus_paid_tiers: 0,
+            tokens_redeemed: 0,
+            vesting_unclaimed: 0,
+            tier_bonus_claimed: [0; MAX_TIERS],
+            reserved: [0; Self::RESERVED_BYTES],
         }
     }
-}
 
+    /// migration_authority may always drive migration; anyone else once the
+    /// cap has been reached for more than MIGRATION_GRACE_SLOTS.
+    pub fn may_drive_migration(&self, caller: &Pubkey, slot: u64) -> bool {
+        *caller == self.migration_authority
+            || self
+                .cap_reached_slot
+                .is_some_and(|s| slot > s.saturating_add(MIGRATION_GRACE_SLOTS))
+    }
+
+    /// migration_authority or the config admin may abort a migration.
+    pub fn may_abort_migration(&self, caller: &Pubkey, admin: &Pubkey) -> bool {
+        *caller == self.migration_authority || caller == admin
+    }
+
+    /// Once the cap has been reached for more than MIGRATION_ABORT_DELAY_SLOTS
+    /// without the migration finalizing.
+    pub fn migration_abort_unlocked(&self, slot: u64) -> bool {
+        self.cap_reached_slot
+            .is_some_and(|s| slot > s.saturating_add(MIGRATION_ABORT_DELAY_SLOTS))
+    }
+
+    /// Vault lamports that are not curve SOL: its rent-exempt minimum and the seed.
+    pub fn vault_floor(&self) -> Result<u64> {
+        Ok(Rent::get()?.minimum_balance(0).saturating_add(self.seed_sol))
+    }
+
+    /// Lamports the vault must hold while the curve holds its reserves:
+    /// vault_floor plus what selling every sold token back down the curve pays out.
+    /// Selling everything lands at x = 0, so that is the curve's x at
+    /// total_sold; computed directly rather than through sell_on_curve's
+    /// bisection, since every trade runs it.
+    pub fn required_sol(&self, decimals: u8) -> Result<u64> {
+        debug_assert_eq!(decimals, LUT_DECIMALS);
+        let x = x_from_y(&self.tiers, self.total_sold);
+        let sell_back = (x * LAMPORTS_PER_SOL as f64).floor() as u64;
+        Ok(self.vault_floor()?.saturating_add(sell_back))
+    }
+
+    /// reserve_sol fits in the vault above vault_floor (a surplus is only
+    /// donations) and the vault covers required_sol, up to rounding.
+    pub fn check_invariants(&self, vault_lamports: u64, decimals: u8) -> Result<()> {
+        require!(
+            self.reserve_sol.saturating_add(self.vault_floor()?) <= vault_lamports,
+            CustomError::ReserveMismatch
+        );
+        require!(
+            vault_lamports.saturating_add(SOLVENCY_TOLERANCE_LAMPORTS) >= self.required_sol(decimals)?,
+            CustomError::CurveInsolvent
+        );
+        Ok(())
+    }
+
+    /// (protocol fee, creator bonus) taken out of `sol` at graduation.
+    pub fn graduation_cuts(&self, sol: u64) -> (u64, u64) {
+        let bps = |b: u16| (sol as u128 * b as u128 / 10_000) as u64;
+        (bps(self.migration_fee_bps), bps(self.creator_bonus_bps))
+    }
+
+    /// Deadline set and passed while the curve is still Active (a capped
+    /// curve has already left Active, so this means it fell short).
+    pub fn launch_expired(&self, now: i64) -> bool {
+        matches!(self.phase, PoolPhase::Active) && self.launch_deadline > 0 && now >= self.launch_deadline
+    }
+
+    /// Holders' side of the redeem split: every token outside the pool and
+    /// treasury ATAs, plus tier bonuses already earned but not yet claimed.
+    pub fn tokens_outstanding(&self, supply: u64, pool_tokens: u64, treasury_tokens: u64, decimals: u8) -> u64 {
+        supply
+            .saturating_sub(pool_tokens)
+            .saturating_sub(treasury_tokens)
+            .saturating_add(self.bonus_owed(decimals))
+    }
+
+    /// Ends the curve for good: holders redeem `tokens_outstanding` for the
+    /// curve SOL as it stands (see redeem).
+    pub fn expire(&mut self, tokens_outstanding: u64) {
+        self.phase = PoolPhase::Expired;
+        self.reserve_snapshot_token = tokens_outstanding;
+        self.reserve_snapshot_sol = self.reserve_sol;
+    }
+
+    /// Part of a buy of `tokens_out` starting at `y_sold` that lands inside vested tiers.
+    pub fn vested_portion(&self, y_sold: u64, tokens_out: u64) -> u64 {
+        if self.vested_tiers == 0 {
+            return 0;
+        }
+        let ends = tier_ends_base(&self.tiers);
+        let vest_end = ends[(self.vested_tiers as usize).min(ends.len()) - 1];
+        tokens_out.min(vest_end.saturating_sub(y_sold))
+    }
+
+    /// Splits a buy that moved cumulative sold from `y_before` to `y_after`
+    /// across tiers, recording pool and (if given) buyer weights in tiers not
+    /// completed yet. Returns how many tiers the buy completed for the first
+    /// time, marking them in `bonus_paid_tiers`.
+    pub fn record_tier_buy(
+        &mut self,
+        y_before: u64,
+        y_after: u64,
+        mut participation: Option<&mut TierParticipation>,
+    ) -> Result<u64> {
+        let mut start = 0u64;
+        let mut completed = 0u64;
+        for (i, end) in tier_ends_base(&self.tiers).into_iter().enumerate() {
+            let lo = y_before.max(start);
+            let hi = y_after.min(end);
+            let frozen = self.bonus_paid_tiers & (1u8 << i) != 0;
+            if hi > lo && !frozen {
+                let part = hi - lo;
+                self.tier_bought[i] = self.tier_bought[i]
+                    .checked_add(part)
+                    .ok_or_else(|| error!(CustomError::OverflowOrUnderflowOccurred))?;
+                if let Some(p) = participation.as_deref_mut() {
+                    p.bought[i] = p.bought[i]
+                        .checked_add(part)
+                        .ok_or_else(|| error!(CustomError::OverflowOrUnderflowOccurred))?;
+                }
+            }
+            if !frozen && y_before < end && end <= y_after {
+                self.bonus_paid_tiers |= 1u8 << i;
+                completed += 1;
+            }
+            start = end;
+        }
+        Ok(completed)
+    }
+
+    /// Takes back the weights of a sell that moved cumulative sold from
+    /// `y_before` down to `y_after`, in tiers not completed yet. With a seller
+    /// record, the pool gives up what the seller does (so pool weight stays
+    /// the sum of buyer weights); without one, the whole slice.
+    pub fn record_tier_sell(
+        &mut self,
+        y_after: u64,
+        y_before: u64,
+        mut participation: Option<&mut TierParticipation>,
+    ) {
+        let mut start = 0u64;
+        for (i, end) in tier_ends_base(&self.tiers).into_iter().enumerate() {
+            let lo = y_after.max(start);
+            let hi = y_before.min(end);
+            if hi > lo && self.bonus_paid_tiers & (1u8 << i) == 0 {
+                let mut part = hi - lo;
+                if let Some(p) = participation.as_deref_mut() {
+                    part = part.min(p.bought[i]);
+                    p.bought[i] -= part;
+                }
+                self.tier_bought[i] = self.tier_bought[i].saturating_sub(part);
+            }
+            start = end;
+        }
+    }
+
+    /// Bonus for one tier in base units.
+    pub fn bonus_per_tier_base(&self, decimals: u8) -> u64 {
+        self.bonus_per_tier.saturating_mul(10u64.pow(decimals as u32))
+    }
+
+    /// Bonus tokens still owed from the treasury (kept back at migration).
+    pub fn bonus_pending(&self, decimals: u8) -> u64 {
+        let tier_count = tier_ends_base(&self.tiers).len() as u64;
+        self.bonus_per_tier_base(decimals)
+            .saturating_mul(tier_count)
+            .saturating_sub(self.bonus_paid)
+    }
+
+    /// Bonus tokens completed tiers still owe (claimable, not yet paid).
+    pub fn bonus_owed(&self, decimals: u8) -> u64 {
+        self.bonus_per_tier_base(decimals)
+            .saturating_mul(self.bonus_paid_tiers.count_ones() as u64)
+            .saturating_sub(self.bonus_paid)
+    }
+}
 
 
 pub trait LiquidityPoolAccount<'info> {
@@ -117,6 +829,7 @@ pub trait LiquidityPoolAccount<'info> {
             &mut Account<'info, TokenAccount>,  // user ATA (token source)
         ),
         pool_sol_vault: &mut AccountInfo<'info>,
+        initial_lamports: u64,
         authority: &Signer<'info>,
         token_program: &Program<'info, Token>,
         system_program: &Program<'info, System>,
@@ -132,11 +845,11 @@ pub trait LiquidityPoolAccount<'info> {
         ),
         pool_sol_vault: &mut AccountInfo<'info>,
         authority: &Signer<'info>,
-        bump: u8,
         token_program: &Program<'info, Token>,
         system_program: &Program<'info, System>,
     ) -> Result<()>;
 
+    #[allow(clippy::too_many_arguments)]
     fn buy(
         &mut self,
         token_accounts: (
@@ -146,10 +859,11 @@ pub trait LiquidityPoolAccount<'info> {
         ),
         pool_sol_vault: &mut AccountInfo<'info>,
         amount: u64, // max lamports user is willing to spend
+        vesting: Option<(&mut Account<'info, VestingEscrow>, &mut Account<'info, TokenAccount>)>,
         authority: &Signer<'info>,
         token_program: &Program<'info, Token>,
         system_program: &Program<'info, System>,
-    ) -> Result<()>;
+    ) -> Result<u64>; // tokens_out
 
     fn sell(
         &mut self,
@@ -160,7 +874,6 @@ pub trait LiquidityPoolAccount<'info> {
         ),
         pool_sol_vault: &mut AccountInfo<'info>,
         amount: u64, // tokens (base units) user is selling
-        bump: u8,
         authority: &Signer<'info>,
         token_program: &Program<'info, Token>,
         system_program: &Program<'info, System>,
@@ -183,6 +896,16 @@ pub trait LiquidityPoolAccount<'info> {
         token_program: &Program<'info, Token>,
     ) -> Result<()>;
 
+    fn transfer_token_from_treasury(
+        &self,
+        treasury_pda: &AccountInfo<'info>,
+        treasury_bump: u8,
+        from: &Account<'info, TokenAccount>,
+        to: &Account<'info, TokenAccount>,
+        amount: u64,
+        token_program: &Program<'info, Token>,
+    ) -> Result<()>;
+
     fn transfer_sol_to_pool(
         &self,
         from: &Signer<'info>,
@@ -196,7 +919,6 @@ pub trait LiquidityPoolAccount<'info> {
         from: &mut AccountInfo<'info>,
         to: &Signer<'info>,
         amount: u64,
-        bump: u8,
         system_program: &Program<'info, System>,
     ) -> Result<()>;
 }
@@ -216,29 +938,38 @@ impl<'info> LiquidityPoolAccount<'info> for Account<'info, LiquidityPool> {
             &mut Account<'info, TokenAccount>,
         ),
         pool_sol_vault: &mut AccountInfo<'info>,
+        initial_lamports: u64,
         authority: &Signer<'info>,
         token_program: &Program<'info, Token>,
         system_program: &Program<'info, System>,
     ) -> Result<()> {
-        // pool receives all tokens from user's token account (bootstrap)
-        self.transfer_token_to_pool(
-            token_accounts.2,
-            token_accounts.1,
-            token_accounts.0.supply,
-            authority,
-            token_program,
-        )?;
+        // Only before any sale: create_pool already checked the pool ATA holds
+        // the sold cap, so tokens only move here to refill it after the
+        // creator's remove_liquidity
+        require!(self.total_sold == 0, CustomError::LiquidityLocked);
+        let cap = cap_base(token_accounts.0.decimals);
+        let missing = cap.saturating_sub(token_accounts.1.amount);
+        if missing > 0 {
+            self.transfer_token_to_pool(
+                token_accounts.2,
+                token_accounts.1,
+                missing,
+                authority,
+                token_program,
+            )?;
+        }
 
-        // pool receives the initial SOL seed
+        // pool receives the initial SOL seed (kept out of the curve reserves)
         self.transfer_sol_to_pool(
             authority,
             pool_sol_vault,
-            INITIAL_LAMPORTS_FOR_POOL,
+            initial_lamports,
             system_program,
         )?;
 
         self.total_supply = token_accounts.0.supply;
-        self.update_reserves(token_accounts.1.amount, pool_sol_vault.lamports())?;
+        self.update_reserves(cap, self.reserve_sol)?;
+        self.seed_sol = self.seed_sol.saturating_add(initial_lamports);
         Ok(())
     }
 
@@ -251,7 +982,6 @@ impl<'info> LiquidityPoolAccount<'info> for Account<'info, LiquidityPool> {
         ),
         pool_sol_vault: &mut AccountInfo<'info>,
         authority: &Signer<'info>,
-        bump: u8,
         token_program: &Program<'info, Token>,
         system_program: &Program<'info, System>,
     ) -> Result<()> {
@@ -259,13 +989,15 @@ impl<'info> LiquidityPoolAccount<'info> for Account<'info, LiquidityPool> {
         self.transfer_token_from_pool(
             token_accounts.1,
             token_accounts.2,
-            token_accounts.1.amount as u64,
+            token_accounts.1.amount,
             token_program,
         )?;
 
-        // Transfer all SOL back to user
-        let amount = pool_sol_vault.to_account_info().lamports() as u64;
-        self.transfer_sol_from_pool(pool_sol_vault, authority, amount, bump, system_program)?;
+        // Transfer curve SOL and the seed back to user; the vault keeps its rent
+        let amount = self.reserve_sol.saturating_add(self.seed_sol);
+        self.transfer_sol_from_pool(pool_sol_vault, authority, amount, system_program)?;
+        self.update_reserves(0, 0)?;
+        self.seed_sol = 0;
         Ok(())
     }
 
@@ -278,10 +1010,11 @@ impl<'info> LiquidityPoolAccount<'info> for Account<'info, LiquidityPool> {
         ),
         pool_sol_vault: &mut AccountInfo<'info>,
         amount: u64,
+        vesting: Option<(&mut Account<'info, VestingEscrow>, &mut Account<'info, TokenAccount>)>,
         authority: &Signer<'info>,
         token_program: &Program<'info, Token>,
         system_program: &Program<'info, System>,
-    ) -> Result<()> {
+    ) -> Result<u64> {
         if amount == 0 {
             return err!(CustomError::InvalidAmount);
         }
@@ -290,30 +1023,22 @@ impl<'info> LiquidityPoolAccount<'info> for Account<'info, LiquidityPool> {
         if !matches!(self.phase, PoolPhase::Active) {
             return err!(CustomError::InvalidAmount); // use a dedicated error later
         }
+        require!(
+            !self.launch_expired(Clock::get()?.unix_timestamp),
+            CustomError::LaunchExpired
+        );
 
         msg!("Trying to buy from the pool");
 
-        // 🔑 Auto-initialize reserves if uninitialized
-        if self.reserve_token == 0 && self.reserve_sol == 0 {
-            let pool_balance = token_accounts.1.amount;
-            self.total_supply = token_accounts.0.supply;
-            self.reserve_token = pool_balance;
-            self.reserve_sol = pool_sol_vault.lamports();
-            msg!(
-                "Initialized: total_supply {}, reserve_token {}, reserve_sol {}",
-                self.total_supply, self.reserve_token, self.reserve_sol
-            );
-        }
-
         let decimals = token_accounts.0.decimals;
         let cap = cap_base(decimals);
 
         // How many tokens have been sold so far on the curve
-        let y_sold = y_sold_from_pool(self.reserve_token, decimals);
+        let y_sold = self.total_sold;
 
         // ⚖️ Compute tokens_out and the exact lamports to charge from the curve
         // We pass `amount` as the *budget*; helper will not exceed it.
-        let (tokens_out, lamports_used) = buy_on_curve(y_sold, amount, decimals);
+        let (tokens_out, lamports_used) = buy_on_curve(&self.tiers, y_sold, amount, decimals);
         msg!("curve buy → tokens_out: {}, lamports_used: {}", tokens_out, lamports_used);
 
         // Reject if nothing would be bought or pool doesn't have enough tokens
@@ -328,12 +1053,12 @@ impl<'info> LiquidityPoolAccount<'info> for Account<'info, LiquidityPool> {
         }
 
         // If this trade *fills* the cap exactly, transition to Migrating
-        if total_after == cap {
+        // (start_migration snapshots what it escrows)
+        let cap_reached = total_after == cap;
+        if cap_reached {
             let clock = Clock::get()?;
             self.phase = PoolPhase::Migrating;
             self.cap_reached_slot = Some(clock.slot);
-            self.reserve_snapshot_token = self.reserve_token;
-            self.reserve_snapshot_sol = self.reserve_sol;
         }
 
         // ✅ Update reserves using the exact lamports we will actually take
@@ -347,18 +1072,55 @@ impl<'info> LiquidityPoolAccount<'info> for Account<'info, LiquidityPool> {
             .checked_sub(tokens_out)
             .ok_or_else(|| error!(CustomError::OverflowOrUnderflowOccurred))?;
 
+        self.total_sold = total_after;
+        self.total_sol_in = self
+            .total_sol_in
+            .checked_add(lamports_used)
+            .ok_or_else(|| error!(CustomError::OverflowOrUnderflowOccurred))?;
+
         // 💸 Transfer exactly lamports_used from buyer → pool vault
         self.transfer_sol_to_pool(authority, pool_sol_vault, lamports_used, system_program)?;
 
-        // 🪙 Transfer tokens from pool → buyer
-        self.transfer_token_from_pool(
-            token_accounts.1, // pool ATA
-            token_accounts.2, // user ATA
-            tokens_out,
-            token_program,
-        )?;
+        if cap_reached {
+            emit!(crate::CapReached {
+                pool: self.key(),
+                slot: self.cap_reached_slot.unwrap_or_default(),
+                reserve_token: self.reserve_token,
+                reserve_sol: self.reserve_sol,
+                total_sold: self.total_sold,
+            });
+        }
 
-        Ok(())
+        // 🔒 Tokens bought inside vested tiers go to the buyer's escrow
+        let vested_out = self.vested_portion(y_sold, tokens_out);
+        if vested_out > 0 {
+            let (escrow, escrow_token_account) =
+                vesting.ok_or_else(|| error!(CustomError::VestingAccountsMissing))?;
+            escrow.deposit(vested_out, Clock::get()?.unix_timestamp)?;
+            self.vesting_unclaimed = self.vesting_unclaimed.saturating_add(vested_out);
+            self.transfer_token_from_pool(
+                token_accounts.1,     // pool ATA
+                escrow_token_account, // escrow ATA
+                vested_out,
+                token_program,
+            )?;
+        }
+
+        // 🪙 Transfer the rest from pool → buyer
+        let free_out = tokens_out - vested_out;
+        if free_out > 0 {
+            self.transfer_token_from_pool(
+                token_accounts.1, // pool ATA
+                token_accounts.2, // user ATA
+                free_out,
+                token_program,
+            )?;
+        }
+
+        // 🧮 Reserves reconcile and the vault still backs every sold token
+        self.check_invariants(pool_sol_vault.lamports(), decimals)?;
+
+        Ok(tokens_out)
     }
 
     fn sell(
@@ -370,7 +1132,6 @@ impl<'info> LiquidityPoolAccount<'info> for Account<'info, LiquidityPool> {
         ),
         pool_sol_vault: &mut AccountInfo<'info>,
         amount: u64, // tokens (base units) user is selling
-        bump: u8,
         authority: &Signer<'info>,
         token_program: &Program<'info, Token>,
         system_program: &Program<'info, System>,
@@ -378,55 +1139,37 @@ impl<'info> LiquidityPoolAccount<'info> for Account<'info, LiquidityPool> {
         if amount == 0 {
             return err!(CustomError::InvalidAmount);
         }
-        if self.reserve_token < amount {
-            return err!(CustomError::TokenAmountToSellTooBig);
-        }
 
         // Halt trading if not Active
         if !matches!(self.phase, PoolPhase::Active) {
             return err!(CustomError::InvalidAmount); // dedicate an error later
         }
+        require!(
+            !self.launch_expired(Clock::get()?.unix_timestamp),
+            CustomError::LaunchExpired
+        );
 
         let decimals = token_accounts.0.decimals;
 
         // How many have been sold so far on the curve
-        let y_sold = y_sold_from_pool(self.reserve_token, decimals);
+        let y_sold = self.total_sold;
+        require!(amount <= y_sold, CustomError::TokenAmountToSellTooBig);
+
+        // Ratchet: completed tiers are a hard floor for cumulative sold
+        if self.tier_ratchet {
+            let floor = completed_tier_floor(&self.tiers, y_sold);
+            require!(
+                y_sold.saturating_sub(amount) >= floor,
+                CustomError::TierRatchetFloor
+            );
+        }
 
         // 💵 Lamports owed from curve area
-        let lamports_out = sell_on_curve(y_sold, amount, decimals);
+        let lamports_out = sell_on_curve(&self.tiers, y_sold, amount, decimals);
         msg!("curve sell → tokens_in: {}, lamports_out: {}", amount, lamports_out);
 
         require!(self.reserve_sol >= lamports_out, CustomError::NotEnoughSolInVault);
 
-        // Ensure SOL vault exists (if your flow expects a system account PDA)
-        if pool_sol_vault.lamports() == 0 {
-            msg!("⚡ Funding SOL vault PDA for the first time");
-
-            let rent = Rent::get()?.minimum_balance(0);
-            let mint_key = token_accounts.0.key();
-
-            let seeds = &[
-                LiquidityPool::SOL_VAULT_PREFIX.as_bytes(),
-                mint_key.as_ref(),
-                &[bump],
-            ];
-            let signer_seeds = &[&seeds[..]];
-
-            anchor_lang::system_program::create_account(
-                CpiContext::new_with_signer(
-                    system_program.to_account_info(),
-                    anchor_lang::system_program::CreateAccount {
-                        from: authority.to_account_info(),
-                        to: pool_sol_vault.clone(),
-                    },
-                    signer_seeds,
-                ),
-                rent,
-                0,
-                &system_program::ID,
-            )?;
-        }
-
         // Update reserves to reflect the trade
         self.reserve_sol = self
             .reserve_sol
@@ -438,6 +1181,9 @@ impl<'info> LiquidityPoolAccount<'info> for Account<'info, LiquidityPool> {
             .checked_add(amount)
             .ok_or_else(|| error!(CustomError::OverflowOrUnderflowOccurred))?;
 
+        self.total_sold -= amount;
+        self.total_sol_in = self.total_sol_in.saturating_sub(lamports_out);
+
         // User → Pool (tokens)
         self.transfer_token_to_pool(
             token_accounts.2,
@@ -452,10 +1198,12 @@ impl<'info> LiquidityPoolAccount<'info> for Account<'info, LiquidityPool> {
             pool_sol_vault,
             authority,
             lamports_out,
-            bump,
             system_program,
         )?;
 
+        // 🧮 Reserves reconcile and the vault still backs every sold token
+        self.check_invariants(pool_sol_vault.lamports(), decimals)?;
+
         Ok(())
     }
 
@@ -511,19 +1259,50 @@ impl<'info> LiquidityPoolAccount<'info> for Account<'info, LiquidityPool> {
         Ok(())
     }
 
+    fn transfer_token_from_treasury(
+        &self,
+        treasury_pda: &AccountInfo<'info>,
+        treasury_bump: u8,
+        from: &Account<'info, TokenAccount>,
+        to: &Account<'info, TokenAccount>,
+        amount: u64,
+        token_program: &Program<'info, Token>,
+    ) -> Result<()> {
+        let token_key = self.token.key();
+        let seeds = &[
+            LiquidityPool::TREASURY_SEED_PREFIX.as_bytes(),
+            token_key.as_ref(),
+            &[treasury_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                token::Transfer {
+                    from: from.to_account_info(),
+                    to: to.to_account_info(),
+                    authority: treasury_pda.clone(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+        Ok(())
+    }
+
     fn transfer_sol_from_pool(
         &self,
         from: &mut AccountInfo<'info>,
         to: &Signer<'info>,
         amount: u64,
-        bump: u8,
         system_program: &Program<'info, System>,
     ) -> Result<()> {
         let token_key = self.token.key();
         let seeds = &[
             LiquidityPool::SOL_VAULT_PREFIX.as_bytes(),
             token_key.as_ref(),
-            &[bump],
+            &[self.vault_bump],
         ];
         let signer_seeds = &[&seeds[..]];
 
@@ -563,36 +1342,6 @@ impl<'info> LiquidityPoolAccount<'info> for Account<'info, LiquidityPool> {
     }
 }
 
-// NOTE: This helper remains in your file. Not used by the LUT math paths, but kept intact.
-fn calculate_amount_out(reserve_token_with_decimal: u64, amount_with_decimal: u64) -> Result<u64> {
-    // Convert to f64 for decimal calculations (⚠ consider fixed-point long term)
-    let reserve_token = (reserve_token_with_decimal as f64) / 1_000_000_000.0;
-    let amount = (amount_with_decimal as f64) / 1_000_000_000.0;
-
-    msg!(
-        "Starting calculation with reserve_token: {}, amount: {}",
-        reserve_token,
-        amount
-    );
-
-    let two_reserve_token = reserve_token * 2.0;
-    let one_added = two_reserve_token + 1.0;
-    let squared = one_added * one_added;
-    let amount_added = squared + amount * 8.0;
-    let sqrt_result = amount_added.sqrt();
-    if sqrt_result < 0.0 {
-        return err!(CustomError::NegativeNumber);
-    }
-    let subtract_one = sqrt_result - one_added;
-    let amount_out = subtract_one / 2.0;
-
-    // back to base units
-    let amount_out_decimal =
-        (amount_out * 1_000_000_000.0 * INITIAL_PRICE_DIVIDER as f64).round() as u64;
-
-    Ok(amount_out_decimal)
-}
-
 /* ---------------------------------------------------------------------------------
    The long commented linear-curve section you had stays here (omitted for brevity).
    --------------------------------------------------------------------------------- */
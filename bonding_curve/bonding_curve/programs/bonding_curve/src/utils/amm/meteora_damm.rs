@@ -0,0 +1,194 @@
+//! Meteora DAMM v2 adapter: one `initialize_pool` CPI with the escrow PDA as
+//! creator and payer. Token A is the launched token, token B is wSOL; the
+//! position NFT (Token-2022) plays the LP token.
+//!
+//! remaining_accounts: [config, pool_authority, position, token_a_vault,
+//! token_b_vault, event_authority]. `lp_mint` is the position NFT mint (signer)
+//! and `creator_lp` its `position_nft_account` PDA.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+};
+
+use super::{full_range_liquidity, sqrt_price_x64, token_balance, SeedAccounts, SeededReserves};
+use crate::errors::CustomError;
+
+/// sha256("global:initialize_pool")[..8]
+pub const INITIALIZE_POOL_DISCRIMINATOR: [u8; 8] = [95, 180, 10, 172, 84, 174, 232, 40];
+
+fn pool_address(program_id: &Pubkey, config: &Pubkey, mint_a: &Pubkey, mint_b: &Pubkey) -> Pubkey {
+    let (hi, lo) = if mint_a > mint_b { (mint_a, mint_b) } else { (mint_b, mint_a) };
+    Pubkey::find_program_address(&[b"pool", config.as_ref(), hi.as_ref(), lo.as_ref()], program_id).0
+}
+
+fn vault_address(program_id: &Pubkey, mint: &Pubkey, pool: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"token_vault", mint.as_ref(), pool.as_ref()], program_id).0
+}
+
+/// (liquidity in Q64.64, sqrt price) for a full-range pool holding the deposit.
+fn initial_liquidity(token_amount: u64, sol_amount: u64) -> (u128, u128) {
+    let sqrt_price = sqrt_price_x64(token_amount, sol_amount);
+    (full_range_liquidity(token_amount, sol_amount, sqrt_price) << 64, sqrt_price)
+}
+
+pub fn seed_pool(
+    accs: &SeedAccounts,
+    token_amount: u64,
+    sol_amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let config = accs.extra(0)?;
+    let pool_authority = accs.extra(1)?;
+    let position = accs.extra(2)?;
+    let vault_a = accs.extra(3)?;
+    let vault_b = accs.extra(4)?;
+    let event_authority = accs.extra(5)?;
+    let program_id = accs.amm_program.key();
+    let (mint_a, mint_b) = (accs.token_mint.key(), accs.wsol_mint.key());
+
+    let pool_key = pool_address(&program_id, config.key, &mint_a, &mint_b);
+    require_keys_eq!(pool_key, accs.amm_pool.key(), CustomError::InvalidAmmPool);
+    require_keys_eq!(
+        vault_address(&program_id, &mint_a, &pool_key),
+        vault_a.key(),
+        CustomError::InvalidAmmPool
+    );
+    require_keys_eq!(
+        vault_address(&program_id, &mint_b, &pool_key),
+        vault_b.key(),
+        CustomError::InvalidAmmPool
+    );
+    let (position_key, _) =
+        Pubkey::find_program_address(&[b"position", accs.lp_mint.key.as_ref()], &program_id);
+    require_keys_eq!(position_key, position.key(), CustomError::InvalidAmmPool);
+    let (position_nft_account, _) = Pubkey::find_program_address(
+        &[b"position_nft_account", accs.lp_mint.key.as_ref()],
+        &program_id,
+    );
+    require_keys_eq!(position_nft_account, accs.creator_lp.key(), CustomError::InvalidAmmPool);
+
+    // InitializePoolParameters { liquidity (Q64.64), sqrt_price, activation_point: None }
+    let (liquidity, sqrt_price) = initial_liquidity(token_amount, sol_amount);
+    let mut data = INITIALIZE_POOL_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&liquidity.to_le_bytes());
+    data.extend_from_slice(&sqrt_price.to_le_bytes());
+    data.push(0);
+
+    let ix = Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(accs.creator.key(), false), // creator
+            AccountMeta::new(accs.lp_mint.key(), true),
+            AccountMeta::new(accs.creator_lp.key(), false),
+            AccountMeta::new(accs.creator.key(), true), // payer
+            AccountMeta::new_readonly(config.key(), false),
+            AccountMeta::new_readonly(pool_authority.key(), false),
+            AccountMeta::new(accs.amm_pool.key(), false),
+            AccountMeta::new(position.key(), false),
+            AccountMeta::new_readonly(mint_a, false),
+            AccountMeta::new_readonly(mint_b, false),
+            AccountMeta::new(vault_a.key(), false),
+            AccountMeta::new(vault_b.key(), false),
+            AccountMeta::new(accs.creator_token.key(), false),
+            AccountMeta::new(accs.creator_wsol.key(), false),
+            AccountMeta::new_readonly(accs.token_program.key(), false),
+            AccountMeta::new_readonly(accs.token_program.key(), false),
+            AccountMeta::new_readonly(accs.lp_token_program.key(), false),
+            AccountMeta::new_readonly(accs.system_program.key(), false),
+            AccountMeta::new_readonly(event_authority.key(), false),
+            AccountMeta::new_readonly(program_id, false),
+        ],
+        data,
+    };
+    invoke_signed(
+        &ix,
+        &[
+            accs.creator.clone(),
+            accs.lp_mint.clone(),
+            accs.creator_lp.clone(),
+            config.clone(),
+            pool_authority.clone(),
+            accs.amm_pool.clone(),
+            position.clone(),
+            accs.token_mint.clone(),
+            accs.wsol_mint.clone(),
+            vault_a.clone(),
+            vault_b.clone(),
+            accs.creator_token.clone(),
+            accs.creator_wsol.clone(),
+            accs.token_program.clone(),
+            accs.lp_token_program.clone(),
+            accs.system_program.clone(),
+            event_authority.clone(),
+            accs.amm_program.clone(),
+        ],
+        signer_seeds,
+    )?;
+    Ok(())
+}
+
+/// DAMM v2 pool and vault addresses are PDAs of (config, mints), so the
+/// derivation check stands in for decoding the pool layout.
+pub fn read_reserves(accs: &SeedAccounts) -> Result<SeededReserves> {
+    let program_id = accs.amm_program.key();
+    let (mint_a, mint_b) = (accs.token_mint.key(), accs.wsol_mint.key());
+    let pool_key = pool_address(&program_id, accs.extra(0)?.key, &mint_a, &mint_b);
+    require_keys_eq!(pool_key, accs.amm_pool.key(), CustomError::AmmPoolMintMismatch);
+    require!(!accs.amm_pool.data_is_empty(), CustomError::InvalidAmmPool);
+
+    let (vault_a, vault_b) = (accs.extra(3)?, accs.extra(4)?);
+    require_keys_eq!(
+        vault_address(&program_id, &mint_a, &pool_key),
+        vault_a.key(),
+        CustomError::InvalidAmmPool
+    );
+    require_keys_eq!(
+        vault_address(&program_id, &mint_b, &pool_key),
+        vault_b.key(),
+        CustomError::InvalidAmmPool
+    );
+
+    Ok(SeededReserves {
+        token: token_balance(vault_a, &mint_a)?,
+        wsol: token_balance(vault_b, &mint_b)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        state::AmmKind,
+        utils::amm::{deposit_floor, MAX_SQRT_PRICE_X64, MIN_SQRT_PRICE_X64},
+    };
+
+    /// What DAMM v2 pulls for a Q64.64 `liquidity` over its full price range,
+    /// rounded up the way the pool rounds.
+    fn damm_deposit(liquidity: u128, sqrt_price: u128) -> (u64, u64) {
+        let q64 = 18_446_744_073_709_551_616.0;
+        let l = liquidity as f64 / q64;
+        let (p, lo, hi) = (
+            sqrt_price as f64 / q64,
+            MIN_SQRT_PRICE_X64 as f64 / q64,
+            MAX_SQRT_PRICE_X64 as f64 / q64,
+        );
+        ((l * (1.0 / p - 1.0 / hi)).ceil() as u64, (l * (p - lo)).ceil() as u64)
+    }
+
+    #[test]
+    fn initial_deposit_stays_within_the_escrow_and_clears_the_floor() {
+        for (tokens, sol) in [
+            (206_900_000 * 1_000_000_000u64, 84_700_000_000u64),
+            (1_000_000_000 * 1_000_000_000u64, 1_000_000_000u64),
+        ] {
+            let (liquidity, sqrt_price) = initial_liquidity(tokens, sol);
+            let (dep_token, dep_sol) = damm_deposit(liquidity, sqrt_price);
+
+            assert!(dep_token <= tokens && dep_sol <= sol);
+            assert!(dep_token >= deposit_floor(AmmKind::MeteoraDamm, tokens));
+            assert!(dep_sol >= deposit_floor(AmmKind::MeteoraDamm, sol));
+        }
+    }
+}
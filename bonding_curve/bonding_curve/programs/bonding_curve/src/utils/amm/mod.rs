@@ -0,0 +1,133 @@
+//! Migration adapters. Each AMM seeds a fresh pool from the migration escrow PDA's
+//! token + wSOL accounts (the escrow PDA is the creator/funder and signs via
+//! seeds), then reads back the pool's vault balances so finalize_migration can
+//! verify what was created.
+//!
+//! Accounts every AMM needs are named on `FinalizeMigration`; AMM-specific ones
+//! come in `remaining_accounts`, in the order each adapter documents.
+
+pub mod meteora_damm;
+pub mod orca_whirlpool;
+pub mod raydium_cpmm;
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, TokenAccount};
+
+use crate::{errors::CustomError, state::AmmKind};
+
+/// Q64.64 bounds shared by the CLMM-style AMMs (Whirlpool, DAMM v2).
+pub const MIN_SQRT_PRICE_X64: u128 = 4_295_048_016;
+pub const MAX_SQRT_PRICE_X64: u128 = 79_226_673_515_401_279_992_447_579_055;
+
+/// CLMM liquidity is trimmed this far below what the deposit covers, for float
+/// error and the AMM rounding its side of the deposit up.
+pub const LIQUIDITY_TRIM_BPS: u64 = 10;
+/// How far under the requested amounts a CLMM deposit may land: the trim plus
+/// slack for float error and the finite full range.
+pub const CLMM_DEPOSIT_TOLERANCE_BPS: u64 = 20;
+
+pub struct SeedAccounts<'a, 'info> {
+    pub amm_program: &'a AccountInfo<'info>,
+    pub creator: &'a AccountInfo<'info>, // migration escrow PDA
+    pub token_mint: &'a AccountInfo<'info>,
+    pub wsol_mint: &'a AccountInfo<'info>,
+    pub creator_token: &'a AccountInfo<'info>, // escrow-owned token account
+    pub creator_wsol: &'a AccountInfo<'info>,  // escrow-owned wSOL account
+    pub amm_pool: &'a AccountInfo<'info>,
+    pub lp_mint: &'a AccountInfo<'info>,    // LP mint, or position NFT mint
+    pub creator_lp: &'a AccountInfo<'info>, // where the AMM puts the creator's LP
+    pub token_program: &'a AccountInfo<'info>,
+    pub lp_token_program: &'a AccountInfo<'info>,
+    pub associated_token_program: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+    pub rent: &'a AccountInfo<'info>,
+    pub extra: &'a [AccountInfo<'info>], // remaining_accounts
+}
+
+impl<'a, 'info> SeedAccounts<'a, 'info> {
+    /// i-th AMM-specific account.
+    pub fn extra(&self, i: usize) -> Result<&'a AccountInfo<'info>> {
+        self.extra
+            .get(i)
+            .ok_or_else(|| error!(CustomError::InvalidAmmPool))
+    }
+}
+
+/// AMM vault balances after seeding.
+pub struct SeededReserves {
+    pub token: u64,
+    pub wsol: u64,
+}
+
+/// Creates the pool and deposits `token_amount` / `sol_amount` from the escrow PDA.
+pub fn seed_pool(
+    kind: AmmKind,
+    accs: &SeedAccounts,
+    token_amount: u64,
+    sol_amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    match kind {
+        AmmKind::RaydiumCpmm => raydium_cpmm::seed_pool(accs, token_amount, sol_amount, signer_seeds),
+        AmmKind::OrcaWhirlpool => {
+            orca_whirlpool::seed_pool(accs, token_amount, sol_amount, signer_seeds)
+        }
+        AmmKind::MeteoraDamm => meteora_damm::seed_pool(accs, token_amount, sol_amount, signer_seeds),
+    }
+}
+
+/// Least the AMM's vault must hold after seeding `amount`: Raydium takes the
+/// deposit exactly, the CLMMs take liquidity-rounded amounts.
+pub fn deposit_floor(kind: AmmKind, amount: u64) -> u64 {
+    match kind {
+        AmmKind::RaydiumCpmm => amount,
+        AmmKind::OrcaWhirlpool | AmmKind::MeteoraDamm => {
+            (amount as u128 * (10_000 - CLMM_DEPOSIT_TOLERANCE_BPS) as u128 / 10_000) as u64
+        }
+    }
+}
+
+/// Decodes the pool the AMM created, checks it pairs this token with wSOL, and
+/// returns its vault balances. Caller has already checked the pool's owner.
+pub fn read_reserves(kind: AmmKind, accs: &SeedAccounts) -> Result<SeededReserves> {
+    match kind {
+        AmmKind::RaydiumCpmm => raydium_cpmm::read_reserves(accs),
+        AmmKind::OrcaWhirlpool => orca_whirlpool::read_reserves(accs),
+        AmmKind::MeteoraDamm => meteora_damm::read_reserves(accs),
+    }
+}
+
+/// Balance of an SPL token account after checking its owner program and mint.
+pub fn token_balance(acc: &AccountInfo, mint: &Pubkey) -> Result<u64> {
+    require_keys_eq!(*acc.owner, token::ID, CustomError::InvalidAmmPool);
+    let data = acc.try_borrow_data()?;
+    let account = TokenAccount::try_deserialize(&mut &data[..])?;
+    require_keys_eq!(account.mint, *mint, CustomError::InvalidAmmPool);
+    Ok(account.amount)
+}
+
+/// Maps (token, wSOL) balances onto an AMM's (a, b) ordering.
+pub fn reserves_for(token_is_a: bool, amount_a: u64, amount_b: u64) -> SeededReserves {
+    if token_is_a {
+        SeededReserves { token: amount_a, wsol: amount_b }
+    } else {
+        SeededReserves { token: amount_b, wsol: amount_a }
+    }
+}
+
+/// Q64.64 sqrt of the b-per-a price implied by the deposit, clamped to range.
+pub fn sqrt_price_x64(amount_a: u64, amount_b: u64) -> u128 {
+    let price = (amount_b as f64) / (amount_a as f64);
+    let sqrt = (price.sqrt() * 18_446_744_073_709_551_616.0) as u128; // * 2^64
+    sqrt.clamp(MIN_SQRT_PRICE_X64, MAX_SQRT_PRICE_X64)
+}
+
+/// Full-range liquidity (real units) both amounts can cover at `sqrt_price_x64`.
+/// L = min(a * sqrtP, b / sqrtP), less `LIQUIDITY_TRIM_BPS`; the unused
+/// remainder stays in the escrow's accounts.
+pub fn full_range_liquidity(amount_a: u64, amount_b: u64, sqrt_price_x64: u128) -> u128 {
+    let sqrt_p = (sqrt_price_x64 as f64) / 18_446_744_073_709_551_616.0;
+    let from_a = (amount_a as f64) * sqrt_p;
+    let from_b = (amount_b as f64) / sqrt_p;
+    (from_a.min(from_b) * (10_000 - LIQUIDITY_TRIM_BPS) as f64 / 10_000.0) as u128
+}
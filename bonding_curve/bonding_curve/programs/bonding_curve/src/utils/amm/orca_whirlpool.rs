@@ -0,0 +1,349 @@
+//! Orca Whirlpool adapter: `initialize_pool`, two `initialize_tick_array`s,
+//! `open_position` over the full tick range and `increase_liquidity`, all
+//! funded and owned by the escrow PDA. The position NFT plays the LP token.
+//!
+//! remaining_accounts: [whirlpools_config, fee_tier, token_vault_a (signer),
+//! token_vault_b (signer), tick_array_lower, tick_array_upper, position].
+//! `lp_mint` is the position mint (signer) and `creator_lp` the escrow PDA's ATA for it.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+};
+use anchor_spl::associated_token::get_associated_token_address;
+
+use super::{full_range_liquidity, reserves_for, sqrt_price_x64, token_balance, SeedAccounts, SeededReserves};
+use crate::errors::CustomError;
+
+/// sha256("global:initialize_pool")[..8]
+pub const INITIALIZE_POOL_DISCRIMINATOR: [u8; 8] = [95, 180, 10, 172, 84, 174, 232, 40];
+/// sha256("global:initialize_tick_array")[..8]
+pub const INITIALIZE_TICK_ARRAY_DISCRIMINATOR: [u8; 8] = [11, 188, 193, 214, 141, 91, 149, 184];
+/// sha256("global:open_position")[..8]
+pub const OPEN_POSITION_DISCRIMINATOR: [u8; 8] = [135, 128, 47, 77, 15, 152, 240, 49];
+/// sha256("global:increase_liquidity")[..8]
+pub const INCREASE_LIQUIDITY_DISCRIMINATOR: [u8; 8] = [46, 156, 243, 118, 13, 205, 251, 178];
+/// sha256("account:Whirlpool")[..8]
+pub const WHIRLPOOL_DISCRIMINATOR: [u8; 8] = [63, 149, 209, 12, 225, 128, 99, 9];
+/// sha256("account:FeeTier")[..8]
+pub const FEE_TIER_DISCRIMINATOR: [u8; 8] = [56, 75, 159, 76, 142, 68, 190, 105];
+
+pub const MAX_TICK_INDEX: i32 = 443_636;
+pub const TICK_ARRAY_SIZE: i32 = 88;
+
+// Whirlpool account offsets (after the discriminator: config, bump, spacing,
+// spacing seed, fee rates, liquidity, sqrt price, current tick, protocol fees)
+const MINT_A_OFFSET: usize = 101;
+const VAULT_A_OFFSET: usize = 133;
+const MINT_B_OFFSET: usize = 181;
+const VAULT_B_OFFSET: usize = 213;
+// FeeTier: discriminator, whirlpools_config, tick_spacing
+const FEE_TIER_SPACING_OFFSET: usize = 40;
+
+fn key_at(data: &[u8], offset: usize) -> Pubkey {
+    Pubkey::new_from_array(data[offset..offset + 32].try_into().unwrap())
+}
+
+/// First tick of the tick array containing `tick`.
+fn tick_array_start(tick: i32, tick_spacing: u16) -> i32 {
+    let span = tick_spacing as i32 * TICK_ARRAY_SIZE;
+    tick.div_euclid(span) * span
+}
+
+/// Widest tick range the spacing allows, symmetric around zero.
+fn full_range_ticks(tick_spacing: u16) -> (i32, i32) {
+    let upper = (MAX_TICK_INDEX / tick_spacing as i32) * tick_spacing as i32;
+    (-upper, upper)
+}
+
+fn cpi(
+    accs: &SeedAccounts,
+    metas: Vec<AccountMeta>,
+    infos: &[AccountInfo],
+    data: Vec<u8>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let ix = Instruction {
+        program_id: accs.amm_program.key(),
+        accounts: metas,
+        data,
+    };
+    invoke_signed(&ix, infos, signer_seeds)?;
+    Ok(())
+}
+
+pub fn seed_pool(
+    accs: &SeedAccounts,
+    token_amount: u64,
+    sol_amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let config = accs.extra(0)?;
+    let fee_tier = accs.extra(1)?;
+    let vault_a = accs.extra(2)?;
+    let vault_b = accs.extra(3)?;
+    let tick_array_lower = accs.extra(4)?;
+    let tick_array_upper = accs.extra(5)?;
+    let position = accs.extra(6)?;
+    let program_id = accs.amm_program.key();
+
+    // Position NFT must land where finalize can move it
+    require_keys_eq!(
+        accs.creator_lp.key(),
+        get_associated_token_address(accs.creator.key, accs.lp_mint.key),
+        CustomError::InvalidAmmPool
+    );
+
+    let tick_spacing = {
+        require_keys_eq!(*fee_tier.owner, program_id, CustomError::InvalidAmmPool);
+        let data = fee_tier.try_borrow_data()?;
+        require!(
+            data.len() >= FEE_TIER_SPACING_OFFSET + 2 && data[..8] == FEE_TIER_DISCRIMINATOR,
+            CustomError::InvalidAmmPool
+        );
+        u16::from_le_bytes([data[FEE_TIER_SPACING_OFFSET], data[FEE_TIER_SPACING_OFFSET + 1]])
+    };
+    require!(tick_spacing > 0, CustomError::InvalidAmmPool);
+
+    // Whirlpool wants mint_a < mint_b by key
+    let token_is_a = accs.token_mint.key() < accs.wsol_mint.key();
+    let (mint_a, mint_b, src_a, src_b, amount_a, amount_b) = if token_is_a {
+        (accs.token_mint, accs.wsol_mint, accs.creator_token, accs.creator_wsol, token_amount, sol_amount)
+    } else {
+        (accs.wsol_mint, accs.token_mint, accs.creator_wsol, accs.creator_token, sol_amount, token_amount)
+    };
+
+    let (whirlpool_key, whirlpool_bump) = Pubkey::find_program_address(
+        &[
+            b"whirlpool",
+            config.key.as_ref(),
+            mint_a.key.as_ref(),
+            mint_b.key.as_ref(),
+            &tick_spacing.to_le_bytes(),
+        ],
+        &program_id,
+    );
+    require_keys_eq!(whirlpool_key, accs.amm_pool.key(), CustomError::InvalidAmmPool);
+
+    // 1) initialize_pool at the deposit's price
+    let sqrt_price = sqrt_price_x64(amount_a, amount_b);
+    let mut data = INITIALIZE_POOL_DISCRIMINATOR.to_vec();
+    data.push(whirlpool_bump);
+    data.extend_from_slice(&tick_spacing.to_le_bytes());
+    data.extend_from_slice(&sqrt_price.to_le_bytes());
+    cpi(
+        accs,
+        vec![
+            AccountMeta::new_readonly(config.key(), false),
+            AccountMeta::new_readonly(mint_a.key(), false),
+            AccountMeta::new_readonly(mint_b.key(), false),
+            AccountMeta::new(accs.creator.key(), true),
+            AccountMeta::new(accs.amm_pool.key(), false),
+            AccountMeta::new(vault_a.key(), true),
+            AccountMeta::new(vault_b.key(), true),
+            AccountMeta::new_readonly(fee_tier.key(), false),
+            AccountMeta::new_readonly(accs.token_program.key(), false),
+            AccountMeta::new_readonly(accs.system_program.key(), false),
+            AccountMeta::new_readonly(accs.rent.key(), false),
+        ],
+        &[
+            config.clone(),
+            mint_a.clone(),
+            mint_b.clone(),
+            accs.creator.clone(),
+            accs.amm_pool.clone(),
+            vault_a.clone(),
+            vault_b.clone(),
+            fee_tier.clone(),
+            accs.token_program.clone(),
+            accs.system_program.clone(),
+            accs.rent.clone(),
+            accs.amm_program.clone(),
+        ],
+        data,
+        signer_seeds,
+    )?;
+
+    // 2) tick arrays at both ends of the full range
+    let (tick_lower, tick_upper) = full_range_ticks(tick_spacing);
+    for (tick_array, tick) in [(tick_array_lower, tick_lower), (tick_array_upper, tick_upper)] {
+        let start = tick_array_start(tick, tick_spacing);
+        let (expected, _) = Pubkey::find_program_address(
+            &[b"tick_array", whirlpool_key.as_ref(), start.to_string().as_bytes()],
+            &program_id,
+        );
+        require_keys_eq!(expected, tick_array.key(), CustomError::InvalidAmmPool);
+
+        let mut data = INITIALIZE_TICK_ARRAY_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&start.to_le_bytes());
+        cpi(
+            accs,
+            vec![
+                AccountMeta::new_readonly(accs.amm_pool.key(), false),
+                AccountMeta::new(accs.creator.key(), true),
+                AccountMeta::new(tick_array.key(), false),
+                AccountMeta::new_readonly(accs.system_program.key(), false),
+            ],
+            &[
+                accs.amm_pool.clone(),
+                accs.creator.clone(),
+                tick_array.clone(),
+                accs.system_program.clone(),
+                accs.amm_program.clone(),
+            ],
+            data,
+            signer_seeds,
+        )?;
+    }
+
+    // 3) full-range position owned by the escrow PDA
+    let (position_key, position_bump) =
+        Pubkey::find_program_address(&[b"position", accs.lp_mint.key.as_ref()], &program_id);
+    require_keys_eq!(position_key, position.key(), CustomError::InvalidAmmPool);
+
+    let mut data = OPEN_POSITION_DISCRIMINATOR.to_vec();
+    data.push(position_bump);
+    data.extend_from_slice(&tick_lower.to_le_bytes());
+    data.extend_from_slice(&tick_upper.to_le_bytes());
+    cpi(
+        accs,
+        vec![
+            AccountMeta::new(accs.creator.key(), true), // funder
+            AccountMeta::new_readonly(accs.creator.key(), false), // owner
+            AccountMeta::new(position.key(), false),
+            AccountMeta::new(accs.lp_mint.key(), true),
+            AccountMeta::new(accs.creator_lp.key(), false),
+            AccountMeta::new_readonly(accs.amm_pool.key(), false),
+            AccountMeta::new_readonly(accs.token_program.key(), false),
+            AccountMeta::new_readonly(accs.system_program.key(), false),
+            AccountMeta::new_readonly(accs.rent.key(), false),
+            AccountMeta::new_readonly(accs.associated_token_program.key(), false),
+        ],
+        &[
+            accs.creator.clone(),
+            position.clone(),
+            accs.lp_mint.clone(),
+            accs.creator_lp.clone(),
+            accs.amm_pool.clone(),
+            accs.token_program.clone(),
+            accs.system_program.clone(),
+            accs.rent.clone(),
+            accs.associated_token_program.clone(),
+            accs.amm_program.clone(),
+        ],
+        data,
+        signer_seeds,
+    )?;
+
+    // 4) deposit; the escrow PDA owns both the position and the source accounts
+    let liquidity = full_range_liquidity(amount_a, amount_b, sqrt_price);
+    let mut data = INCREASE_LIQUIDITY_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&liquidity.to_le_bytes());
+    data.extend_from_slice(&amount_a.to_le_bytes());
+    data.extend_from_slice(&amount_b.to_le_bytes());
+    cpi(
+        accs,
+        vec![
+            AccountMeta::new(accs.amm_pool.key(), false),
+            AccountMeta::new_readonly(accs.token_program.key(), false),
+            AccountMeta::new_readonly(accs.creator.key(), true), // position_authority
+            AccountMeta::new(position.key(), false),
+            AccountMeta::new_readonly(accs.creator_lp.key(), false),
+            AccountMeta::new(src_a.key(), false),
+            AccountMeta::new(src_b.key(), false),
+            AccountMeta::new(vault_a.key(), false),
+            AccountMeta::new(vault_b.key(), false),
+            AccountMeta::new(tick_array_lower.key(), false),
+            AccountMeta::new(tick_array_upper.key(), false),
+        ],
+        &[
+            accs.amm_pool.clone(),
+            accs.token_program.clone(),
+            accs.creator.clone(),
+            position.clone(),
+            accs.creator_lp.clone(),
+            src_a.clone(),
+            src_b.clone(),
+            vault_a.clone(),
+            vault_b.clone(),
+            tick_array_lower.clone(),
+            tick_array_upper.clone(),
+            accs.amm_program.clone(),
+        ],
+        data,
+        signer_seeds,
+    )
+}
+
+pub fn read_reserves(accs: &SeedAccounts) -> Result<SeededReserves> {
+    let (mint_a, vault_a_key, mint_b, vault_b_key) = {
+        let data = accs.amm_pool.try_borrow_data()?;
+        require!(
+            data.len() >= VAULT_B_OFFSET + 32 && data[..8] == WHIRLPOOL_DISCRIMINATOR,
+            CustomError::InvalidAmmPool
+        );
+        (
+            key_at(&data, MINT_A_OFFSET),
+            key_at(&data, VAULT_A_OFFSET),
+            key_at(&data, MINT_B_OFFSET),
+            key_at(&data, VAULT_B_OFFSET),
+        )
+    };
+
+    let (token_key, wsol_key) = (accs.token_mint.key(), accs.wsol_mint.key());
+    let token_is_a = if mint_a == token_key && mint_b == wsol_key {
+        true
+    } else if mint_a == wsol_key && mint_b == token_key {
+        false
+    } else {
+        return err!(CustomError::AmmPoolMintMismatch);
+    };
+
+    let (vault_a, vault_b) = (accs.extra(2)?, accs.extra(3)?);
+    require_keys_eq!(vault_a_key, vault_a.key(), CustomError::InvalidAmmPool);
+    require_keys_eq!(vault_b_key, vault_b.key(), CustomError::InvalidAmmPool);
+
+    Ok(reserves_for(
+        token_is_a,
+        token_balance(vault_a, &mint_a)?,
+        token_balance(vault_b, &mint_b)?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{state::AmmKind, utils::amm::deposit_floor};
+
+    fn sqrt_at_tick(tick: i32) -> f64 { 1.0001f64.powf(tick as f64 / 2.0) }
+
+    /// What `increase_liquidity` pulls for `liquidity` over [lower, upper],
+    /// rounded up the way Whirlpool rounds.
+    fn whirlpool_deposit(liquidity: u128, sqrt_price: u128, lower: i32, upper: i32) -> (u64, u64) {
+        let l = liquidity as f64;
+        let sqrt_p = sqrt_price as f64 / 18_446_744_073_709_551_616.0;
+        let a = l * (1.0 / sqrt_p - 1.0 / sqrt_at_tick(upper));
+        let b = l * (sqrt_p - sqrt_at_tick(lower));
+        (a.ceil() as u64, b.ceil() as u64)
+    }
+
+    #[test]
+    fn full_range_deposit_stays_within_the_escrow_and_clears_the_floor() {
+        let tokens = 206_900_000 * 1_000_000_000u64;
+        let sol = 84_700_000_000u64;
+        for tick_spacing in [64u16, 128] {
+            let (lower, upper) = full_range_ticks(tick_spacing);
+            // token sorts as a or b depending on its mint key
+            for (amount_a, amount_b) in [(tokens, sol), (sol, tokens)] {
+                let sqrt_price = sqrt_price_x64(amount_a, amount_b);
+                let liquidity = full_range_liquidity(amount_a, amount_b, sqrt_price);
+                let (dep_a, dep_b) = whirlpool_deposit(liquidity, sqrt_price, lower, upper);
+
+                assert!(dep_a <= amount_a && dep_b <= amount_b);
+                assert!(dep_a >= deposit_floor(AmmKind::OrcaWhirlpool, amount_a));
+                assert!(dep_b >= deposit_floor(AmmKind::OrcaWhirlpool, amount_b));
+            }
+        }
+    }
+}
@@ -0,0 +1,177 @@
+//! Raydium CP-Swap (CPMM) adapter: hand-built CPI into `initialize`.
+//! Account order and args mirror cp-swap's `Initialize` context; we build the
+//! instruction ourselves so the program doesn't depend on the cp-swap crate.
+//!
+//! remaining_accounts: [amm_config, authority, token_0_vault, token_1_vault,
+//! create_pool_fee, observation_state]. `lp_mint` is the pool's LP mint and
+//! `creator_lp` the escrow PDA's LP ATA.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+};
+
+use anchor_spl::associated_token::get_associated_token_address;
+
+use super::{reserves_for, token_balance, SeedAccounts, SeededReserves};
+use crate::errors::CustomError;
+
+/// sha256("global:initialize")[..8]
+pub const INITIALIZE_DISCRIMINATOR: [u8; 8] = [175, 175, 109, 31, 13, 152, 155, 237];
+
+/// sha256("account:PoolState")[..8]
+pub const POOL_STATE_DISCRIMINATOR: [u8; 8] = [247, 237, 227, 245, 215, 195, 222, 70];
+
+/// The parts of cp-swap's `PoolState` finalize checks. The account is packed
+/// (`zero_copy`), so fields sit at fixed offsets after the discriminator.
+pub struct PoolStateView {
+    pub token_0_vault: Pubkey,
+    pub token_1_vault: Pubkey,
+    pub lp_mint: Pubkey,
+    pub token_0_mint: Pubkey,
+    pub token_1_mint: Pubkey,
+}
+
+impl PoolStateView {
+    // discriminator, amm_config, pool_creator
+    const TOKEN_0_VAULT_OFFSET: usize = 8 + 32 + 32;
+    // ... token_0_vault, token_1_vault, lp_mint, token_0_mint, token_1_mint
+    const MIN_LEN: usize = Self::TOKEN_0_VAULT_OFFSET + 5 * 32;
+
+    /// Decodes a pool state account; caller checks the owning program.
+    pub fn read(acc: &AccountInfo) -> Result<Self> {
+        let data = acc.try_borrow_data()?;
+        require!(
+            data.len() >= Self::MIN_LEN && data[..8] == POOL_STATE_DISCRIMINATOR,
+            CustomError::InvalidAmmPool
+        );
+
+        let key_at = |i: usize| {
+            let start = Self::TOKEN_0_VAULT_OFFSET + i * 32;
+            Pubkey::new_from_array(data[start..start + 32].try_into().unwrap())
+        };
+        Ok(Self {
+            token_0_vault: key_at(0),
+            token_1_vault: key_at(1),
+            lp_mint: key_at(2),
+            token_0_mint: key_at(3),
+            token_1_mint: key_at(4),
+        })
+    }
+}
+
+pub fn seed_pool(
+    accs: &SeedAccounts,
+    token_amount: u64,
+    sol_amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let amm_config = accs.extra(0)?;
+    let authority = accs.extra(1)?;
+    let token_0_vault = accs.extra(2)?;
+    let token_1_vault = accs.extra(3)?;
+    let create_pool_fee = accs.extra(4)?;
+    let observation_state = accs.extra(5)?;
+
+    // LP must land where finalize can move it
+    require_keys_eq!(
+        accs.creator_lp.key(),
+        get_associated_token_address(accs.creator.key, accs.lp_mint.key),
+        CustomError::InvalidAmmPool
+    );
+
+    // CPMM wants token_0 < token_1 by key
+    let token_is_0 = accs.token_mint.key() < accs.wsol_mint.key();
+    let (mint_0, mint_1, src_0, src_1, amount_0, amount_1) = if token_is_0 {
+        (accs.token_mint, accs.wsol_mint, accs.creator_token, accs.creator_wsol, token_amount, sol_amount)
+    } else {
+        (accs.wsol_mint, accs.token_mint, accs.creator_wsol, accs.creator_token, sol_amount, token_amount)
+    };
+
+    let accounts = vec![
+        AccountMeta::new(accs.creator.key(), true),
+        AccountMeta::new_readonly(amm_config.key(), false),
+        AccountMeta::new_readonly(authority.key(), false),
+        AccountMeta::new(accs.amm_pool.key(), false),
+        AccountMeta::new_readonly(mint_0.key(), false),
+        AccountMeta::new_readonly(mint_1.key(), false),
+        AccountMeta::new(accs.lp_mint.key(), false),
+        AccountMeta::new(src_0.key(), false),
+        AccountMeta::new(src_1.key(), false),
+        AccountMeta::new(accs.creator_lp.key(), false),
+        AccountMeta::new(token_0_vault.key(), false),
+        AccountMeta::new(token_1_vault.key(), false),
+        AccountMeta::new(create_pool_fee.key(), false),
+        AccountMeta::new(observation_state.key(), false),
+        AccountMeta::new_readonly(accs.token_program.key(), false), // token_program (LP)
+        AccountMeta::new_readonly(accs.token_program.key(), false), // token_0_program
+        AccountMeta::new_readonly(accs.token_program.key(), false), // token_1_program
+        AccountMeta::new_readonly(accs.associated_token_program.key(), false),
+        AccountMeta::new_readonly(accs.system_program.key(), false),
+        AccountMeta::new_readonly(accs.rent.key(), false),
+    ];
+
+    let mut data = Vec::with_capacity(8 + 8 * 3);
+    data.extend_from_slice(&INITIALIZE_DISCRIMINATOR);
+    data.extend_from_slice(&amount_0.to_le_bytes());
+    data.extend_from_slice(&amount_1.to_le_bytes());
+    data.extend_from_slice(&0u64.to_le_bytes()); // open_time: immediately
+
+    let ix = Instruction {
+        program_id: accs.amm_program.key(),
+        accounts,
+        data,
+    };
+
+    invoke_signed(
+        &ix,
+        &[
+            accs.creator.clone(),
+            amm_config.clone(),
+            authority.clone(),
+            accs.amm_pool.clone(),
+            mint_0.clone(),
+            mint_1.clone(),
+            accs.lp_mint.clone(),
+            src_0.clone(),
+            src_1.clone(),
+            accs.creator_lp.clone(),
+            token_0_vault.clone(),
+            token_1_vault.clone(),
+            create_pool_fee.clone(),
+            observation_state.clone(),
+            accs.token_program.clone(),
+            accs.associated_token_program.clone(),
+            accs.system_program.clone(),
+            accs.rent.clone(),
+            accs.amm_program.clone(),
+        ],
+        signer_seeds,
+    )?;
+    Ok(())
+}
+
+pub fn read_reserves(accs: &SeedAccounts) -> Result<SeededReserves> {
+    let state = PoolStateView::read(accs.amm_pool)?;
+
+    let (token_key, wsol_key) = (accs.token_mint.key(), accs.wsol_mint.key());
+    let token_is_0 = if state.token_0_mint == token_key && state.token_1_mint == wsol_key {
+        true
+    } else if state.token_0_mint == wsol_key && state.token_1_mint == token_key {
+        false
+    } else {
+        return err!(CustomError::AmmPoolMintMismatch);
+    };
+    require_keys_eq!(state.lp_mint, accs.lp_mint.key(), CustomError::InvalidAmmPool);
+
+    let (vault_0, vault_1) = (accs.extra(2)?, accs.extra(3)?);
+    require_keys_eq!(state.token_0_vault, vault_0.key(), CustomError::InvalidAmmPool);
+    require_keys_eq!(state.token_1_vault, vault_1.key(), CustomError::InvalidAmmPool);
+
+    Ok(reserves_for(
+        token_is_0,
+        token_balance(vault_0, &state.token_0_mint)?,
+        token_balance(vault_1, &state.token_1_mint)?,
+    ))
+}
@@ -1,20 +1,29 @@
 //! LUT-based bonding curve using cumulative supply tables in base units.
 //! - F_floor(x): floor cumulative supply (base units) at x
 //! - F_ceil (x): ceil  cumulative supply (base units) at x
+//!
 //! Buys:  tokens_out = max(0, F_floor(x1) - F_ceil(x0))
 //! Sells: find x1 s.t. F_ceil(x0) - F_floor(x1) >= tokens_in  (conservative)
 //! Inversion (y->x) uses floor table: largest x with F_floor(x) ≤ y
 
+//!
+//! Tiers: a pool may carry its own schedule of N tiers. Each tier replays one
+//! LUT period (the first third of the table), stretched to the tier's SOL width
+//! and scaled to its token allocation, so the stitched cumulative supply stays
+//! monotone and ends exactly at the cap. An empty schedule is the original
+//! three equal tiers and reads the full LUT directly.
+
 #![allow(clippy::many_single_char_names)]
 
 use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+use crate::state::Tier;
 
 // ====================== Domain / constants ======================
 
 /// Total SOL span of the curve (should match your LUT metadata; ~78.53981633974483).
 pub const X_MAX: f64 = 78.539_816_339_744_83;
 
-/// Period length just for reference.
+/// Period length: the SOL span of one LUT segment (one legacy tier).
 pub const T: f64 = X_MAX / 3.0;
 
 /// Target total tokens sold along the curve (WHOLE tokens).
@@ -39,11 +48,9 @@ fn clamp(v: f64, lo: f64, hi: f64) -> f64 {
 
 #[inline]
 fn clamp01(t: f64) -> f64 {
-    if t <= 0.0 { 0.0 } else if t >= 1.0 { 1.0 } else { t }
+    t.clamp(0.0, 1.0)
 }
 
-#[inline]
-fn n_nodes() -> usize { Y_FLOOR.len() }             // = N + 1
 #[inline]
 fn n_intervals() -> usize { Y_FLOOR.len() - 1 }     // = N
 #[inline]
@@ -114,6 +121,122 @@ fn y_at_x_floor_clamped(x: f64) -> u128 { y_interp_floor(&Y_FLOOR, x) }
 #[inline]
 fn y_at_x_ceil_clamped (x: f64) -> u128 { y_interp_ceil (&Y_CEIL , x) }
 
+// ====================== Tier stitching ======================
+//
+// Inside a tier, progress u = (x - x_start) / width in [0, 1] maps to
+// alloc * F(u*T) / F_floor(T). Both tables share the floor denominator so the
+// ceil side never drops below the floor side; ceil is clamped to the tier's
+// allocation so boundaries land exactly on the cumulative allocation.
+
+#[inline]
+fn seg_norm() -> u128 { y_at_x_floor_clamped(T).max(1) }
+
+#[inline]
+fn tier_width_sol(t: &Tier) -> f64 { (t.sol_width as f64) / (LAMPORTS_PER_SOL as f64) }
+
+#[inline]
+fn tier_alloc_base(t: &Tier) -> u128 { (t.token_alloc as u128) * 10u128.pow(LUT_DECIMALS as u32) }
+
+/// Total SOL span of the curve for this schedule.
+pub fn x_max(tiers: &[Tier]) -> f64 {
+    if tiers.is_empty() { return X_MAX; }
+    tiers.iter().map(tier_width_sol).sum()
+}
+
+/// Walk the schedule to the tier containing x.
+/// Returns (y_start_base, tier, u) with u clamped to [0, 1].
+fn locate_x(tiers: &[Tier], x: f64) -> (u128, &Tier, f64) {
+    let mut x0 = 0.0_f64;
+    let mut y0 = 0u128;
+    let last = tiers.len() - 1;
+    for (i, t) in tiers.iter().enumerate() {
+        let w = tier_width_sol(t);
+        if x < x0 + w || i == last {
+            let u = if w > 0.0 { clamp01((x - x0) / w) } else { 1.0 };
+            return (y0, t, u);
+        }
+        x0 += w;
+        y0 += tier_alloc_base(t);
+    }
+    unreachable!()
+}
+
+fn y_floor(tiers: &[Tier], x: f64) -> u128 {
+    if tiers.is_empty() { return y_at_x_floor_clamped(x); }
+    if x <= 0.0 { return 0u128; }
+    if x >= x_max(tiers) { return CAP_BASE_U128; }
+
+    let (y0, t, u) = locate_x(tiers, x);
+    let a = tier_alloc_base(t);
+    let local = a * y_at_x_floor_clamped(u * T) / seg_norm();
+    (y0 + local.min(a)).min(CAP_BASE_U128)
+}
+
+fn y_ceil(tiers: &[Tier], x: f64) -> u128 {
+    if tiers.is_empty() { return y_at_x_ceil_clamped(x); }
+    if x <= 0.0 { return 0u128; }
+    if x >= x_max(tiers) { return CAP_BASE_U128; }
+
+    let (y0, t, u) = locate_x(tiers, x);
+    let a = tier_alloc_base(t);
+    let d = seg_norm();
+    let local = (a * y_at_x_ceil_clamped(u * T)).div_ceil(d);
+    (y0 + local.min(a)).min(CAP_BASE_U128)
+}
+
+/// Cumulative sold (base units) at the end of each tier.
+/// For the legacy schedule these are the LUT values at T, 2T and the cap.
+pub fn tier_ends_base(tiers: &[Tier]) -> Vec<u64> {
+    if tiers.is_empty() {
+        return vec![
+            y_at_x_floor_clamped(T) as u64,
+            y_at_x_floor_clamped(2.0 * T) as u64,
+            CAP_BASE_U128 as u64,
+        ];
+    }
+    let mut acc = 0u128;
+    tiers
+        .iter()
+        .map(|t| {
+            acc += tier_alloc_base(t);
+            acc.min(CAP_BASE_U128) as u64
+        })
+        .collect()
+}
+
+/// Cumulative SOL position (SOL) at the end of each tier.
+pub fn tier_ends_x(tiers: &[Tier]) -> Vec<f64> {
+    if tiers.is_empty() {
+        return vec![T, 2.0 * T, X_MAX];
+    }
+    let mut acc = 0.0_f64;
+    tiers
+        .iter()
+        .map(|t| {
+            acc += tier_width_sol(t);
+            acc
+        })
+        .collect()
+}
+
+/// Index of the tier that the next token sold at y belongs to
+/// (clamped to the last tier once the cap is reached).
+pub fn tier_index_for_y(tiers: &[Tier], y_base: u64) -> usize {
+    let ends = tier_ends_base(tiers);
+    ends.iter()
+        .position(|&end| y_base < end)
+        .unwrap_or(ends.len() - 1)
+}
+
+/// End of the last fully completed tier at or below y (0 if none).
+pub fn completed_tier_floor(tiers: &[Tier], y_base: u64) -> u64 {
+    tier_ends_base(tiers)
+        .into_iter()
+        .take_while(|&end| end <= y_base)
+        .last()
+        .unwrap_or(0)
+}
+
 // ====================== Inversion (y -> x) ======================
 //
 // Largest x with F_floor(x) <= y (monotone & conservative).
@@ -150,14 +273,39 @@ pub fn x_from_y_lut(y_base: u64) -> f64 {
     (lo as f64 + frac) * dx_sol()
 }
 
+/// Largest x with F_floor(x) <= y for this schedule.
+pub fn x_from_y(tiers: &[Tier], y_base: u64) -> f64 {
+    if tiers.is_empty() { return x_from_y_lut(y_base); }
+
+    let yb = (y_base as u128).min(CAP_BASE_U128);
+    if yb == 0 { return 0.0; }
+    if yb >= CAP_BASE_U128 { return x_max(tiers); }
+
+    let mut x0 = 0.0_f64;
+    let mut y0 = 0u128;
+    for t in tiers {
+        let a = tier_alloc_base(t);
+        let w = tier_width_sol(t);
+        if yb < y0 + a {
+            // Map back into one LUT period, then stretch to the tier width.
+            let target = (yb - y0) * seg_norm() / a;
+            let x_lut = x_from_y_lut(target as u64).min(T);
+            return x0 + (x_lut / T) * w;
+        }
+        x0 += w;
+        y0 += a;
+    }
+    x_max(tiers)
+}
+
 // ====================== Frontend-identical sell solver ======================
 
 #[inline]
-fn x_after_selling_tokens(x0: f64, tokens_in_base: u64) -> f64 {
+fn x_after_selling_tokens(tiers: &[Tier], x0: f64, tokens_in_base: u64) -> f64 {
     if tokens_in_base == 0 { return x0; }
 
     let want: u128 = tokens_in_base as u128;
-    let y0_ceil: u128 = y_at_x_ceil_clamped(x0);
+    let y0_ceil: u128 = y_ceil(tiers, x0);
 
     // search x1 in [0, x0] such that  F_ceil(x0) - F_floor(x1) >= want
     let mut lo = 0.0_f64;
@@ -165,7 +313,7 @@ fn x_after_selling_tokens(x0: f64, tokens_in_base: u64) -> f64 {
 
     for _ in 0..50 {
         let mid = 0.5 * (lo + hi);
-        let y_mid_floor = y_at_x_floor_clamped(mid);
+        let y_mid_floor = y_floor(tiers, mid);
         if y0_ceil.saturating_sub(y_mid_floor) >= want {
             // still enough tokens released — move right to reduce SOL out (conservative)
             lo = mid;
@@ -181,6 +329,7 @@ fn x_after_selling_tokens(x0: f64, tokens_in_base: u64) -> f64 {
 // BUY by lamports budget.
 // Returns (tokens_out_base_units, lamports_used).
 pub fn buy_on_curve(
+    tiers: &[Tier],        // pool tier schedule (empty = legacy three tiers)
     y_current_base: u64,   // cumulative sold so far (base units)
     lamports_in: u64,      // pay-in budget
     decimals: u8,
@@ -189,18 +338,24 @@ pub fn buy_on_curve(
     if lamports_in == 0 { return (0, 0); }
 
     // Position from cumulative sold so far
-    let x0 = x_from_y_lut(y_current_base);
+    let x0 = x_from_y(tiers, y_current_base);
 
     // Advance by SOL budget
     let sol_budget = (lamports_in as f64) / (LAMPORTS_PER_SOL as f64);
-    let x1 = clamp(x0 + sol_budget, 0.0, X_MAX);
+    let x1 = clamp(x0 + sol_budget, 0.0, x_max(tiers));
 
     // Conservative tokens_out: F_floor(x1) - F_ceil(x0), clamped at remaining cap
-    let y0_ceil = y_at_x_ceil_clamped(x0);
-    let y1_floor = y_at_x_floor_clamped(x1);
+    let y0_ceil = y_ceil(tiers, x0);
+    let y1_floor = y_floor(tiers, x1);
 
+    // A budget that reaches the end of the curve takes whatever the cap leaves,
+    // so the rounding above can't strand the last base units below the cap
     let cap_remaining = CAP_BASE_U128.saturating_sub(y_current_base as u128);
-    let dy = y1_floor.saturating_sub(y0_ceil).min(cap_remaining);
+    let dy = if x1 >= x_max(tiers) {
+        cap_remaining
+    } else {
+        y1_floor.saturating_sub(y0_ceil).min(cap_remaining)
+    };
 
     // Lamports actually used (floor; never overcharge)
     let used_sol = (x1 - x0).max(0.0);
@@ -209,9 +364,9 @@ pub fn buy_on_curve(
     (dy as u64, used_lamports.min(lamports_in))
 }
 
-/// Temporary buy curve for testing
-/// Gives you exactly 800,000,000 tokens for 0.02 SOL (2,000,000 lamports)
-/// Scales linearly in between.
+// Temporary buy curve for testing
+// Gives you exactly 800,000,000 tokens for 0.02 SOL (2,000,000 lamports)
+// Scales linearly in between.
 // pub fn buy_on_curve(
 //     _y_sold: u64,
 //     lamports_budget: u64,
@@ -227,6 +382,7 @@ pub fn buy_on_curve(
 
 /// SELL by tokens-in (base units). Returns lamports_out.
 pub fn sell_on_curve(
+    tiers: &[Tier],      // pool tier schedule (empty = legacy three tiers)
     y_current_base: u64, // cumulative sold so far (base units)
     tokens_in_base: u64, // tokens to burn (base units)
     decimals: u8,
@@ -235,27 +391,100 @@ pub fn sell_on_curve(
     if tokens_in_base == 0 { return 0; }
 
     // position on curve from cumulative sold
-    let x0 = x_from_y_lut(y_current_base);
+    let x0 = x_from_y(tiers, y_current_base);
 
     // identical to frontend: binary-search x1 so that ceil(x0) - floor(x1) >= tokens_in
-    let x1 = x_after_selling_tokens(x0, tokens_in_base);
+    let x1 = x_after_selling_tokens(tiers, x0, tokens_in_base);
 
     // lamports out (floor; never overpay)
     let sol_out = (x0 - x1).max(0.0);
     (sol_out * (LAMPORTS_PER_SOL as f64)).floor() as u64
 }
 
-// ====================== (Optional) spot helper ======================
+// ====================== Spot helper ======================
 //
-// Approximate spot price from slope of FLOOR cumulative (for UI only).
-#[allow(dead_code)]
-pub fn spot_price_sol_per_token(x: f64) -> f64 {
+// Approximate spot price from slope of FLOOR cumulative (used by the curve view).
+pub fn spot_price_sol_per_token(tiers: &[Tier], x: f64) -> f64 {
     let h = dx_sol();
-    let xl = clamp(x - 0.5 * h, 0.0, X_MAX);
-    let xr = clamp(x + 0.5 * h, 0.0, X_MAX);
-    let yl = y_at_x_floor_clamped(xl);
-    let yr = y_at_x_floor_clamped(xr);
+    let x_hi = x_max(tiers);
+    let xl = clamp(x - 0.5 * h, 0.0, x_hi);
+    let xr = clamp(x + 0.5 * h, 0.0, x_hi);
+    let yl = y_floor(tiers, xl);
+    let yr = y_floor(tiers, xr);
     let d_tokens = (yr.saturating_sub(yl)) as f64 / 10f64.powi(LUT_DECIMALS as i32);
     if d_tokens <= 0.0 { return f64::INFINITY; }
     (xr - xl) / d_tokens
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consts::{MAX_TIERS, SOLVENCY_TOLERANCE_LAMPORTS};
+
+    const ONE: u64 = 1_000_000_000;
+
+    fn lamports(sol: f64) -> u64 { (sol * LAMPORTS_PER_SOL as f64) as u64 }
+
+    /// MAX_TIERS tiers of 100M tokens over uneven widths (5..=12 SOL).
+    fn max_tier_schedule() -> Vec<Tier> {
+        (0..MAX_TIERS as u64)
+            .map(|i| Tier { sol_width: (5 + i) * LAMPORTS_PER_SOL, token_alloc: 100_000_000 })
+            .collect()
+    }
+
+    fn schedules() -> Vec<Vec<Tier>> { vec![vec![], max_tier_schedule()] }
+
+    #[test]
+    fn x_from_y_lands_on_tier_ends() {
+        for tiers in schedules() {
+            for (y_end, x_end) in tier_ends_base(&tiers).into_iter().zip(tier_ends_x(&tiers)) {
+                let x = x_from_y(&tiers, y_end);
+                assert!((x - x_end).abs() < 1e-6, "x_from_y({y_end}) = {x}, tier ends at {x_end}");
+                assert!(y_floor(&tiers, x) <= y_end as u128 + 1);
+            }
+        }
+    }
+
+    #[test]
+    fn buy_then_sell_round_trips_across_every_tier_boundary() {
+        for tiers in schedules() {
+            let ends = tier_ends_base(&tiers);
+            let ends_x = tier_ends_x(&tiers);
+            for (i, (&y_end, &x_end)) in ends.iter().zip(&ends_x).enumerate() {
+                // start 1M tokens short of the boundary, budget half a SOL past it
+                // (past the end of the curve for the last tier)
+                let y0 = y_end - 1_000_000 * ONE;
+                let budget = lamports(x_end + 0.5 - x_from_y(&tiers, y0));
+                let (dy, used) = buy_on_curve(&tiers, y0, budget, LUT_DECIMALS);
+                assert!(used <= budget);
+                assert!(y0 + dy >= y_end, "tier {i}: bought to {} short of {y_end}", y0 + dy);
+
+                // selling it straight back pays out what the buy took, give or
+                // take the f64 slack the solvency check allows for
+                let back = sell_on_curve(&tiers, y0 + dy, dy, LUT_DECIMALS);
+                assert!(back <= used + SOLVENCY_TOLERANCE_LAMPORTS, "tier {i}: sold back {back} for {used}");
+                assert!(used.saturating_sub(back) < lamports(0.001), "tier {i}: round trip lost {}", used - back);
+            }
+        }
+    }
+
+    #[test]
+    fn a_budget_past_the_end_fills_the_cap_exactly() {
+        for tiers in schedules() {
+            let (first, _) = buy_on_curve(&tiers, 0, lamports(10.0), LUT_DECIMALS);
+            let (rest, _) = buy_on_curve(&tiers, first, lamports(200.0), LUT_DECIMALS);
+            assert_eq!(first + rest, cap_base(LUT_DECIMALS));
+        }
+    }
+
+    #[test]
+    fn selling_everything_pays_the_x_at_total_sold() {
+        for tiers in schedules() {
+            for y in [1, 123_456_789 * ONE, 400_000_000 * ONE, cap_base(LUT_DECIMALS) - 1] {
+                let all = sell_on_curve(&tiers, y, y, LUT_DECIMALS);
+                let x = lamports(x_from_y(&tiers, y));
+                assert!(all.abs_diff(x) <= 1_000, "y {y}: sell-all {all}, x {x}");
+            }
+        }
+    }
+}
@@ -1,3 +1,4 @@
 pub mod calc;
 pub use calc::*;
-pub mod curve;
\ No newline at end of file
+pub mod curve;
+pub mod amm;
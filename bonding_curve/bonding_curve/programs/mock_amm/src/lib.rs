@@ -0,0 +1,248 @@
+//! Minimal stand-in for Raydium CP-Swap used by the bonding_curve tests.
+//! `initialize` takes the same accounts, args and PDA seeds as cp-swap, and
+//! `PoolState` keeps cp-swap's field order so readers of the real account work here too.
+//! No fees, no swaps, no price math beyond the LP amount.
+
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, MintTo, Token, TokenAccount, Transfer},
+};
+
+// Same id as Raydium CPMM on devnet, so the bonding curve's allowlist accepts it.
+declare_id!("DRaycpLY18LhpbydsBWbVJtxpNv9oXPgjRSfpF2bWpYb");
+
+pub const AUTH_SEED: &str = "vault_and_lp_mint_auth_seed";
+pub const POOL_SEED: &str = "pool";
+pub const POOL_LP_MINT_SEED: &str = "pool_lp_mint";
+pub const POOL_VAULT_SEED: &str = "pool_vault";
+pub const OBSERVATION_SEED: &str = "observation";
+
+#[program]
+pub mod mock_amm {
+    use super::*;
+
+    pub fn initialize(
+        ctx: Context<Initialize>,
+        init_amount_0: u64,
+        init_amount_1: u64,
+        open_time: u64,
+    ) -> Result<()> {
+        require_keys_neq!(ctx.accounts.token_0_mint.key(), ctx.accounts.token_1_mint.key());
+        require!(
+            ctx.accounts.token_0_mint.key() < ctx.accounts.token_1_mint.key(),
+            MockAmmError::InvalidMintOrder
+        );
+
+        // Seed the vaults from the creator's accounts
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.creator_token_0.to_account_info(),
+                    to: ctx.accounts.token_0_vault.to_account_info(),
+                    authority: ctx.accounts.creator.to_account_info(),
+                },
+            ),
+            init_amount_0,
+        )?;
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.creator_token_1.to_account_info(),
+                    to: ctx.accounts.token_1_vault.to_account_info(),
+                    authority: ctx.accounts.creator.to_account_info(),
+                },
+            ),
+            init_amount_1,
+        )?;
+
+        // LP = sqrt(amount_0 * amount_1), like cp-swap (minus its lock-up)
+        let lp_amount = isqrt((init_amount_0 as u128) * (init_amount_1 as u128)) as u64;
+        let auth_bump = ctx.bumps.authority;
+        let auth_seeds: &[&[u8]] = &[AUTH_SEED.as_bytes(), &[auth_bump]];
+        token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.lp_mint.to_account_info(),
+                    to: ctx.accounts.creator_lp_token.to_account_info(),
+                    authority: ctx.accounts.authority.to_account_info(),
+                },
+                &[auth_seeds],
+            ),
+            lp_amount,
+        )?;
+
+        let pool = &mut ctx.accounts.pool_state;
+        pool.amm_config = ctx.accounts.amm_config.key();
+        pool.pool_creator = ctx.accounts.creator.key();
+        pool.token_0_vault = ctx.accounts.token_0_vault.key();
+        pool.token_1_vault = ctx.accounts.token_1_vault.key();
+        pool.lp_mint = ctx.accounts.lp_mint.key();
+        pool.token_0_mint = ctx.accounts.token_0_mint.key();
+        pool.token_1_mint = ctx.accounts.token_1_mint.key();
+        pool.token_0_program = ctx.accounts.token_0_program.key();
+        pool.token_1_program = ctx.accounts.token_1_program.key();
+        pool.observation_key = ctx.accounts.observation_state.key();
+        pool.auth_bump = auth_bump;
+        pool.status = 0;
+        pool.lp_mint_decimals = ctx.accounts.lp_mint.decimals;
+        pool.mint_0_decimals = ctx.accounts.token_0_mint.decimals;
+        pool.mint_1_decimals = ctx.accounts.token_1_mint.decimals;
+        pool.lp_supply = lp_amount;
+        pool.open_time = open_time;
+        pool.recent_epoch = Clock::get()?.epoch;
+
+        Ok(())
+    }
+}
+
+// Newton's method; platform-tools' rustc predates u128::isqrt
+fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    let mut x = n;
+    let mut y = x.div_ceil(2);
+    while y < x {
+        x = y;
+        y = (x + n / x) / 2;
+    }
+    x
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// CHECK: fee config; not read by the mock
+    pub amm_config: UncheckedAccount<'info>,
+
+    /// CHECK: vault + LP mint authority PDA
+    #[account(seeds = [AUTH_SEED.as_bytes()], bump)]
+    pub authority: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + PoolState::INIT_SPACE,
+        seeds = [
+            POOL_SEED.as_bytes(),
+            amm_config.key().as_ref(),
+            token_0_mint.key().as_ref(),
+            token_1_mint.key().as_ref(),
+        ],
+        bump
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    pub token_0_mint: Box<Account<'info, Mint>>,
+    pub token_1_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = creator,
+        seeds = [POOL_LP_MINT_SEED.as_bytes(), pool_state.key().as_ref()],
+        bump,
+        mint::decimals = 9,
+        mint::authority = authority,
+    )]
+    pub lp_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut, token::mint = token_0_mint, token::authority = creator)]
+    pub creator_token_0: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, token::mint = token_1_mint, token::authority = creator)]
+    pub creator_token_1: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = lp_mint,
+        associated_token::authority = creator,
+    )]
+    pub creator_lp_token: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = creator,
+        seeds = [POOL_VAULT_SEED.as_bytes(), pool_state.key().as_ref(), token_0_mint.key().as_ref()],
+        bump,
+        token::mint = token_0_mint,
+        token::authority = authority,
+    )]
+    pub token_0_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = creator,
+        seeds = [POOL_VAULT_SEED.as_bytes(), pool_state.key().as_ref(), token_1_mint.key().as_ref()],
+        bump,
+        token::mint = token_1_mint,
+        token::authority = authority,
+    )]
+    pub token_1_vault: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: create-pool fee receiver; the mock charges no fee
+    #[account(mut)]
+    pub create_pool_fee: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + ObservationState::INIT_SPACE,
+        seeds = [OBSERVATION_SEED.as_bytes(), pool_state.key().as_ref()],
+        bump
+    )]
+    pub observation_state: Box<Account<'info, ObservationState>>,
+
+    pub token_program: Program<'info, Token>,
+    pub token_0_program: Program<'info, Token>,
+    pub token_1_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Prefix of cp-swap's `PoolState` (same discriminator, same field order).
+#[account]
+#[derive(InitSpace)]
+pub struct PoolState {
+    pub amm_config: Pubkey,
+    pub pool_creator: Pubkey,
+    pub token_0_vault: Pubkey,
+    pub token_1_vault: Pubkey,
+    pub lp_mint: Pubkey,
+    pub token_0_mint: Pubkey,
+    pub token_1_mint: Pubkey,
+    pub token_0_program: Pubkey,
+    pub token_1_program: Pubkey,
+    pub observation_key: Pubkey,
+    pub auth_bump: u8,
+    pub status: u8,
+    pub lp_mint_decimals: u8,
+    pub mint_0_decimals: u8,
+    pub mint_1_decimals: u8,
+    pub lp_supply: u64,
+    pub protocol_fees_token_0: u64,
+    pub protocol_fees_token_1: u64,
+    pub fund_fees_token_0: u64,
+    pub fund_fees_token_1: u64,
+    pub open_time: u64,
+    pub recent_epoch: u64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ObservationState {
+    pub pool_id: Pubkey,
+}
+
+#[error_code]
+pub enum MockAmmError {
+    #[msg("token_0_mint must sort before token_1_mint")]
+    InvalidMintOrder,
+}
@@ -1,28 +1,90 @@
 use anchor_lang::{
     prelude::*,
     AccountDeserialize,    // for try_deserialize
+    Discriminator,         // for ::DISCRIMINATOR
     InstructionData,       // for .data()
     ToAccountMetas,        // for .to_account_metas()
 };
-use bonding_curve::state::CurveConfiguration;
-use solana_program::instruction::Instruction;
-use solana_program::sysvar::{rent::Rent, SysvarId}; // Rent::id() needs SysvarId in scope
-use solana_program_test::{processor, tokio, ProgramTest};
+use anchor_spl::associated_token::{
+    get_associated_token_address, spl_associated_token_account::instruction as ata_ix,
+};
+use anchor_spl::token::spl_token::{self, native_mint, state::Mint as SplMint};
+use bonding_curve::errors::CustomError;
+use bonding_curve::state::{
+    AmmKind, AmmTarget, ConfigUpdate, GlobalConfig, GraduatedPool, LiquidityPool, LpLockConfig,
+    PoolPhase, BonusMode, Tier, TierBonusConfig, TierParticipation, TokenMetadataArgs, VestingConfig,
+    VestingEscrow,
+};
+use bonding_curve::instructions::curve_view::CurveView;
+use bonding_curve::consts::SOLVENCY_TOLERANCE_LAMPORTS;
+use bonding_curve::InvariantsChecked;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_lang::solana_program::program_error::ProgramError;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::metadata::mpl_token_metadata;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::solana_program::sysvar::{rent::Rent, SysvarId}; // Rent::id() needs SysvarId in scope
+use solana_program_test::{processor, tokio, BanksClientError, ProgramTest, ProgramTestContext};
 use solana_sdk::{
-    account::Account,
+    account::{Account, AccountSharedData},
+    instruction::InstructionError,
     signature::Keypair,
     signer::Signer,
+    system_instruction,
     system_program,
-    transaction::Transaction,
+    transaction::{Transaction, TransactionError},
 };
 
+// Anchor's entrypoints tie the accounts slice and the AccountInfos to one
+// lifetime, which processor! can't express; leak the slice to satisfy it.
+fn bonding_curve_entry(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    bonding_curve::entry(program_id, Box::leak(accounts.to_vec().into_boxed_slice()), data)
+}
+
+fn mock_amm_entry(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    mock_amm::entry(program_id, Box::leak(accounts.to_vec().into_boxed_slice()), data)
+}
+
+/// Stands in for Metaplex (no .so offline): takes CreateMetadataAccountV3
+/// only from the mint's current, signing mint authority, and keeps the raw
+/// instruction data at the metadata PDA for the test to read.
+fn mock_metadata_entry(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    const CREATE_METADATA_ACCOUNT_V3: u8 = 33;
+    let [metadata, mint, mint_authority, payer, _update_authority, system, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if data.first() != Some(&CREATE_METADATA_ACCOUNT_V3) {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let authority = SplMint::unpack(&mint.data.borrow())?.mint_authority;
+    if !mint_authority.is_signer || authority != COption::Some(*mint_authority.key) {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    let (_, bump) = Pubkey::find_program_address(&[b"metadata", program_id.as_ref(), mint.key.as_ref()], program_id);
+    invoke_signed(
+        &system_instruction::create_account(
+            payer.key,
+            metadata.key,
+            Rent::default().minimum_balance(data.len()),
+            data.len() as u64,
+            program_id,
+        ),
+        &[payer.clone(), metadata.clone(), system.clone()],
+        &[&[b"metadata", program_id.as_ref(), mint.key.as_ref(), &[bump]]],
+    )?;
+    metadata.data.borrow_mut().copy_from_slice(data);
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_initialize() {
     // Register the on-chain processor for this program
     let mut validator = ProgramTest::new(
         "bonding_curve",
         bonding_curve::id(),                 // must match declare_id! in lib.rs
-        processor!(bonding_curve::entry),    // wire the entrypoint
+        processor!(bonding_curve_entry),    // wire the entrypoint
     );
 
     // fund a user
@@ -64,8 +126,2384 @@ async fn test_initialize() {
 
     // fetch & deserialize
     let account = ctx.banks_client.get_account(cfg_pda).await.unwrap().unwrap();
-    let cfg: CurveConfiguration =
-        CurveConfiguration::try_deserialize(&mut account.data.as_slice()).unwrap();
+    let cfg: GlobalConfig = GlobalConfig::try_deserialize(&mut account.data.as_slice()).unwrap();
 
     assert_eq!(cfg.fees, 0.0);
+    assert_eq!(cfg.admin, user.pubkey());
+    assert_eq!(cfg.version, GlobalConfig::VERSION);
+
+    // admin can update a single field; the rest stays
+    let update_ix = Instruction {
+        program_id: bonding_curve::id(),
+        accounts: bonding_curve::accounts::UpdateConfig {
+            dex_configuration_account: cfg_pda,
+            admin: user.pubkey(),
+        }
+        .to_account_metas(None),
+        data: bonding_curve::instruction::UpdateConfig {
+            update: ConfigUpdate {
+                fees: Some(1.5),
+                initial_lamports_for_pool: None,
+                migration_fee_bps: None,
+                max_creator_bonus_bps: None,
+                guardian: None,
+                amm_allowlist: None,
+            },
+        }
+        .data(),
+    };
+    let tx = Transaction::new_signed_with_payer(
+        &[update_ix],
+        Some(&user.pubkey()),
+        &[&user],
+        ctx.last_blockhash,
+    );
+    ctx.banks_client.process_transaction(tx).await.unwrap();
+
+    let account = ctx.banks_client.get_account(cfg_pda).await.unwrap().unwrap();
+    let updated = GlobalConfig::try_deserialize(&mut account.data.as_slice()).unwrap();
+    assert_eq!(updated.fees, 1.5);
+    assert_eq!(updated.initial_lamports_for_pool, cfg.initial_lamports_for_pool);
+}
+
+/// A deployment still on the fees-only CurveConfiguration is rewritten in place
+/// by the program's upgrade authority, and by nobody else.
+#[tokio::test]
+async fn test_upgrade_config_migrates_the_legacy_layout() {
+    use anchor_lang::solana_program::bpf_loader_upgradeable::{self, UpgradeableLoaderState};
+
+    let mut validator = ProgramTest::new(
+        "bonding_curve",
+        bonding_curve::id(),
+        processor!(bonding_curve_entry),
+    );
+    let (upgrader, stranger) = (Keypair::new(), Keypair::new());
+    for who in [&upgrader, &stranger] {
+        validator.add_account(who.pubkey(), Account { lamports: 1_000_000_000, ..Account::default() });
+    }
+    let (cfg_pda, _) = Pubkey::find_program_address(&[b"CurveConfiguration"], &bonding_curve::id());
+    let (program_data, _) =
+        Pubkey::find_program_address(&[bonding_curve::id().as_ref()], &bpf_loader_upgradeable::id());
+
+    // The old layout: discriminator + fees, in a 48-byte account
+    let mut legacy = bonding_curve::state::CurveConfiguration { fees: 2.5 }.try_to_vec().unwrap();
+    legacy.splice(0..0, bonding_curve::state::CurveConfiguration::DISCRIMINATOR.iter().copied());
+    legacy.resize(bonding_curve::state::CurveConfiguration::ACCOUNT_SIZE, 0);
+    validator.add_account(
+        cfg_pda,
+        Account {
+            lamports: Rent::default().minimum_balance(legacy.len()),
+            data: legacy,
+            owner: bonding_curve::id(),
+            ..Account::default()
+        },
+    );
+    // bincode: u32 variant tag, then the fields (Option = 1-byte tag + value)
+    let mut programdata_state = vec![0u8; UpgradeableLoaderState::size_of_programdata_metadata()];
+    programdata_state[..4].copy_from_slice(&3u32.to_le_bytes()); // ProgramData, slot 0
+    programdata_state[12] = 1;
+    programdata_state[13..45].copy_from_slice(upgrader.pubkey().as_ref());
+    validator.add_account(
+        program_data,
+        Account {
+            lamports: Rent::default().minimum_balance(programdata_state.len()),
+            data: programdata_state,
+            owner: bpf_loader_upgradeable::id(),
+            ..Account::default()
+        },
+    );
+
+    let mut ctx = validator.start_with_context().await;
+    let upgrade_ix = |authority: Pubkey| Instruction {
+        program_id: bonding_curve::id(),
+        accounts: bonding_curve::accounts::UpgradeConfig {
+            dex_configuration_account: cfg_pda,
+            program_data,
+            authority,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: bonding_curve::instruction::UpgradeConfig {}.data(),
+    };
+    let send = |signer: &Keypair, blockhash| {
+        Transaction::new_signed_with_payer(&[upgrade_ix(signer.pubkey())], Some(&signer.pubkey()), &[signer], blockhash)
+    };
+
+    // Anyone but the upgrade authority is turned away
+    let err = ctx
+        .banks_client
+        .process_transaction(send(&stranger, ctx.last_blockhash))
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(err), code(CustomError::Unauthorized));
+
+    ctx.banks_client.process_transaction(send(&upgrader, ctx.last_blockhash)).await.unwrap();
+    let account = ctx.banks_client.get_account(cfg_pda).await.unwrap().unwrap();
+    assert_eq!(account.data.len(), GlobalConfig::ACCOUNT_SIZE);
+    assert!(account.lamports >= Rent::default().minimum_balance(GlobalConfig::ACCOUNT_SIZE));
+    let cfg = GlobalConfig::try_deserialize(&mut account.data.as_slice()).unwrap();
+    assert_eq!(cfg.version, GlobalConfig::VERSION);
+    assert_eq!(cfg.fees, 2.5);
+    assert_eq!(cfg.admin, upgrader.pubkey());
+
+    // Only the legacy layout is rewritten
+    let blockhash = ctx.get_new_latest_blockhash().await.unwrap();
+    let err = ctx.banks_client.process_transaction(send(&upgrader, blockhash)).await.unwrap_err();
+    assert_eq!(custom_error(err), code(CustomError::ConfigAlreadyUpgraded));
+}
+
+/// Cap is hit on the curve, then finalize_migration seeds a CPMM pool (mock AMM
+/// under the devnet CPMM id) straight from the vault PDA.
+#[tokio::test]
+async fn test_finalize_migration_creates_amm_pool() {
+    let mut validator = ProgramTest::new(
+        "bonding_curve",
+        bonding_curve::id(),
+        processor!(bonding_curve_entry),
+    );
+    validator.add_program("mock_amm", mock_amm::id(), processor!(mock_amm_entry));
+
+    // wSOL mint, in case the test validator doesn't ship it
+    let mut native_mint_data = vec![0u8; SplMint::LEN];
+    SplMint {
+        decimals: 9,
+        is_initialized: true,
+        ..SplMint::default()
+    }
+    .pack_into_slice(&mut native_mint_data);
+    validator.add_account(
+        native_mint::ID,
+        Account {
+            lamports: 1_000_000_000,
+            data: native_mint_data,
+            owner: spl_token::ID,
+            ..Account::default()
+        },
+    );
+
+    let user = Keypair::new();
+    validator.add_account(
+        user.pubkey(),
+        Account { lamports: 200_000_000_000, ..Account::default() },
+    );
+
+    let ctx = validator.start_with_context().await;
+    let decimals = 9u8;
+    let one = 10u64.pow(decimals as u32);
+
+    let send = |ixs: Vec<Instruction>, extra: Vec<&Keypair>, blockhash| {
+        let mut signers = vec![&user];
+        signers.extend(extra);
+        Transaction::new_signed_with_payer(&ixs, Some(&user.pubkey()), &signers, blockhash)
+    };
+
+    // --- config + mint ---
+    let (cfg_pda, _) =
+        Pubkey::find_program_address(&[b"CurveConfiguration"], &bonding_curve::id());
+    let mint = Keypair::new();
+    let mint_pk = mint.pubkey();
+    let user_ata = get_associated_token_address(&user.pubkey(), &mint_pk);
+    let rent = ctx.banks_client.get_rent().await.unwrap();
+
+    let ixs = vec![
+        Instruction {
+            program_id: bonding_curve::id(),
+            accounts: bonding_curve::accounts::InitializeCurveConfiguration {
+                dex_configuration_account: cfg_pda,
+                admin: user.pubkey(),
+                rent: Rent::id(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: bonding_curve::instruction::Initialize { fee: 0.0 }.data(),
+        },
+        system_instruction::create_account(
+            &user.pubkey(),
+            &mint_pk,
+            rent.minimum_balance(SplMint::LEN),
+            SplMint::LEN as u64,
+            &spl_token::ID,
+        ),
+        spl_token::instruction::initialize_mint(&spl_token::ID, &mint_pk, &user.pubkey(), None, decimals)
+            .unwrap(),
+        ata_ix::create_associated_token_account(&user.pubkey(), &user.pubkey(), &mint_pk, &spl_token::ID),
+    ];
+    let tx = send(ixs, vec![&mint], ctx.last_blockhash);
+    ctx.banks_client.process_transaction(tx).await.unwrap();
+
+    // --- pool PDAs ---
+    let (pool_pda, _) = Pubkey::find_program_address(
+        &[LiquidityPool::POOL_SEED_PREFIX.as_bytes(), mint_pk.as_ref()],
+        &bonding_curve::id(),
+    );
+    let (sol_vault, _) = Pubkey::find_program_address(
+        &[LiquidityPool::SOL_VAULT_PREFIX.as_bytes(), mint_pk.as_ref()],
+        &bonding_curve::id(),
+    );
+    let (treasury_pda, _) = Pubkey::find_program_address(
+        &[LiquidityPool::TREASURY_SEED_PREFIX.as_bytes(), mint_pk.as_ref()],
+        &bonding_curve::id(),
+    );
+    let pool_ata = get_associated_token_address(&pool_pda, &mint_pk);
+    let treasury_ata = get_associated_token_address(&treasury_pda, &mint_pk);
+    let migration_authority = user.pubkey();
+
+    // --- fixed supply: 800M curve supply in the pool ATA, 200M in the treasury, mint authority revoked ---
+    let ixs = vec![
+        ata_ix::create_associated_token_account(&user.pubkey(), &pool_pda, &mint_pk, &spl_token::ID),
+        ata_ix::create_associated_token_account(&user.pubkey(), &treasury_pda, &mint_pk, &spl_token::ID),
+        spl_token::instruction::mint_to(&spl_token::ID, &mint_pk, &pool_ata, &user.pubkey(), &[], 800_000_000 * one)
+            .unwrap(),
+        spl_token::instruction::mint_to(&spl_token::ID, &mint_pk, &treasury_ata, &user.pubkey(), &[], 200_000_000 * one)
+            .unwrap(),
+        spl_token::instruction::set_authority(
+            &spl_token::ID,
+            &mint_pk,
+            None,
+            spl_token::instruction::AuthorityType::MintTokens,
+            &user.pubkey(),
+            &[],
+        )
+        .unwrap(),
+    ];
+    let tx = send(ixs, vec![], ctx.last_blockhash);
+    ctx.banks_client.process_transaction(tx).await.unwrap();
+
+    // --- create pool over the distributed supply, then add the creator's SOL seed ---
+    let ixs = vec![
+        Instruction {
+            program_id: bonding_curve::id(),
+            accounts: bonding_curve::accounts::CreatePool {
+                dex_configuration_account: cfg_pda,
+                pool: pool_pda,
+                token_mint: mint_pk,
+                pool_token_account: pool_ata,
+                treasury_pda,
+                treasury_token_account: treasury_ata,
+                pool_sol_vault: sol_vault,
+                payer: user.pubkey(),
+                token_program: spl_token::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+                rent: Rent::id(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: bonding_curve::instruction::CreatePool {
+                migration_authority,
+                tiers: vec![],
+                tier_ratchet: false,
+                vesting: None,
+                bonus: None,
+                lp_lock: None,
+                amm: AmmTarget { kind: AmmKind::RaydiumCpmm, program: mock_amm::id() },
+                launch_deadline: None,
+                creator_bonus_bps: Some(100),
+            }
+            .data(),
+        },
+        Instruction {
+            program_id: bonding_curve::id(),
+            accounts: bonding_curve::accounts::AddLiquidity {
+                dex_configuration_account: cfg_pda,
+                pool: pool_pda,
+                token_mint: mint_pk,
+                pool_token_account: pool_ata,
+                user_token_account: user_ata,
+                pool_sol_vault: sol_vault,
+                user: user.pubkey(),
+                rent: Rent::id(),
+                system_program: system_program::ID,
+                token_program: spl_token::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+            }
+            .to_account_metas(None),
+            data: bonding_curve::instruction::AddLiquidity {}.data(),
+        },
+    ];
+    let tx = send(ixs, vec![], ctx.last_blockhash);
+    ctx.banks_client.process_transaction(tx).await.unwrap();
+
+    // --- buy the whole curve (budget above X_MAX; the curve clamps at the cap) ---
+    let buy_ix = Instruction {
+        program_id: bonding_curve::id(),
+        accounts: bonding_curve::accounts::Buy {
+            dex_configuration_account: cfg_pda,
+            pool: pool_pda,
+            token_mint: mint_pk,
+            pool_token_account: pool_ata,
+            pool_sol_vault: sol_vault,
+            user_token_account: user_ata,
+            vesting_escrow: None,
+            vesting_token_account: None,
+            tier_participation: None,
+            treasury_pda: None,
+            treasury_token_account: None,
+            user: user.pubkey(),
+            system_program: system_program::ID,
+            token_program: spl_token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            rent: Rent::id(),
+        }
+        .to_account_metas(None),
+        data: bonding_curve::instruction::Buy { amount: 100 * one }.data(),
+    };
+    let tx = send(vec![buy_ix], vec![], ctx.last_blockhash);
+    ctx.banks_client.process_transaction(tx).await.unwrap();
+
+    // --- CPMM PDAs (token_0 < token_1) ---
+    let amm = mock_amm::id();
+    let (t0, t1) = if mint_pk < native_mint::ID {
+        (mint_pk, native_mint::ID)
+    } else {
+        (native_mint::ID, mint_pk)
+    };
+    let (amm_config, _) = Pubkey::find_program_address(&[b"amm_config", &0u16.to_be_bytes()], &amm);
+    let (amm_authority, _) = Pubkey::find_program_address(&[b"vault_and_lp_mint_auth_seed"], &amm);
+    let (amm_pool, _) =
+        Pubkey::find_program_address(&[b"pool", amm_config.as_ref(), t0.as_ref(), t1.as_ref()], &amm);
+    let (amm_lp_mint, _) = Pubkey::find_program_address(&[b"pool_lp_mint", amm_pool.as_ref()], &amm);
+    let (amm_vault_0, _) =
+        Pubkey::find_program_address(&[b"pool_vault", amm_pool.as_ref(), t0.as_ref()], &amm);
+    let (amm_vault_1, _) =
+        Pubkey::find_program_address(&[b"pool_vault", amm_pool.as_ref(), t1.as_ref()], &amm);
+    let (amm_observation, _) = Pubkey::find_program_address(&[b"observation", amm_pool.as_ref()], &amm);
+    let (lp_escrow, _) = Pubkey::find_program_address(
+        &[LiquidityPool::LP_ESCROW_PREFIX.as_bytes(), mint_pk.as_ref()],
+        &bonding_curve::id(),
+    );
+    let lp_escrow_ata = get_associated_token_address(&lp_escrow, &amm_lp_mint);
+    let (migration_escrow, _) = Pubkey::find_program_address(
+        &[LiquidityPool::MIGRATION_ESCROW_PREFIX.as_bytes(), mint_pk.as_ref()],
+        &bonding_curve::id(),
+    );
+
+    let (protocol_fee_vault, _) = Pubkey::find_program_address(
+        &[LiquidityPool::PROTOCOL_FEE_PREFIX.as_bytes()],
+        &bonding_curve::id(),
+    );
+
+    // curve SOL only; the vault's rent and the creator's seed sit outside reserve_sol
+    let account = ctx.banks_client.get_account(pool_pda).await.unwrap().unwrap();
+    let curve_sol = LiquidityPool::try_deserialize(&mut account.data.as_slice()).unwrap().reserve_sol;
+    let vault_rent = rent.minimum_balance(0);
+    assert!(curve_sol > 0);
+    assert_eq!(
+        ctx.banks_client.get_balance(sol_vault).await.unwrap(),
+        vault_rent + curve_sol + bonding_curve::consts::INITIAL_LAMPORTS_FOR_POOL
+    );
+
+    let ixs = vec![
+        Instruction {
+            program_id: bonding_curve::id(),
+            accounts: bonding_curve::accounts::StartMigration {
+                dex_configuration_account: cfg_pda,
+                pool: pool_pda,
+                token_mint: mint_pk,
+                pool_token_account: pool_ata,
+                pool_sol_vault: sol_vault,
+                migration_escrow,
+                escrow_token_account: get_associated_token_address(&migration_escrow, &mint_pk),
+                caller: migration_authority,
+                token_program: spl_token::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: bonding_curve::instruction::StartMigration {}.data(),
+        },
+        Instruction {
+            program_id: bonding_curve::id(),
+            accounts: bonding_curve::accounts::FinalizeMigration {
+                dex_configuration_account: cfg_pda,
+                pool: pool_pda,
+                token_mint: mint_pk,
+                wsol_mint: native_mint::ID,
+                treasury_pda,
+                treasury_token_account: treasury_ata,
+                migration_escrow,
+                escrow_token_account: get_associated_token_address(&migration_escrow, &mint_pk),
+                escrow_wsol_account: get_associated_token_address(&migration_escrow, &native_mint::ID),
+                caller: migration_authority,
+                protocol_fee_vault,
+                creator: user.pubkey(),
+                pool_sol_vault: sol_vault,
+                amm_program: amm,
+                amm_pool,
+                amm_lp_mint,
+                escrow_lp_account: get_associated_token_address(&migration_escrow, &amm_lp_mint),
+                lp_escrow,
+                lp_escrow_token_account: lp_escrow_ata,
+                token_program: spl_token::ID,
+                lp_token_program: spl_token::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+                system_program: system_program::ID,
+                rent: Rent::id(),
+            }
+            .to_account_metas(None)
+            .into_iter()
+            // CPMM adapter's remaining_accounts
+            .chain([
+                AccountMeta::new_readonly(amm_config, false),
+                AccountMeta::new_readonly(amm_authority, false),
+                AccountMeta::new(amm_vault_0, false),
+                AccountMeta::new(amm_vault_1, false),
+                AccountMeta::new(Keypair::new().pubkey(), false), // create_pool_fee
+                AccountMeta::new(amm_observation, false),
+            ])
+            .collect(),
+            data: bonding_curve::instruction::FinalizeMigration {}.data(),
+        },
+    ];
+    let tx = send(ixs, vec![], ctx.last_blockhash);
+    ctx.banks_client.process_transaction(tx).await.unwrap();
+
+    // --- pool flipped and points at the AMM pool ---
+    let account = ctx.banks_client.get_account(pool_pda).await.unwrap().unwrap();
+    let pool = LiquidityPool::try_deserialize(&mut account.data.as_slice()).unwrap();
+    assert!(pool.phase == PoolPhase::Graduated);
+    assert_eq!(pool.amm_pool, Some(amm_pool));
+    assert_eq!(pool.lp_timelock, Some(lp_escrow_ata));
+    assert_eq!(pool.total_sold, 800_000_000 * one);
+
+    // --- 1% protocol fee and 1% creator bonus come off the curve SOL ---
+    let cut = curve_sol / 100;
+    assert_eq!(ctx.banks_client.get_balance(protocol_fee_vault).await.unwrap(), cut);
+
+    // --- AMM vaults hold the treasury tokens and the escrowed SOL minus the cuts and reserve ---
+    let account = ctx.banks_client.get_account(amm_pool).await.unwrap().unwrap();
+    let amm_state = mock_amm::PoolState::try_deserialize(&mut account.data.as_slice()).unwrap();
+    assert_eq!(amm_state.pool_creator, migration_escrow);
+
+    let (token_vault, wsol_vault) = if t0 == mint_pk {
+        (amm_vault_0, amm_vault_1)
+    } else {
+        (amm_vault_1, amm_vault_0)
+    };
+    let token_amount = |acc: Account| spl_token::state::Account::unpack(&acc.data).unwrap().amount;
+    let got_tokens = token_amount(ctx.banks_client.get_account(token_vault).await.unwrap().unwrap());
+    let got_wsol = token_amount(ctx.banks_client.get_account(wsol_vault).await.unwrap().unwrap());
+    assert_eq!(got_tokens, 200_000_000 * one);
+    assert_eq!(
+        got_wsol,
+        curve_sol - 2 * cut - bonding_curve::consts::AMM_CREATION_RESERVE_LAMPORTS
+    );
+
+    // LP sits in the program-owned escrow (no lp_lock given = locked for good)
+    let lp_held = token_amount(ctx.banks_client.get_account(lp_escrow_ata).await.unwrap().unwrap());
+    assert!(lp_held > 0);
+
+    // nothing left behind in the pool or treasury ATAs, the escrow's are
+    // closed; the SOL vault is down to its rent (seed returned to the creator)
+    assert_eq!(ctx.banks_client.get_balance(sol_vault).await.unwrap(), vault_rent);
+    for ata in [pool_ata, treasury_ata] {
+        let acc = ctx.banks_client.get_account(ata).await.unwrap().unwrap();
+        assert_eq!(token_amount(acc), 0);
+    }
+    for mint in [mint_pk, native_mint::ID] {
+        let ata = get_associated_token_address(&migration_escrow, &mint);
+        assert!(ctx.banks_client.get_account(ata).await.unwrap().is_none());
+    }
+
+    // --- close the graduated pool: ATA gone, PDA shrunk to a GraduatedPool record ---
+    let close_ix = Instruction {
+        program_id: bonding_curve::id(),
+        accounts: bonding_curve::accounts::CloseGraduatedPool {
+            pool: pool_pda,
+            token_mint: mint_pk,
+            pool_token_account: pool_ata,
+            pool_sol_vault: sol_vault,
+            migration_escrow,
+            protocol_fee_vault,
+            creator: user.pubkey(),
+            lp_escrow_token_account: None,
+            caller: user.pubkey(),
+            token_program: spl_token::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: bonding_curve::instruction::CloseGraduatedPool {}.data(),
+    };
+    let tx = send(vec![close_ix], vec![], ctx.last_blockhash);
+    ctx.banks_client.process_transaction(tx).await.unwrap();
+
+    assert!(ctx.banks_client.get_account(pool_ata).await.unwrap().is_none());
+    assert_eq!(ctx.banks_client.get_balance(sol_vault).await.unwrap(), 0);
+    assert_eq!(ctx.banks_client.get_balance(protocol_fee_vault).await.unwrap(), cut);
+    let account = ctx.banks_client.get_account(pool_pda).await.unwrap().unwrap();
+    assert_eq!(account.data.len(), GraduatedPool::ACCOUNT_SIZE);
+    let record = GraduatedPool::try_deserialize(&mut account.data.as_slice()).unwrap();
+    assert_eq!(record.amm_pool, amm_pool);
+    assert_eq!(record.lp_timelock, Some(lp_escrow_ata));
+}
+
+/// A bootstrapped curve for the behavioural tests below: config, fixed
+/// 800M/200M supply with the mint authority revoked, create_pool (with the
+/// given args) and add_liquidity. `user` is config admin, creator and buyer.
+struct Curve {
+    ctx: ProgramTestContext,
+    user: Keypair,
+    one: u64,
+    cfg: Pubkey,
+    mint: Pubkey,
+    pool: Pubkey,
+    vault: Pubkey,
+    treasury: Pubkey,
+    pool_ata: Pubkey,
+    treasury_ata: Pubkey,
+    user_ata: Pubkey,
+    escrow: Pubkey,
+    escrow_ata: Pubkey,
+}
+
+/// How Curve::minted sets up the mint; the default is what create_pool
+/// accepts (9 decimals, 800M/200M, mint authority revoked, no freeze).
+struct MintSetup {
+    decimals: u8,
+    pool_tokens: u64,     // whole tokens
+    treasury_tokens: u64, // whole tokens
+    revoke_mint: bool,
+    freeze_authority: bool,
+}
+
+impl Default for MintSetup {
+    fn default() -> Self {
+        Self { decimals: 9, pool_tokens: 800_000_000, treasury_tokens: 200_000_000, revoke_mint: true, freeze_authority: false }
+    }
+}
+
+/// What a successful transaction left behind.
+#[derive(Debug)]
+struct Sent {
+    return_data: Vec<u8>,
+    compute_units: u64,
+}
+
+/// create_pool args for a plain legacy-schedule pool graduating to the mock AMM.
+fn pool_args() -> bonding_curve::instruction::CreatePool {
+    bonding_curve::instruction::CreatePool {
+        migration_authority: Pubkey::default(), // replaced by the creator in Curve::new
+        tiers: vec![],
+        tier_ratchet: false,
+        vesting: None,
+        bonus: None,
+        lp_lock: None,
+        amm: AmmTarget { kind: AmmKind::RaydiumCpmm, program: mock_amm::id() },
+        launch_deadline: None,
+        creator_bonus_bps: None,
+    }
+}
+
+/// Program test with both programs and the wSOL mint.
+fn program_test() -> ProgramTest {
+    let mut validator = ProgramTest::new(
+        "bonding_curve",
+        bonding_curve::id(),
+        processor!(bonding_curve_entry),
+    );
+    validator.add_program("mock_amm", mock_amm::id(), processor!(mock_amm_entry));
+
+    let mut native_mint_data = vec![0u8; SplMint::LEN];
+    SplMint {
+        decimals: 9,
+        is_initialized: true,
+        ..SplMint::default()
+    }
+    .pack_into_slice(&mut native_mint_data);
+    validator.add_account(
+        native_mint::ID,
+        Account {
+            lamports: 1_000_000_000,
+            data: native_mint_data,
+            owner: spl_token::ID,
+            ..Account::default()
+        },
+    );
+    validator
+}
+
+impl Curve {
+    /// Keys for a fresh mint, with the config initialised; no mint or pool yet.
+    async fn bare(mut validator: ProgramTest) -> (Self, Keypair) {
+        let user = Keypair::new();
+        validator.add_account(
+            user.pubkey(),
+            Account { lamports: 500_000_000_000, ..Account::default() },
+        );
+        let ctx = validator.start_with_context().await;
+
+        let mint = Keypair::new();
+        let mint_pk = mint.pubkey();
+        let pda = |prefix: &str| {
+            Pubkey::find_program_address(&[prefix.as_bytes(), mint_pk.as_ref()], &bonding_curve::id()).0
+        };
+        let (pool, treasury, escrow) = (
+            pda(LiquidityPool::POOL_SEED_PREFIX),
+            pda(LiquidityPool::TREASURY_SEED_PREFIX),
+            pda(LiquidityPool::MIGRATION_ESCROW_PREFIX),
+        );
+        let mut curve = Curve {
+            one: 10u64.pow(9),
+            cfg: Pubkey::find_program_address(&[b"CurveConfiguration"], &bonding_curve::id()).0,
+            mint: mint_pk,
+            pool,
+            vault: pda(LiquidityPool::SOL_VAULT_PREFIX),
+            treasury,
+            pool_ata: get_associated_token_address(&pool, &mint_pk),
+            treasury_ata: get_associated_token_address(&treasury, &mint_pk),
+            user_ata: get_associated_token_address(&user.pubkey(), &mint_pk),
+            escrow,
+            escrow_ata: get_associated_token_address(&escrow, &mint_pk),
+            ctx,
+            user,
+        };
+        let ix = Instruction {
+            program_id: bonding_curve::id(),
+            accounts: bonding_curve::accounts::InitializeCurveConfiguration {
+                dex_configuration_account: curve.cfg,
+                admin: curve.user.pubkey(),
+                rent: Rent::id(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: bonding_curve::instruction::Initialize { fee: 0.0 }.data(),
+        };
+        curve.send(vec![ix], &[]).await.unwrap();
+        (curve, mint)
+    }
+
+    async fn new(args: bonding_curve::instruction::CreatePool) -> Self {
+        let mut curve = Self::minted(MintSetup::default()).await;
+        curve.send(curve.create_pool_ixs(args), &[]).await.unwrap();
+        curve
+    }
+
+    /// A fresh mint set up as `setup` says, with its supply in the pool and
+    /// treasury ATAs (and an empty user ATA); no pool yet.
+    async fn minted(setup: MintSetup) -> Self {
+        let (mut curve, mint) = Self::bare(program_test()).await;
+        let (mint_pk, pool, treasury) = (curve.mint, curve.pool, curve.treasury);
+        let user_pk = curve.user.pubkey();
+        let one = 10u64.pow(setup.decimals as u32);
+        curve.one = one;
+        let rent = curve.ctx.banks_client.get_rent().await.unwrap();
+
+        let mut ixs = vec![
+            system_instruction::create_account(
+                &user_pk,
+                &mint_pk,
+                rent.minimum_balance(SplMint::LEN),
+                SplMint::LEN as u64,
+                &spl_token::ID,
+            ),
+            spl_token::instruction::initialize_mint(
+                &spl_token::ID,
+                &mint_pk,
+                &user_pk,
+                setup.freeze_authority.then_some(&user_pk),
+                setup.decimals,
+            )
+            .unwrap(),
+            ata_ix::create_associated_token_account(&user_pk, &user_pk, &mint_pk, &spl_token::ID),
+            ata_ix::create_associated_token_account(&user_pk, &pool, &mint_pk, &spl_token::ID),
+            ata_ix::create_associated_token_account(&user_pk, &treasury, &mint_pk, &spl_token::ID),
+            spl_token::instruction::mint_to(&spl_token::ID, &mint_pk, &curve.pool_ata, &user_pk, &[], setup.pool_tokens * one)
+                .unwrap(),
+            spl_token::instruction::mint_to(&spl_token::ID, &mint_pk, &curve.treasury_ata, &user_pk, &[], setup.treasury_tokens * one)
+                .unwrap(),
+        ];
+        if setup.revoke_mint {
+            ixs.push(
+                spl_token::instruction::set_authority(
+                    &spl_token::ID,
+                    &mint_pk,
+                    None,
+                    spl_token::instruction::AuthorityType::MintTokens,
+                    &user_pk,
+                    &[],
+                )
+                .unwrap(),
+            );
+        }
+        curve.send(ixs, &[&mint]).await.unwrap();
+        curve
+    }
+
+    /// create_pool (with `user` as migration authority) and add_liquidity.
+    fn create_pool_ixs(&self, mut args: bonding_curve::instruction::CreatePool) -> Vec<Instruction> {
+        let user_pk = self.user.pubkey();
+        args.migration_authority = user_pk;
+        vec![
+            Instruction {
+                program_id: bonding_curve::id(),
+                accounts: bonding_curve::accounts::CreatePool {
+                    dex_configuration_account: self.cfg,
+                    pool: self.pool,
+                    token_mint: self.mint,
+                    pool_token_account: self.pool_ata,
+                    treasury_pda: self.treasury,
+                    treasury_token_account: self.treasury_ata,
+                    pool_sol_vault: self.vault,
+                    payer: user_pk,
+                    token_program: spl_token::ID,
+                    associated_token_program: anchor_spl::associated_token::ID,
+                    rent: Rent::id(),
+                    system_program: system_program::ID,
+                }
+                .to_account_metas(None),
+                data: args.data(),
+            },
+            Instruction {
+                program_id: bonding_curve::id(),
+                accounts: bonding_curve::accounts::AddLiquidity {
+                    dex_configuration_account: self.cfg,
+                    pool: self.pool,
+                    token_mint: self.mint,
+                    pool_token_account: self.pool_ata,
+                    user_token_account: self.user_ata,
+                    pool_sol_vault: self.vault,
+                    user: user_pk,
+                    rent: Rent::id(),
+                    system_program: system_program::ID,
+                    token_program: spl_token::ID,
+                    associated_token_program: anchor_spl::associated_token::ID,
+                }
+                .to_account_metas(None),
+                data: bonding_curve::instruction::AddLiquidity {}.data(),
+            },
+        ]
+    }
+
+    /// launch_token over a fresh mint (metadata goes to the mock metadata
+    /// program), as the pool args say, with an optional first buy.
+    async fn launch(
+        args: bonding_curve::instruction::CreatePool,
+        initial_buy: Option<u64>,
+    ) -> std::result::Result<Self, BanksClientError> {
+        Self::launch_with(args, initial_buy, |_, _| {}).await
+    }
+
+    /// launch, with `accounts` adjusted first (e.g. to add the first buy's
+    /// vesting and participation accounts, or to drop the creator's ATA).
+    async fn launch_with(
+        args: bonding_curve::instruction::CreatePool,
+        initial_buy: Option<u64>,
+        accounts: impl FnOnce(&Self, &mut bonding_curve::accounts::LaunchToken),
+    ) -> std::result::Result<Self, BanksClientError> {
+        let mut validator = program_test();
+        validator.add_program("mpl_token_metadata", mpl_token_metadata::ID, processor!(mock_metadata_entry));
+        let (mut curve, mint) = Self::bare(validator).await;
+        let user_pk = curve.user.pubkey();
+        let mut launch_accounts = bonding_curve::accounts::LaunchToken {
+            dex_configuration_account: curve.cfg,
+            pool: curve.pool,
+            token_mint: curve.mint,
+            metadata: curve.metadata(),
+            pool_token_account: curve.pool_ata,
+            treasury_pda: curve.treasury,
+            treasury_token_account: curve.treasury_ata,
+            pool_sol_vault: curve.vault,
+            user_token_account: initial_buy.map(|_| curve.user_ata),
+            vesting_escrow: None,
+            vesting_token_account: None,
+            tier_participation: None,
+            creator: user_pk,
+            token_program: spl_token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            token_metadata_program: mpl_token_metadata::ID,
+            rent: Rent::id(),
+            system_program: system_program::ID,
+        };
+        accounts(&curve, &mut launch_accounts);
+        let ix = Instruction {
+            program_id: bonding_curve::id(),
+            accounts: launch_accounts.to_account_metas(None),
+            data: bonding_curve::instruction::LaunchToken {
+                metadata: TokenMetadataArgs {
+                    name: "Curve".into(),
+                    symbol: "CRV".into(),
+                    uri: "https://example.com/crv.json".into(),
+                },
+                migration_authority: user_pk,
+                tiers: args.tiers,
+                tier_ratchet: args.tier_ratchet,
+                vesting: args.vesting,
+                bonus: args.bonus,
+                lp_lock: args.lp_lock,
+                amm: args.amm,
+                launch_deadline: args.launch_deadline,
+                creator_bonus_bps: args.creator_bonus_bps,
+                initial_buy,
+            }
+            .data(),
+        };
+        curve.send(vec![ix], &[&mint]).await?;
+        Ok(curve)
+    }
+
+    fn metadata(&self) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"metadata", mpl_token_metadata::ID.as_ref(), self.mint.as_ref()],
+            &mpl_token_metadata::ID,
+        )
+        .0
+    }
+
+    /// Signs with `user` (fee payer) plus `extra`, on a fresh blockhash so
+    /// repeating an instruction is a new transaction.
+    async fn send(&mut self, ixs: Vec<Instruction>, extra: &[&Keypair]) -> std::result::Result<Sent, BanksClientError> {
+        let blockhash = self.ctx.get_new_latest_blockhash().await.unwrap();
+        let mut signers = vec![&self.user];
+        signers.extend(extra);
+        let tx = Transaction::new_signed_with_payer(&ixs, Some(&self.user.pubkey()), &signers, blockhash);
+        let res = self.ctx.banks_client.process_transaction_with_metadata(tx).await?;
+        res.result?;
+        let meta = res.metadata.unwrap();
+        Ok(Sent {
+            return_data: meta.return_data.map(|r| r.data).unwrap_or_default(),
+            compute_units: meta.compute_units_consumed,
+        })
+    }
+
+    async fn state(&mut self) -> LiquidityPool {
+        let account = self.ctx.banks_client.get_account(self.pool).await.unwrap().unwrap();
+        LiquidityPool::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    async fn lamports(&mut self, key: Pubkey) -> u64 {
+        self.ctx.banks_client.get_balance(key).await.unwrap()
+    }
+
+    async fn tokens(&mut self, ata: Pubkey) -> u64 {
+        let acc = self.ctx.banks_client.get_account(ata).await.unwrap().unwrap();
+        spl_token::state::Account::unpack(&acc.data).unwrap().amount
+    }
+
+    /// Overwrites the pool account, e.g. to fake a bookkeeping bug.
+    async fn set_state(&mut self, pool: &LiquidityPool) {
+        let mut account = self.ctx.banks_client.get_account(self.pool).await.unwrap().unwrap();
+        pool.try_serialize(&mut &mut account.data[..]).unwrap();
+        self.ctx.set_account(&self.pool, &AccountSharedData::from(account));
+    }
+
+    async fn set_lamports(&mut self, key: Pubkey, lamports: u64) {
+        let mut account = self.ctx.banks_client.get_account(key).await.unwrap().unwrap();
+        account.lamports = lamports;
+        self.ctx.set_account(&key, &AccountSharedData::from(account));
+    }
+
+    /// Moves the clock `slots` ahead (and unix time with it, 400ms a slot).
+    async fn warp(&mut self, slots: u64) {
+        let mut clock: Clock = self.ctx.banks_client.get_sysvar().await.unwrap();
+        let slot = clock.slot + slots;
+        self.ctx.warp_to_slot(slot).unwrap();
+        clock.slot = slot;
+        clock.unix_timestamp += (slots * 2 / 5) as i64;
+        self.ctx.set_sysvar(&clock);
+    }
+
+    fn pda(&self, prefix: &str) -> Pubkey {
+        Pubkey::find_program_address(
+            &[prefix.as_bytes(), self.pool.as_ref(), self.user.pubkey().as_ref()],
+            &bonding_curve::id(),
+        )
+        .0
+    }
+
+    /// Buy with every optional account passed (vesting escrow, tier
+    /// participation, treasury), so any pool configuration accepts it.
+    fn buy_ix(&self, lamports: u64) -> Instruction {
+        let vesting_escrow = self.pda(VestingEscrow::SEED_PREFIX);
+        Instruction {
+            program_id: bonding_curve::id(),
+            accounts: bonding_curve::accounts::Buy {
+                dex_configuration_account: self.cfg,
+                pool: self.pool,
+                token_mint: self.mint,
+                pool_token_account: self.pool_ata,
+                pool_sol_vault: self.vault,
+                user_token_account: self.user_ata,
+                vesting_escrow: Some(vesting_escrow),
+                vesting_token_account: Some(get_associated_token_address(&vesting_escrow, &self.mint)),
+                tier_participation: Some(self.pda(TierParticipation::SEED_PREFIX)),
+                treasury_pda: Some(self.treasury),
+                treasury_token_account: Some(self.treasury_ata),
+                user: self.user.pubkey(),
+                system_program: system_program::ID,
+                token_program: spl_token::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+                rent: Rent::id(),
+            }
+            .to_account_metas(None),
+            data: bonding_curve::instruction::Buy { amount: lamports }.data(),
+        }
+    }
+
+    fn sell_ix(&self, tokens: u64) -> Instruction {
+        Instruction {
+            program_id: bonding_curve::id(),
+            accounts: bonding_curve::accounts::Sell {
+                dex_configuration_account: self.cfg,
+                pool: self.pool,
+                token_mint: self.mint,
+                pool_token_account: self.pool_ata,
+                pool_sol_vault: self.vault,
+                user_token_account: self.user_ata,
+                tier_participation: Some(self.pda(TierParticipation::SEED_PREFIX)),
+                user: self.user.pubkey(),
+                system_program: system_program::ID,
+                token_program: spl_token::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+                rent: Rent::id(),
+            }
+            .to_account_metas(None),
+            data: bonding_curve::instruction::SellV2 { amount: tokens }.data(),
+        }
+    }
+
+    fn claim_tier_bonus_ix(&self, tier: u8) -> Instruction {
+        Instruction {
+            program_id: bonding_curve::id(),
+            accounts: bonding_curve::accounts::ClaimTierBonus {
+                pool: self.pool,
+                token_mint: self.mint,
+                tier_participation: self.pda(TierParticipation::SEED_PREFIX),
+                treasury_pda: self.treasury,
+                treasury_token_account: self.treasury_ata,
+                user_token_account: self.user_ata,
+                user: self.user.pubkey(),
+                system_program: system_program::ID,
+                token_program: spl_token::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+            }
+            .to_account_metas(None),
+            data: bonding_curve::instruction::ClaimTierBonus { tier }.data(),
+        }
+    }
+
+    fn claim_vested_ix(&self) -> Instruction {
+        let vesting_escrow = self.pda(VestingEscrow::SEED_PREFIX);
+        Instruction {
+            program_id: bonding_curve::id(),
+            accounts: bonding_curve::accounts::ClaimVested {
+                pool: self.pool,
+                token_mint: self.mint,
+                vesting_escrow,
+                vesting_token_account: get_associated_token_address(&vesting_escrow, &self.mint),
+                user_token_account: self.user_ata,
+                user: self.user.pubkey(),
+                system_program: system_program::ID,
+                token_program: spl_token::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+            }
+            .to_account_metas(None),
+            data: bonding_curve::instruction::ClaimVested {}.data(),
+        }
+    }
+
+    async fn participation(&mut self) -> TierParticipation {
+        let key = self.pda(TierParticipation::SEED_PREFIX);
+        let account = self.ctx.banks_client.get_account(key).await.unwrap().unwrap();
+        TierParticipation::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    /// Moves config admin from `user` to `admin` (transfer + accept).
+    async fn hand_admin_to(&mut self, admin: &Keypair) {
+        let ixs = vec![
+            Instruction {
+                program_id: bonding_curve::id(),
+                accounts: bonding_curve::accounts::TransferAdmin {
+                    dex_configuration_account: self.cfg,
+                    admin: self.user.pubkey(),
+                }
+                .to_account_metas(None),
+                data: bonding_curve::instruction::TransferAdmin { new_admin: admin.pubkey() }.data(),
+            },
+            Instruction {
+                program_id: bonding_curve::id(),
+                accounts: bonding_curve::accounts::AcceptAdmin {
+                    dex_configuration_account: self.cfg,
+                    pending_admin: admin.pubkey(),
+                }
+                .to_account_metas(None),
+                data: bonding_curve::instruction::AcceptAdmin {}.data(),
+            },
+        ];
+        self.send(ixs, &[admin]).await.unwrap();
+    }
+
+    async fn set_guardian(&mut self, guardian: Pubkey) {
+        let ix = Instruction {
+            program_id: bonding_curve::id(),
+            accounts: bonding_curve::accounts::UpdateConfig {
+                dex_configuration_account: self.cfg,
+                admin: self.user.pubkey(),
+            }
+            .to_account_metas(None),
+            data: bonding_curve::instruction::UpdateConfig {
+                update: ConfigUpdate {
+                    fees: None,
+                    initial_lamports_for_pool: None,
+                    migration_fee_bps: None,
+                    max_creator_bonus_bps: None,
+                    guardian: Some(guardian),
+                    amm_allowlist: None,
+                },
+            }
+            .data(),
+        };
+        self.send(vec![ix], &[]).await.unwrap();
+    }
+
+    fn set_paused_ix(&self, guardian: Pubkey, paused: bool) -> Instruction {
+        Instruction {
+            program_id: bonding_curve::id(),
+            accounts: bonding_curve::accounts::SetPaused {
+                dex_configuration_account: self.cfg,
+                guardian,
+            }
+            .to_account_metas(None),
+            data: bonding_curve::instruction::SetPaused { paused }.data(),
+        }
+    }
+
+    fn set_pool_paused_ix(&self, guardian: Pubkey, paused: bool) -> Instruction {
+        Instruction {
+            program_id: bonding_curve::id(),
+            accounts: bonding_curve::accounts::SetPoolPaused {
+                dex_configuration_account: self.cfg,
+                pool: self.pool,
+                token_mint: self.mint,
+                guardian,
+            }
+            .to_account_metas(None),
+            data: bonding_curve::instruction::SetPoolPaused { paused }.data(),
+        }
+    }
+
+    fn request_emergency_withdraw_ix(&self, admin: Pubkey) -> Instruction {
+        Instruction {
+            program_id: bonding_curve::id(),
+            accounts: bonding_curve::accounts::RequestEmergencyWithdraw {
+                dex_configuration_account: self.cfg,
+                pool: self.pool,
+                token_mint: self.mint,
+                admin,
+            }
+            .to_account_metas(None),
+            data: bonding_curve::instruction::RequestEmergencyWithdraw {}.data(),
+        }
+    }
+
+    fn cancel_emergency_withdraw_ix(&self, admin: Pubkey) -> Instruction {
+        Instruction {
+            program_id: bonding_curve::id(),
+            accounts: bonding_curve::accounts::CancelEmergencyWithdraw {
+                dex_configuration_account: self.cfg,
+                pool: self.pool,
+                token_mint: self.mint,
+                admin,
+            }
+            .to_account_metas(None),
+            data: bonding_curve::instruction::CancelEmergencyWithdraw {}.data(),
+        }
+    }
+
+    fn remove_liquidity_ix(&self, user: Pubkey) -> Instruction {
+        Instruction {
+            program_id: bonding_curve::id(),
+            accounts: bonding_curve::accounts::RemoveLiquidity {
+                dex_configuration_account: self.cfg,
+                pool: self.pool,
+                token_mint: self.mint,
+                pool_token_account: self.pool_ata,
+                user_token_account: get_associated_token_address(&user, &self.mint),
+                pool_sol_vault: self.vault,
+                treasury_pda: Some(self.treasury),
+                treasury_token_account: Some(self.treasury_ata),
+                user,
+                rent: Rent::id(),
+                system_program: system_program::ID,
+                token_program: spl_token::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+            }
+            .to_account_metas(None),
+            data: bonding_curve::instruction::RemoveLiquidityV2 {}.data(),
+        }
+    }
+
+    fn redeem_ix(&self, amount: u64) -> Instruction {
+        Instruction {
+            program_id: bonding_curve::id(),
+            accounts: bonding_curve::accounts::Redeem {
+                pool: self.pool,
+                token_mint: self.mint,
+                pool_token_account: self.pool_ata,
+                treasury_pda: self.treasury,
+                treasury_token_account: self.treasury_ata,
+                pool_sol_vault: self.vault,
+                user_token_account: self.user_ata,
+                user: self.user.pubkey(),
+                token_program: spl_token::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: bonding_curve::instruction::Redeem { amount }.data(),
+        }
+    }
+
+    fn reclaim_expired_pool_ix(&self, creator: Pubkey) -> Instruction {
+        Instruction {
+            program_id: bonding_curve::id(),
+            accounts: bonding_curve::accounts::ReclaimExpiredPool {
+                pool: self.pool,
+                token_mint: self.mint,
+                pool_sol_vault: self.vault,
+                creator,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: bonding_curve::instruction::ReclaimExpiredPool {}.data(),
+        }
+    }
+
+    fn sweep_donations_ix(&self) -> Instruction {
+        Instruction {
+            program_id: bonding_curve::id(),
+            accounts: bonding_curve::accounts::SweepDonations {
+                pool: self.pool,
+                token_mint: self.mint,
+                pool_token_account: self.pool_ata,
+                pool_sol_vault: self.vault,
+                protocol_fee_vault: protocol_fee_vault(),
+                caller: self.user.pubkey(),
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: bonding_curve::instruction::SweepDonations {}.data(),
+        }
+    }
+
+    fn check_invariants_ix(&self) -> Instruction {
+        Instruction {
+            program_id: bonding_curve::id(),
+            accounts: bonding_curve::accounts::CheckInvariants {
+                pool: self.pool,
+                token_mint: self.mint,
+                pool_sol_vault: self.vault,
+            }
+            .to_account_metas(None),
+            data: bonding_curve::instruction::CheckInvariants {}.data(),
+        }
+    }
+
+    /// Runs check_invariants and returns its report.
+    async fn check_invariants(&mut self) -> InvariantsChecked {
+        let sent = self.send(vec![self.check_invariants_ix()], &[]).await.unwrap();
+        InvariantsChecked::try_from_slice(&sent.return_data).unwrap()
+    }
+
+    async fn curve_view(&mut self) -> CurveView {
+        let ix = Instruction {
+            program_id: bonding_curve::id(),
+            accounts: bonding_curve::accounts::GetCurveView { pool: self.pool, token_mint: self.mint }
+                .to_account_metas(None),
+            data: bonding_curve::instruction::GetCurveView {}.data(),
+        };
+        let sent = self.send(vec![ix], &[]).await.unwrap();
+        CurveView::try_from_slice(&sent.return_data).unwrap()
+    }
+
+    fn upgrade_pool_account_ix(&self) -> Instruction {
+        Instruction {
+            program_id: bonding_curve::id(),
+            accounts: bonding_curve::accounts::UpgradePoolAccount {
+                pool: self.pool,
+                token_mint: self.mint,
+                pool_sol_vault: self.vault,
+                migration_escrow: self.escrow,
+                escrow_token_account: None,
+                dex_configuration_account: self.cfg,
+                authority: self.user.pubkey(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: bonding_curve::instruction::UpgradePoolAccount { amm_program: Some(mock_amm::id()) }.data(),
+        }
+    }
+
+    fn start_migration_ix(&self) -> Instruction {
+        self.start_migration_ix_by(self.user.pubkey())
+    }
+
+    fn start_migration_ix_by(&self, caller: Pubkey) -> Instruction {
+        Instruction {
+            program_id: bonding_curve::id(),
+            accounts: bonding_curve::accounts::StartMigration {
+                dex_configuration_account: self.cfg,
+                pool: self.pool,
+                token_mint: self.mint,
+                pool_token_account: self.pool_ata,
+                pool_sol_vault: self.vault,
+                migration_escrow: self.escrow,
+                escrow_token_account: self.escrow_ata,
+                caller,
+                token_program: spl_token::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: bonding_curve::instruction::StartMigration {}.data(),
+        }
+    }
+
+    /// finalize_migration into the mock CPMM; also returns the AMM pool id.
+    fn finalize_migration_ix(&self) -> (Instruction, Pubkey) {
+        self.finalize_migration_ix_by(self.user.pubkey())
+    }
+
+    fn finalize_migration_ix_by(&self, caller: Pubkey) -> (Instruction, Pubkey) {
+        let amm = mock_amm::id();
+        let (t0, t1) = if self.mint < native_mint::ID { (self.mint, native_mint::ID) } else { (native_mint::ID, self.mint) };
+        let amm_pda = |seeds: &[&[u8]]| Pubkey::find_program_address(seeds, &amm).0;
+        let amm_config = amm_pda(&[b"amm_config", &0u16.to_be_bytes()]);
+        let amm_pool = amm_pda(&[b"pool", amm_config.as_ref(), t0.as_ref(), t1.as_ref()]);
+        let amm_lp_mint = amm_pda(&[b"pool_lp_mint", amm_pool.as_ref()]);
+        let lp_escrow = self.lp_escrow();
+        let ix = Instruction {
+            program_id: bonding_curve::id(),
+            accounts: bonding_curve::accounts::FinalizeMigration {
+                dex_configuration_account: self.cfg,
+                pool: self.pool,
+                token_mint: self.mint,
+                wsol_mint: native_mint::ID,
+                treasury_pda: self.treasury,
+                treasury_token_account: self.treasury_ata,
+                migration_escrow: self.escrow,
+                escrow_token_account: self.escrow_ata,
+                escrow_wsol_account: get_associated_token_address(&self.escrow, &native_mint::ID),
+                caller,
+                protocol_fee_vault: protocol_fee_vault(),
+                creator: self.user.pubkey(),
+                pool_sol_vault: self.vault,
+                amm_program: amm,
+                amm_pool,
+                amm_lp_mint,
+                escrow_lp_account: get_associated_token_address(&self.escrow, &amm_lp_mint),
+                lp_escrow,
+                lp_escrow_token_account: get_associated_token_address(&lp_escrow, &amm_lp_mint),
+                token_program: spl_token::ID,
+                lp_token_program: spl_token::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+                system_program: system_program::ID,
+                rent: Rent::id(),
+            }
+            .to_account_metas(None)
+            .into_iter()
+            // CPMM adapter's remaining_accounts
+            .chain([
+                AccountMeta::new_readonly(amm_config, false),
+                AccountMeta::new_readonly(amm_pda(&[b"vault_and_lp_mint_auth_seed"]), false),
+                AccountMeta::new(amm_pda(&[b"pool_vault", amm_pool.as_ref(), t0.as_ref()]), false),
+                AccountMeta::new(amm_pda(&[b"pool_vault", amm_pool.as_ref(), t1.as_ref()]), false),
+                AccountMeta::new(Keypair::new().pubkey(), false), // create_pool_fee
+                AccountMeta::new(amm_pda(&[b"observation", amm_pool.as_ref()]), false),
+            ])
+            .collect(),
+            data: bonding_curve::instruction::FinalizeMigration {}.data(),
+        };
+        (ix, amm_pool)
+    }
+
+    fn lp_escrow(&self) -> Pubkey {
+        Pubkey::find_program_address(
+            &[LiquidityPool::LP_ESCROW_PREFIX.as_bytes(), self.mint.as_ref()],
+            &bonding_curve::id(),
+        )
+        .0
+    }
+
+    /// Fills the cap, then start + finalize into the mock CPMM; returns the
+    /// AMM pool and its LP mint.
+    async fn graduate(&mut self) -> (Pubkey, Pubkey) {
+        self.send(vec![self.buy_ix(100 * self.one)], &[]).await.unwrap();
+        self.send(vec![self.start_migration_ix()], &[]).await.unwrap();
+        let (finalize, amm_pool) = self.finalize_migration_ix();
+        self.send(vec![finalize], &[]).await.unwrap();
+        let lp_mint = Pubkey::find_program_address(&[b"pool_lp_mint", amm_pool.as_ref()], &mock_amm::id()).0;
+        (amm_pool, lp_mint)
+    }
+
+    fn close_graduated_pool_ix(&self) -> Instruction {
+        Instruction {
+            program_id: bonding_curve::id(),
+            accounts: bonding_curve::accounts::CloseGraduatedPool {
+                pool: self.pool,
+                token_mint: self.mint,
+                pool_token_account: self.pool_ata,
+                pool_sol_vault: self.vault,
+                migration_escrow: self.escrow,
+                protocol_fee_vault: protocol_fee_vault(),
+                creator: self.user.pubkey(),
+                lp_escrow_token_account: None,
+                caller: self.user.pubkey(),
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: bonding_curve::instruction::CloseGraduatedPool {}.data(),
+        }
+    }
+
+    fn release_lp_ix(&self, lp_mint: Pubkey, recipient: Pubkey) -> Instruction {
+        Instruction {
+            program_id: bonding_curve::id(),
+            accounts: bonding_curve::accounts::ReleaseLp {
+                pool: self.pool,
+                token_mint: self.mint,
+                lp_mint,
+                lp_escrow: self.lp_escrow(),
+                lp_escrow_token_account: get_associated_token_address(&self.lp_escrow(), &lp_mint),
+                recipient_lp_account: get_associated_token_address(&recipient, &lp_mint),
+                recipient,
+                system_program: system_program::ID,
+                lp_token_program: spl_token::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+            }
+            .to_account_metas(None),
+            data: bonding_curve::instruction::ReleaseLp {}.data(),
+        }
+    }
+
+    async fn supply(&mut self, mint: Pubkey) -> u64 {
+        let acc = self.ctx.banks_client.get_account(mint).await.unwrap().unwrap();
+        SplMint::unpack(&acc.data).unwrap().supply
+    }
+
+    /// A fresh keypair holding `lamports`.
+    async fn funded(&mut self, lamports: u64) -> Keypair {
+        let kp = Keypair::new();
+        self.send(vec![system_instruction::transfer(&self.user.pubkey(), &kp.pubkey(), lamports)], &[])
+            .await
+            .unwrap();
+        kp
+    }
+
+    fn abort_migration_ix(&self) -> Instruction {
+        self.abort_migration_ix_by(self.user.pubkey())
+    }
+
+    fn abort_migration_ix_by(&self, authority: Pubkey) -> Instruction {
+        Instruction {
+            program_id: bonding_curve::id(),
+            accounts: bonding_curve::accounts::AbortMigration {
+                dex_configuration_account: self.cfg,
+                pool: self.pool,
+                token_mint: self.mint,
+                pool_token_account: self.pool_ata,
+                pool_sol_vault: self.vault,
+                migration_escrow: self.escrow,
+                escrow_token_account: self.escrow_ata,
+                authority,
+                token_program: spl_token::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: bonding_curve::instruction::AbortMigration {}.data(),
+        }
+    }
+}
+
+/// Custom error code of a failed transaction.
+fn custom_error(err: BanksClientError) -> u32 {
+    match err {
+        BanksClientError::TransactionError(TransactionError::InstructionError(
+            _,
+            InstructionError::Custom(code),
+        )) => code,
+        other => panic!("expected a custom program error, got {other:?}"),
+    }
+}
+
+fn protocol_fee_vault() -> Pubkey {
+    Pubkey::find_program_address(&[LiquidityPool::PROTOCOL_FEE_PREFIX.as_bytes()], &bonding_curve::id()).0
+}
+
+fn code(err: CustomError) -> u32 {
+    anchor_lang::error::ERROR_CODE_OFFSET + err as u32
+}
+
+/// Abort puts back exactly what start_migration escrowed; the curve then
+/// trades on and still reconciles.
+#[tokio::test]
+async fn test_abort_migration_restores_a_tradeable_curve() {
+    let mut c = Curve::new(pool_args()).await;
+    let one = c.one;
+
+    c.send(vec![c.buy_ix(100 * one)], &[]).await.unwrap();
+    let capped = c.state().await;
+    assert!(capped.phase == PoolPhase::Migrating);
+
+    c.send(vec![c.start_migration_ix()], &[]).await.unwrap();
+    let started = c.state().await;
+    assert_eq!(started.reserve_sol, 0);
+    assert_eq!(started.reserve_snapshot_sol, capped.reserve_sol);
+    assert_eq!(c.lamports(c.escrow).await, capped.reserve_sol);
+
+    // only after the abort delay
+    let err = c.send(vec![c.abort_migration_ix()], &[]).await.unwrap_err();
+    assert_eq!(custom_error(err), code(CustomError::MigrationAbortLocked));
+    c.warp(bonding_curve::consts::MIGRATION_ABORT_DELAY_SLOTS + 1).await;
+    c.send(vec![c.abort_migration_ix()], &[]).await.unwrap();
+
+    let aborted = c.state().await;
+    assert!(aborted.phase == PoolPhase::Active);
+    assert_eq!(aborted.reserve_sol, capped.reserve_sol);
+    assert_eq!(aborted.reserve_sol, aborted.total_sol_in);
+    assert_eq!(aborted.reserve_token, 0);
+    assert_eq!((aborted.reserve_snapshot_token, aborted.reserve_snapshot_sol), (0, 0));
+    assert_eq!(c.lamports(c.escrow).await, 0);
+
+    // sell some back, buy some again: reserves track the trades
+    c.send(vec![c.sell_ix(100_000_000 * one)], &[]).await.unwrap();
+    c.send(vec![c.buy_ix(one)], &[]).await.unwrap();
+    let report = c.check_invariants().await;
+    let traded = c.state().await;
+    assert!(report.ok);
+    assert_eq!(report.reserve_drift, 0);
+    assert_eq!(traded.reserve_token, 800_000_000 * one - traded.total_sold);
+    assert_eq!(c.tokens(c.pool_ata).await, traded.reserve_token);
+}
+
+/// The config admin may abort too, even before start_migration has created
+/// the escrow ATA; a stranger may not. The abort restarts the grace clock, so
+/// anyone may start migration again once it has run out.
+#[tokio::test]
+async fn test_admin_aborts_a_migration_that_never_started() {
+    use bonding_curve::consts::{MIGRATION_ABORT_DELAY_SLOTS, MIGRATION_GRACE_SLOTS};
+    let mut c = Curve::new(pool_args()).await;
+    let admin = c.funded(1_000_000_000).await;
+    let stranger = c.funded(1_000_000_000).await;
+    c.hand_admin_to(&admin).await;
+
+    c.send(vec![c.buy_ix(100 * c.one)], &[]).await.unwrap();
+    assert!(c.state().await.phase == PoolPhase::Migrating);
+    assert!(c.ctx.banks_client.get_account(c.escrow_ata).await.unwrap().is_none());
+    c.warp(MIGRATION_ABORT_DELAY_SLOTS + 1).await;
+
+    let err = c.send(vec![c.abort_migration_ix_by(stranger.pubkey())], &[&stranger]).await.unwrap_err();
+    assert_eq!(custom_error(err), code(CustomError::Unauthorized));
+    c.send(vec![c.abort_migration_ix_by(admin.pubkey())], &[&admin]).await.unwrap();
+
+    let aborted = c.state().await;
+    assert!(aborted.phase == PoolPhase::Active);
+    assert_eq!(aborted.reserve_sol, aborted.total_sol_in);
+    let abort_slot = aborted.cap_reached_slot.unwrap();
+
+    let err = c.send(vec![c.start_migration_ix_by(stranger.pubkey())], &[&stranger]).await.unwrap_err();
+    assert_eq!(custom_error(err), code(CustomError::Unauthorized));
+    c.warp(MIGRATION_GRACE_SLOTS + 1).await;
+    c.send(vec![c.start_migration_ix_by(stranger.pubkey())], &[&stranger]).await.unwrap();
+    let restarted = c.state().await;
+    assert!(restarted.phase == PoolPhase::Migrating);
+    assert!(restarted.cap_reached_slot.unwrap() > abort_slot);
+}
+
+/// A healthy curve reports no drift; a vault short of reserve_sol trips
+/// ReserveMismatch, and reserves that no longer cover selling every sold
+/// token back trip CurveInsolvent. Trades refuse both.
+#[tokio::test]
+async fn test_invariants_trip_on_a_short_vault() {
+    let mut c = Curve::new(pool_args()).await;
+    let one = c.one;
+    c.send(vec![c.buy_ix(10 * one)], &[]).await.unwrap();
+
+    let report = c.check_invariants().await;
+    assert!(report.ok);
+    assert_eq!(report.reserve_drift, 0);
+
+    // lamports leave the vault behind the pool's back
+    let short = 1_000_000u64;
+    let vault = c.lamports(c.vault).await;
+    c.set_lamports(c.vault, vault - short).await;
+    let report = c.check_invariants().await;
+    assert!(!report.ok);
+    assert_eq!(report.reserve_drift, -(short as i64));
+    let err = c.send(vec![c.buy_ix(one)], &[]).await.unwrap_err();
+    assert_eq!(custom_error(err), code(CustomError::ReserveMismatch));
+
+    // booked as gone too: the reserves reconcile, but no longer back the sold tokens
+    let mut pool = c.state().await;
+    pool.reserve_sol -= short;
+    c.set_state(&pool).await;
+    let report = c.check_invariants().await;
+    assert!(!report.ok);
+    assert_eq!(report.reserve_drift, 0);
+    assert!(report.required_sol > report.vault_lamports);
+    let err = c.send(vec![c.sell_ix(one)], &[]).await.unwrap_err();
+    assert_eq!(custom_error(err), code(CustomError::CurveInsolvent));
+}
+
+/// Compute for the widest buy there is: one trade across a MAX_TIERS schedule
+/// up to the cap, invariant check included. Only meaningful against the SBF
+/// build (`SBF_OUT_DIR=target/deploy`); natively only CPIs are metered.
+#[tokio::test]
+async fn test_max_tier_buy_compute() {
+    let tiers = (0..bonding_curve::consts::MAX_TIERS)
+        .map(|_| Tier { sol_width: 10_000_000_000, token_alloc: 100_000_000 })
+        .collect();
+    let mut c = Curve::new(bonding_curve::instruction::CreatePool { tiers, ..pool_args() }).await;
+
+    let sent = c.send(vec![c.buy_ix(100 * c.one)], &[]).await.unwrap();
+    assert!(c.state().await.phase == PoolPhase::Migrating);
+    println!("max-tier buy: {} compute units", sent.compute_units);
+    if std::env::var("SBF_OUT_DIR").is_ok() {
+        assert!(sent.compute_units < 200_000);
+    }
+}
+
+/// Eight 100M-token tiers, 10 SOL wide.
+fn eight_tiers() -> Vec<Tier> {
+    (0..8).map(|_| Tier { sol_width: 10_000_000_000, token_alloc: 100_000_000 }).collect()
+}
+
+/// eight_tiers() paying 1M whole tokens of bonus per tier.
+fn bonus_pool_args(mode: BonusMode) -> bonding_curve::instruction::CreatePool {
+    bonding_curve::instruction::CreatePool {
+        tiers: eight_tiers(),
+        bonus: Some(TierBonusConfig { per_tier: 1_000_000, mode }),
+        ..pool_args()
+    }
+}
+
+/// The buy that first completes a tier is paid once; selling back below the
+/// tier end and crossing it again pays nothing more.
+#[tokio::test]
+async fn test_crossing_bonus_is_paid_once_per_tier() {
+    let mut c = Curve::new(bonus_pool_args(BonusMode::CrossingBuyer)).await;
+    let one = c.one;
+    let bonus = 1_000_000 * one;
+
+    c.send(vec![c.buy_ix(15 * one)], &[]).await.unwrap();
+    let pool = c.state().await;
+    assert_eq!(pool.bonus_paid_tiers, 0b1);
+    assert_eq!(pool.bonus_paid, bonus);
+    assert_eq!(c.tokens(c.user_ata).await, pool.total_sold + bonus);
+    let treasury = c.tokens(c.treasury_ata).await;
+
+    // back to zero, then across tier 0 again
+    c.send(vec![c.sell_ix(pool.total_sold)], &[]).await.unwrap();
+    assert_eq!(c.state().await.total_sold, 0);
+    c.send(vec![c.buy_ix(15 * one)], &[]).await.unwrap();
+
+    let pool = c.state().await;
+    assert_eq!(pool.bonus_paid_tiers, 0b1);
+    assert_eq!(pool.bonus_paid, bonus);
+    assert_eq!(c.tokens(c.treasury_ata).await, treasury);
+}
+
+/// Sells give pro-rata weight back while a tier is open; once completed its
+/// weights are frozen, so a sell-back and re-cross neither adds weight nor a
+/// second bonus, and the claim pays exactly the tier's bonus.
+#[tokio::test]
+async fn test_pro_rata_weights_follow_sells_and_freeze_on_completion() {
+    let mut c = Curve::new(bonus_pool_args(BonusMode::ProRata)).await;
+    let one = c.one;
+    let bonus = 1_000_000 * one;
+
+    c.send(vec![c.buy_ix(5 * one)], &[]).await.unwrap();
+    let bought = c.state().await.total_sold;
+    c.send(vec![c.sell_ix(bought / 2)], &[]).await.unwrap();
+    let pool = c.state().await;
+    assert_eq!(c.participation().await.bought[0], pool.total_sold);
+    assert_eq!(pool.tier_bought[0], pool.total_sold);
+
+    let err = c.send(vec![c.claim_tier_bonus_ix(0)], &[]).await.unwrap_err();
+    assert_eq!(custom_error(err), code(CustomError::TierNotCompleted));
+
+    // complete tier 0, sell all of it back and cross it again
+    c.send(vec![c.buy_ix(15 * one)], &[]).await.unwrap();
+    let tier_end = 100_000_000 * one;
+    assert_eq!(c.state().await.tier_bought[0], tier_end);
+    let sold = c.state().await.total_sold;
+    c.send(vec![c.sell_ix(sold)], &[]).await.unwrap();
+    c.send(vec![c.buy_ix(15 * one)], &[]).await.unwrap();
+
+    let pool = c.state().await;
+    assert_eq!(pool.bonus_paid_tiers, 0b1);
+    assert_eq!(pool.tier_bought[0], tier_end);
+    assert_eq!(c.participation().await.bought[0], tier_end);
+
+    // sold back below the end, tier 0 still pays its bonus, once
+    c.send(vec![c.sell_ix(pool.total_sold)], &[]).await.unwrap();
+    let before = c.tokens(c.user_ata).await;
+    c.send(vec![c.claim_tier_bonus_ix(0)], &[]).await.unwrap();
+    assert_eq!(c.tokens(c.user_ata).await - before, bonus);
+    assert_eq!(c.state().await.bonus_paid, bonus);
+    let err = c.send(vec![c.claim_tier_bonus_ix(0)], &[]).await.unwrap_err();
+    assert_eq!(custom_error(err), code(CustomError::BonusAlreadyClaimed));
+}
+
+/// The emergency path waits out its timelock (a cancel resets it), pays the
+/// admin nothing and leaves the pool Expired, where holders redeem the vault.
+#[tokio::test]
+async fn test_emergency_withdraw_expires_the_pool_for_holders() {
+    let mut c = Curve::new(pool_args()).await;
+    let one = c.one;
+    let admin = Keypair::new();
+    let admin_pk = admin.pubkey();
+    c.hand_admin_to(&admin).await;
+    let create_ata = ata_ix::create_associated_token_account(&c.user.pubkey(), &admin_pk, &c.mint, &spl_token::ID);
+    c.send(vec![create_ata, c.buy_ix(10 * one)], &[]).await.unwrap();
+    let sold = c.state().await.total_sold;
+
+    // requested, then called off: the timelock passing alone isn't enough
+    c.send(vec![c.request_emergency_withdraw_ix(admin_pk)], &[&admin]).await.unwrap();
+    let err = c.send(vec![c.remove_liquidity_ix(admin_pk)], &[&admin]).await.unwrap_err();
+    assert_eq!(custom_error(err), code(CustomError::EmergencyWithdrawLocked));
+    c.send(vec![c.cancel_emergency_withdraw_ix(admin_pk)], &[&admin]).await.unwrap();
+    assert_eq!(c.state().await.emergency_withdraw_slot, 0);
+    c.warp(bonding_curve::consts::EMERGENCY_WITHDRAW_DELAY_SLOTS + 1).await;
+    let err = c.send(vec![c.remove_liquidity_ix(admin_pk)], &[&admin]).await.unwrap_err();
+    assert_eq!(custom_error(err), code(CustomError::EmergencyWithdrawLocked));
+
+    // requested again and waited out
+    c.send(vec![c.request_emergency_withdraw_ix(admin_pk)], &[&admin]).await.unwrap();
+    c.warp(bonding_curve::consts::EMERGENCY_WITHDRAW_DELAY_SLOTS + 1).await;
+    let vault = c.lamports(c.vault).await;
+    let admin_lamports = c.lamports(admin_pk).await;
+    c.send(vec![c.remove_liquidity_ix(admin_pk)], &[&admin]).await.unwrap();
+
+    let pool = c.state().await;
+    assert!(pool.phase == PoolPhase::Expired);
+    assert_eq!(pool.emergency_withdraw_slot, 0);
+    assert_eq!(pool.reserve_snapshot_token, sold);
+    assert_eq!(pool.reserve_snapshot_sol, pool.reserve_sol);
+    assert_eq!(c.lamports(c.vault).await, vault);
+    assert_eq!(c.lamports(admin_pk).await, admin_lamports);
+    assert_eq!(c.tokens(get_associated_token_address(&admin_pk, &c.mint)).await, 0);
+
+    // terminal: no trading, no second request; holders take the curve SOL
+    let err = c.send(vec![c.buy_ix(one)], &[]).await.unwrap_err();
+    assert_eq!(custom_error(err), code(CustomError::InvalidAmount)); // buy's not-Active error
+    let err = c.send(vec![c.request_emergency_withdraw_ix(admin_pk)], &[&admin]).await.unwrap_err();
+    assert_eq!(custom_error(err), code(CustomError::BadPhase));
+    let user_lamports = c.lamports(c.user.pubkey()).await;
+    c.send(vec![c.redeem_ix(sold)], &[]).await.unwrap();
+    assert_eq!(c.lamports(c.user.pubkey()).await - user_lamports, pool.reserve_sol - 5000);
+    assert_eq!(c.state().await.reserve_sol, 0);
+}
+
+/// Donations to an Active pool are swept without touching the reserves; a
+/// Migrating pool refuses the sweep.
+#[tokio::test]
+async fn test_sweep_donations_only_takes_surplus_in_open_phases() {
+    let mut c = Curve::new(pool_args()).await;
+    let one = c.one;
+    let user_pk = c.user.pubkey();
+    c.send(vec![c.buy_ix(10 * one)], &[]).await.unwrap();
+
+    let donated = 1_000 * one;
+    let ixs = vec![
+        spl_token::instruction::transfer(&spl_token::ID, &c.user_ata, &c.pool_ata, &user_pk, &[], donated).unwrap(),
+        system_instruction::transfer(&user_pk, &c.vault, one),
+        system_instruction::transfer(&user_pk, &protocol_fee_vault(), one), // keep the fee PDA rent-exempt
+    ];
+    c.send(ixs, &[]).await.unwrap();
+    let before = c.state().await;
+    let pool_tokens = c.tokens(c.pool_ata).await;
+    let fees = c.lamports(protocol_fee_vault()).await;
+
+    c.send(vec![c.sweep_donations_ix()], &[]).await.unwrap();
+    let after = c.state().await;
+    assert_eq!(c.tokens(c.pool_ata).await, pool_tokens - donated);
+    assert_eq!(c.lamports(protocol_fee_vault()).await, fees + one);
+    assert_eq!((after.reserve_token, after.reserve_sol), (before.reserve_token, before.reserve_sol));
+    assert_eq!(after.total_sold, before.total_sold);
+    assert_eq!(c.check_invariants().await.reserve_drift, 0);
+
+    // capped: the reserves are on their way to the AMM
+    c.send(vec![c.buy_ix(100 * one)], &[]).await.unwrap();
+    assert!(c.state().await.phase == PoolPhase::Migrating);
+    let err = c.send(vec![c.sweep_donations_ix()], &[]).await.unwrap_err();
+    assert_eq!(custom_error(err), code(CustomError::BadPhase));
+}
+
+/// Only the guardian flips either switch; while one is on, trading stops with
+/// the matching error, and turning it off restores trading.
+#[tokio::test]
+async fn test_guardian_pauses_and_unpauses_trading() {
+    let mut c = Curve::new(pool_args()).await;
+    let one = c.one;
+    let user_pk = c.user.pubkey();
+    let guardian = c.funded(one).await;
+    c.set_guardian(guardian.pubkey()).await;
+    c.send(vec![c.buy_ix(10 * one)], &[]).await.unwrap();
+
+    // the admin is not the guardian
+    let err = c.send(vec![c.set_paused_ix(user_pk, true)], &[]).await.unwrap_err();
+    assert_eq!(custom_error(err), code(CustomError::Unauthorized));
+    let err = c.send(vec![c.set_pool_paused_ix(user_pk, true)], &[]).await.unwrap_err();
+    assert_eq!(custom_error(err), code(CustomError::Unauthorized));
+
+    // protocol-wide
+    c.send(vec![c.set_paused_ix(guardian.pubkey(), true)], &[&guardian]).await.unwrap();
+    let err = c.send(vec![c.buy_ix(one)], &[]).await.unwrap_err();
+    assert_eq!(custom_error(err), code(CustomError::ProtocolPaused));
+    let err = c.send(vec![c.sell_ix(one)], &[]).await.unwrap_err();
+    assert_eq!(custom_error(err), code(CustomError::ProtocolPaused));
+    c.send(vec![c.set_paused_ix(guardian.pubkey(), false)], &[&guardian]).await.unwrap();
+    c.send(vec![c.buy_ix(one)], &[]).await.unwrap();
+
+    // this pool only
+    c.send(vec![c.set_pool_paused_ix(guardian.pubkey(), true)], &[&guardian]).await.unwrap();
+    assert!(c.state().await.paused);
+    let err = c.send(vec![c.buy_ix(one)], &[]).await.unwrap_err();
+    assert_eq!(custom_error(err), code(CustomError::PoolPaused));
+    let err = c.send(vec![c.sell_ix(one)], &[]).await.unwrap_err();
+    assert_eq!(custom_error(err), code(CustomError::PoolPaused));
+    c.send(vec![c.set_pool_paused_ix(guardian.pubkey(), false)], &[&guardian]).await.unwrap();
+    c.send(vec![c.buy_ix(one)], &[]).await.unwrap();
+    c.send(vec![c.sell_ix(one)], &[]).await.unwrap();
+    assert_eq!(c.check_invariants().await.reserve_drift, 0);
+}
+
+/// launch_token leaves a fixed-supply mint create_pool would accept (no
+/// authorities, 800M to the pool, 200M to the treasury), mutable metadata
+/// under the creator, and a tradeable curve, with or without a first buy.
+#[tokio::test]
+async fn test_launch_token_with_and_without_initial_buy() {
+    for initial_buy in [None, Some(5_000_000_000)] {
+        let mut c = Curve::launch(pool_args(), initial_buy).await.unwrap();
+        let one = c.one;
+
+        let mint = c.ctx.banks_client.get_account(c.mint).await.unwrap().unwrap();
+        let mint = SplMint::unpack(&mint.data).unwrap();
+        assert_eq!(mint.mint_authority, COption::None);
+        assert_eq!(mint.freeze_authority, COption::None);
+        assert_eq!(mint.decimals, 9);
+        assert_eq!(mint.supply, 1_000_000_000 * one);
+        assert_eq!(c.tokens(c.treasury_ata).await, 200_000_000 * one);
+
+        let pool = c.state().await;
+        let bought = if initial_buy.is_some() { c.tokens(c.user_ata).await } else { 0 };
+        assert_eq!(initial_buy.is_some(), bought > 0);
+        assert_eq!(pool.total_sold, bought);
+        assert_eq!(c.tokens(c.pool_ata).await, 800_000_000 * one - bought);
+        assert_eq!(pool.reserve_token, 800_000_000 * one - bought);
+        assert_eq!(pool.reserve_sol, pool.total_sol_in);
+        assert_eq!(initial_buy.is_some(), pool.reserve_sol > 0);
+        let vault_floor = Rent::default().minimum_balance(0) + pool.seed_sol;
+        assert_eq!(c.lamports(c.vault).await, vault_floor + pool.reserve_sol);
+        assert!(pool.phase == PoolPhase::Active);
+        assert_eq!(pool.creator, c.user.pubkey());
+
+        // DataV2 with no creators / collection / uses, is_mutable, no collection details
+        let metadata = c.ctx.banks_client.get_account(c.metadata()).await.unwrap().unwrap();
+        assert!(metadata.data.ends_with(&[0, 0, 0, 1, 0]));
+        assert!(metadata.data.windows(5).any(|w| w == b"Curve"));
+
+        let report = c.check_invariants().await;
+        assert!(report.ok);
+        assert_eq!(report.reserve_drift, 0);
+        c.send(vec![c.buy_ix(one)], &[]).await.unwrap();
+    }
+}
+
+/// A first buy needs the creator's ATA, and on a vesting, pro-rata bonus pool
+/// the escrow and participation accounts too; with them it books exactly
+/// like a buy.
+#[tokio::test]
+async fn test_launch_token_initial_buy_accounts() {
+    const FIRST_BUY: u64 = 5_000_000_000;
+    let err = Curve::launch_with(pool_args(), Some(FIRST_BUY), |_, a| a.user_token_account = None)
+        .await
+        .err()
+        .unwrap();
+    assert_eq!(custom_error(err), code(CustomError::CreatorTokenAccountMissing));
+
+    let args = || bonding_curve::instruction::CreatePool {
+        vesting: Some(VestingConfig { vested_tiers: 1, duration: 1_000 }),
+        ..bonus_pool_args(BonusMode::ProRata)
+    };
+    let with_vesting = |c: &Curve, a: &mut bonding_curve::accounts::LaunchToken| {
+        let escrow = c.pda(VestingEscrow::SEED_PREFIX);
+        a.vesting_escrow = Some(escrow);
+        a.vesting_token_account = Some(get_associated_token_address(&escrow, &c.mint));
+    };
+    let err = Curve::launch_with(args(), Some(FIRST_BUY), with_vesting).await.err().unwrap();
+    assert_eq!(custom_error(err), code(CustomError::BonusAccountsMissing));
+    let err = Curve::launch_with(args(), Some(FIRST_BUY), |c, a| {
+        a.tier_participation = Some(c.pda(TierParticipation::SEED_PREFIX))
+    })
+    .await
+    .err()
+    .unwrap();
+    assert_eq!(custom_error(err), code(CustomError::VestingAccountsMissing));
+
+    let mut c = Curve::launch_with(args(), Some(FIRST_BUY), |c, a| {
+        with_vesting(c, a);
+        a.tier_participation = Some(c.pda(TierParticipation::SEED_PREFIX));
+    })
+    .await
+    .unwrap();
+    let one = c.one;
+    let pool = c.state().await;
+    let bought = pool.total_sold;
+    assert!(bought > 0);
+    assert_eq!(c.tokens(c.pool_ata).await, 800_000_000 * one - bought);
+    assert_eq!(pool.reserve_token, 800_000_000 * one - bought);
+    assert_eq!(pool.reserve_sol, pool.total_sol_in);
+    // all of it inside vested tier 0: escrowed, nothing to the creator yet
+    let escrow = c.pda(VestingEscrow::SEED_PREFIX);
+    assert_eq!(c.tokens(get_associated_token_address(&escrow, &c.mint)).await, bought);
+    assert_eq!(c.tokens(c.user_ata).await, 0);
+    assert_eq!(pool.vesting_unclaimed, bought);
+    assert_eq!(pool.tier_bought[0], bought);
+    let participation = c.participation().await;
+    assert_eq!((participation.owner, participation.bought[0]), (c.user.pubkey(), bought));
+    assert!(c.check_invariants().await.ok);
+}
+
+/// With the ratchet on, sells may unwind the open tier down to the end of
+/// the last completed one, and not a base unit further.
+#[tokio::test]
+async fn test_tier_ratchet_floors_sells_at_the_completed_tier() {
+    let args = bonding_curve::instruction::CreatePool { tiers: eight_tiers(), tier_ratchet: true, ..pool_args() };
+    let mut c = Curve::new(args).await;
+    let one = c.one;
+    let tier_end = 100_000_000 * one;
+
+    c.send(vec![c.buy_ix(15 * one)], &[]).await.unwrap();
+    let above = c.state().await.total_sold - tier_end;
+    assert!(above > 0);
+
+    let err = c.send(vec![c.sell_ix(above + 1)], &[]).await.unwrap_err();
+    assert_eq!(custom_error(err), code(CustomError::TierRatchetFloor));
+    c.send(vec![c.sell_ix(above)], &[]).await.unwrap();
+    assert_eq!(c.state().await.total_sold, tier_end);
+    let err = c.send(vec![c.sell_ix(1)], &[]).await.unwrap_err();
+    assert_eq!(custom_error(err), code(CustomError::TierRatchetFloor));
+}
+
+/// Buys inside a vested tier land in the buyer's escrow and come out
+/// linearly over the creator's duration, never ahead of it.
+#[tokio::test]
+async fn test_vested_tier_buys_release_linearly() {
+    const DURATION: i64 = 1_000;
+    let args = bonding_curve::instruction::CreatePool {
+        tiers: eight_tiers(),
+        vesting: Some(VestingConfig { vested_tiers: 1, duration: DURATION }),
+        ..pool_args()
+    };
+    let mut c = Curve::new(args).await;
+    let one = c.one;
+    let escrow_ata = get_associated_token_address(&c.pda(VestingEscrow::SEED_PREFIX), &c.mint);
+
+    c.send(vec![c.buy_ix(5 * one)], &[]).await.unwrap();
+    let bought = c.state().await.total_sold;
+    assert_eq!(c.tokens(escrow_ata).await, bought);
+    assert_eq!(c.tokens(c.user_ata).await, 0);
+
+    let err = c.send(vec![c.claim_vested_ix()], &[]).await.unwrap_err();
+    assert_eq!(custom_error(err), code(CustomError::NothingToClaim));
+
+    // Half the duration in: about half is out, the rest stays locked.
+    c.warp(DURATION as u64 * 5 / 2 / 2).await;
+    c.send(vec![c.claim_vested_ix()], &[]).await.unwrap();
+    let half = c.tokens(c.user_ata).await;
+    assert!(half > 0 && half <= bought / 2, "{half} of {bought}");
+    assert_eq!(c.tokens(escrow_ata).await, bought - half);
+
+    c.warp(DURATION as u64 * 5 / 2).await;
+    c.send(vec![c.claim_vested_ix()], &[]).await.unwrap();
+    assert_eq!(c.tokens(c.user_ata).await, bought);
+    assert_eq!(c.tokens(escrow_ata).await, 0);
+    let err = c.send(vec![c.claim_vested_ix()], &[]).await.unwrap_err();
+    assert_eq!(custom_error(err), code(CustomError::NothingToClaim));
+}
+
+/// A graduated pool stays a full LiquidityPool while claims still read it:
+/// close waits until every vested token and tier bonus is out, then also
+/// sweeps whatever reached the migration escrow after finalize.
+#[tokio::test]
+async fn test_close_graduated_pool_waits_for_outstanding_claims() {
+    const DURATION: i64 = 1_000;
+    let args = bonding_curve::instruction::CreatePool {
+        vesting: Some(VestingConfig { vested_tiers: 1, duration: DURATION }),
+        ..bonus_pool_args(BonusMode::ProRata)
+    };
+    let mut c = Curve::new(args).await;
+    c.graduate().await;
+
+    let err = c.send(vec![c.close_graduated_pool_ix()], &[]).await.unwrap_err();
+    assert_eq!(custom_error(err), code(CustomError::ClaimsOutstanding));
+    for tier in 0..8 {
+        c.send(vec![c.claim_tier_bonus_ix(tier)], &[]).await.unwrap();
+    }
+    assert_eq!(c.state().await.bonus_pending(9), 0);
+    let err = c.send(vec![c.close_graduated_pool_ix()], &[]).await.unwrap_err();
+    assert_eq!(custom_error(err), code(CustomError::ClaimsOutstanding));
+
+    c.warp(DURATION as u64 * 5 / 2 + 1).await;
+    c.send(vec![c.claim_vested_ix()], &[]).await.unwrap();
+    assert_eq!(c.state().await.vesting_unclaimed, 0);
+
+    c.send(vec![system_instruction::transfer(&c.user.pubkey(), &c.escrow, 1_000_000)], &[])
+        .await
+        .unwrap();
+    let fees = c.lamports(protocol_fee_vault()).await;
+    c.send(vec![c.close_graduated_pool_ix()], &[]).await.unwrap();
+    assert_eq!(c.lamports(c.escrow).await, 0);
+    assert_eq!(c.lamports(protocol_fee_vault()).await, fees + 1_000_000);
+    let account = c.ctx.banks_client.get_account(c.pool).await.unwrap().unwrap();
+    assert_eq!(account.data.len(), GraduatedPool::ACCOUNT_SIZE);
+}
+
+/// The view reports where the last trade left the pool: tier, progress
+/// inside it, what's left and the price at every boundary.
+#[tokio::test]
+async fn test_curve_view_tracks_tier_position() {
+    // Widening tiers (5..=12 SOL per 100M tokens), so each one is pricier.
+    let tiers = (0..8).map(|i| Tier { sol_width: (5 + i) * 1_000_000_000, token_alloc: 100_000_000 }).collect();
+    let args = bonding_curve::instruction::CreatePool { tiers, ..pool_args() };
+    let mut c = Curve::new(args).await;
+    let one = c.one;
+    let tier = 100_000_000 * one;
+
+    let view = c.curve_view().await;
+    assert!(view.phase == PoolPhase::Active);
+    assert_eq!((view.current_tier, view.tier_count, view.total_sold), (0, 8, 0));
+    assert_eq!(view.tier_progress_bps, 0);
+    assert_eq!(view.tokens_remaining, 8 * tier);
+    assert_eq!(view.tier_end_prices.len(), 8);
+    assert!(view.tier_end_prices.windows(2).all(|w| w[0] < w[1]));
+    let (start_price, start_to_graduate) = (view.spot_price_sol_per_token, view.lamports_to_graduate);
+
+    c.send(vec![c.buy_ix(8 * one)], &[]).await.unwrap();
+    let sold = c.state().await.total_sold;
+    let view = c.curve_view().await;
+    assert_eq!((view.current_tier, view.total_sold), (1, sold));
+    assert_eq!(view.tokens_remaining, 8 * tier - sold);
+    assert_eq!(view.tier_progress_bps as u128, (sold - tier) as u128 * 10_000 / tier as u128);
+    assert!(view.spot_price_sol_per_token > start_price);
+    assert!(view.lamports_to_graduate < start_to_graduate);
+}
+
+/// finalize_migration only graduates once the AMM pool it just seeded holds
+/// at least what start_migration snapshotted, and records that pool.
+#[tokio::test]
+async fn test_finalize_migration_verifies_the_amm_pool() {
+    let mut c = Curve::new(pool_args()).await;
+    let one = c.one;
+
+    c.send(vec![c.buy_ix(100 * one)], &[]).await.unwrap();
+    c.send(vec![c.start_migration_ix()], &[]).await.unwrap();
+    let started = c.state().await;
+
+    // Claim more tokens went into escrow than did: the seeded pool falls short.
+    let mut inflated = started.clone();
+    inflated.reserve_snapshot_token += 300_000_000 * one;
+    c.set_state(&inflated).await;
+    let (finalize, amm_pool) = c.finalize_migration_ix();
+    let err = c.send(vec![finalize.clone()], &[]).await.unwrap_err();
+    assert_eq!(custom_error(err), code(CustomError::AmmPoolUnderfunded));
+    assert!(c.state().await.amm_pool.is_none());
+
+    c.set_state(&started).await;
+    c.send(vec![finalize], &[]).await.unwrap();
+    let graduated = c.state().await;
+    assert!(graduated.phase == PoolPhase::Graduated);
+    assert_eq!(graduated.amm_pool, Some(amm_pool));
+}
+
+/// finalize leaves nothing in the migration escrow: its token and wSOL ATAs
+/// close and what the AMM didn't spend of its creation reserve goes to the
+/// creator.
+#[tokio::test]
+async fn test_finalize_sweeps_the_migration_escrow() {
+    use bonding_curve::consts::{AMM_CREATION_RESERVE_LAMPORTS, MIGRATION_GRACE_SLOTS};
+    let mut c = Curve::new(pool_args()).await;
+    let cranker = c.funded(1_000_000_000).await;
+    c.send(vec![c.buy_ix(100 * c.one)], &[]).await.unwrap();
+    c.send(vec![c.start_migration_ix()], &[]).await.unwrap();
+    c.warp(MIGRATION_GRACE_SLOTS + 1).await;
+    let started = c.state().await;
+    let creator = c.user.pubkey();
+    let before = c.lamports(creator).await;
+
+    // cranked, so the creator only pays its signature fee
+    let (finalize, amm_pool) = c.finalize_migration_ix_by(cranker.pubkey());
+    c.send(vec![finalize.clone()], &[&cranker]).await.unwrap();
+
+    let escrow_wsol = get_associated_token_address(&c.escrow, &native_mint::ID);
+    for key in [c.escrow, c.escrow_ata, escrow_wsol] {
+        assert!(c.ctx.banks_client.get_account(key).await.unwrap().is_none());
+    }
+
+    // the rent of the mock AMM's accounts is all the escrow spent of its reserve
+    let lp_mint = Pubkey::find_program_address(&[b"pool_lp_mint", amm_pool.as_ref()], &mock_amm::id()).0;
+    let observation = finalize.accounts[finalize.accounts.len() - 1].pubkey;
+    let ata_rent = Rent::default().minimum_balance(spl_token::state::Account::LEN);
+    let mut amm_rent = 2 * ata_rent; // the two vaults, wSOL balance aside
+    for key in [amm_pool, lp_mint, get_associated_token_address(&c.escrow, &lp_mint), observation] {
+        amm_rent += c.lamports(key).await;
+    }
+    let (_, creator_bonus) = started.graduation_cuts(started.reserve_snapshot_sol);
+    assert_eq!(
+        c.lamports(creator).await + 2 * 5_000,
+        before + creator_bonus + 2 * ata_rent + AMM_CREATION_RESERVE_LAMPORTS - amm_rent
+    );
+}
+
+/// A launch that misses its deadline stops trading; the first redeem flips it
+/// to Expired and every holder burns for a pro-rata share of the curve SOL.
+#[tokio::test]
+async fn test_missed_deadline_expires_into_pro_rata_redemption() {
+    // Genesis time is wall-clock time, so this is ten minutes after start.
+    let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs() as i64;
+    let args = bonding_curve::instruction::CreatePool { launch_deadline: Some(now + 600), ..pool_args() };
+    let mut c = Curve::new(args).await;
+    let one = c.one;
+
+    c.send(vec![c.buy_ix(10 * one)], &[]).await.unwrap();
+    let sold = c.state().await.total_sold;
+    let err = c.send(vec![c.redeem_ix(sold)], &[]).await.unwrap_err();
+    assert_eq!(custom_error(err), code(CustomError::LaunchNotExpired));
+
+    c.warp(2_000).await; // 800s
+    for ix in [c.buy_ix(one), c.sell_ix(sold)] {
+        let err = c.send(vec![ix], &[]).await.unwrap_err();
+        assert_eq!(custom_error(err), code(CustomError::LaunchExpired));
+    }
+
+    let before = c.state().await;
+    let user = c.user.pubkey();
+    let paid_before = c.lamports(user).await;
+    c.send(vec![c.redeem_ix(sold / 2)], &[]).await.unwrap();
+    let expired = c.state().await;
+    assert!(expired.phase == PoolPhase::Expired);
+    assert_eq!(expired.reserve_snapshot_token, sold);
+    assert_eq!(expired.reserve_snapshot_sol, before.reserve_sol);
+    let share = (expired.reserve_snapshot_sol as u128 * (sold / 2) as u128 / sold as u128) as u64;
+    assert_eq!(c.lamports(user).await, paid_before + share - 5_000); // less the tx fee
+    assert_eq!(c.tokens(c.user_ata).await, sold - sold / 2);
+
+    // The creator's seed comes straight back; the holders' SOL stays.
+    let stranger = c.funded(100_000_000).await;
+    let err = c.send(vec![c.reclaim_expired_pool_ix(stranger.pubkey())], &[&stranger]).await.unwrap_err();
+    assert_eq!(custom_error(err), code(CustomError::NotCreator));
+    let vault_before = c.lamports(c.vault).await;
+    c.send(vec![c.reclaim_expired_pool_ix(user)], &[]).await.unwrap();
+    assert_eq!(c.lamports(c.vault).await, vault_before - expired.seed_sol);
+    assert_eq!(c.state().await.seed_sol, 0);
+    let err = c.send(vec![c.reclaim_expired_pool_ix(user)], &[]).await.unwrap_err();
+    assert_eq!(custom_error(err), code(CustomError::InvalidAmount));
+
+    // The rest redeems against the same snapshot and empties the curve SOL.
+    c.send(vec![c.redeem_ix(sold - sold / 2)], &[]).await.unwrap();
+    let drained = c.state().await;
+    assert!(drained.reserve_sol <= 1);
+    assert_eq!(drained.tokens_redeemed, sold);
+    assert_eq!(c.tokens(c.user_ata).await, 0);
+
+    // Fully redeemed: the rent and the dust go to the creator, closing the vault.
+    c.send(vec![c.reclaim_expired_pool_ix(user)], &[]).await.unwrap();
+    assert_eq!(c.lamports(c.vault).await, 0);
+}
+
+/// A pool written before versioning (588 bytes, reserve_sol = the whole
+/// vault, none of the later fields) comes back at the current size and
+/// version with the missing fields rebuilt from its balances, and trades on.
+#[tokio::test]
+async fn test_upgrade_pool_account_backfills_a_pre_versioning_pool() {
+    const OLD_SIZE: usize = 221;
+    let mut c = Curve::new(pool_args()).await;
+    let one = c.one;
+    c.send(vec![c.buy_ix(15 * one)], &[]).await.unwrap();
+    let current = c.state().await;
+    let vault = c.lamports(c.vault).await;
+    // The creator is no longer the admin, so only the backfilled
+    // migration_authority lets them through
+    let admin = Keypair::new();
+    c.hand_admin_to(&admin).await;
+
+    // The original layout: reserve_sol counted the whole vault, and a pool
+    // from before the migration fields has no migration_authority
+    let mut old = Vec::with_capacity(OLD_SIZE);
+    old.extend_from_slice(LiquidityPool::DISCRIMINATOR);
+    old.extend_from_slice(current.creator.as_ref());
+    old.extend_from_slice(current.token.as_ref());
+    old.extend_from_slice(&current.total_supply.to_le_bytes());
+    old.extend_from_slice(&current.reserve_token.to_le_bytes());
+    old.extend_from_slice(&vault.to_le_bytes());
+    old.push(current.bump);
+    old.push(0); // phase: Active
+    old.push(0); // cap_reached_slot: None
+    old.push(0); // amm_pool: None
+    old.extend_from_slice(&[0; 32]); // migration_authority: unset
+    old.extend_from_slice(&[0; 16]); // reserve snapshots
+    old.push(0); // lp_timelock: None
+    old.resize(OLD_SIZE, 0);
+    let mut account = c.ctx.banks_client.get_account(c.pool).await.unwrap().unwrap();
+    account.data = old;
+    account.lamports = Rent::default().minimum_balance(OLD_SIZE);
+    c.ctx.set_account(&c.pool, &AccountSharedData::from(account));
+
+    c.send(vec![c.upgrade_pool_account_ix()], &[]).await.unwrap();
+    let account = c.ctx.banks_client.get_account(c.pool).await.unwrap().unwrap();
+    assert_eq!(account.data.len(), LiquidityPool::ACCOUNT_SIZE);
+    assert!(account.lamports >= Rent::default().minimum_balance(LiquidityPool::ACCOUNT_SIZE));
+    let upgraded = c.state().await;
+    assert_eq!(upgraded.version, LiquidityPool::VERSION);
+    assert_eq!(upgraded.migration_authority, current.creator);
+    assert!(upgraded.amm_kind == AmmKind::RaydiumCpmm);
+    assert_eq!(upgraded.amm_program, mock_amm::id());
+    assert_eq!(upgraded.vault_bump, current.vault_bump);
+    assert_eq!(upgraded.total_sold, current.total_sold);
+    assert!(upgraded.total_sol_in.abs_diff(current.total_sol_in) <= SOLVENCY_TOLERANCE_LAMPORTS);
+    assert_eq!(upgraded.reserve_sol, upgraded.total_sol_in);
+    assert_eq!(upgraded.seed_sol + upgraded.reserve_sol, current.seed_sol + current.reserve_sol);
+    assert_eq!(upgraded.bonus_paid_tiers, current.bonus_paid_tiers);
+    assert_eq!((upgraded.tokens_redeemed, upgraded.vesting_unclaimed), (0, 0));
+
+    // Idempotent, and the curve trades on with its books balanced.
+    c.send(vec![c.upgrade_pool_account_ix()], &[]).await.unwrap();
+    c.send(vec![c.sell_ix(10_000_000 * one)], &[]).await.unwrap();
+    c.send(vec![c.buy_ix(one)], &[]).await.unwrap();
+    assert!(c.check_invariants().await.ok);
+}
+
+/// The vault signs with the canonical bump stored at create_pool; the bump
+/// the legacy sell still takes is ignored, whatever the caller passes.
+#[tokio::test]
+async fn test_vault_signs_with_the_stored_canonical_bump() {
+    let mut c = Curve::new(pool_args()).await;
+    let one = c.one;
+    let (vault, canonical) = Pubkey::find_program_address(
+        &[LiquidityPool::SOL_VAULT_PREFIX.as_bytes(), c.mint.as_ref()],
+        &bonding_curve::id(),
+    );
+    assert_eq!(vault, c.vault);
+    assert_eq!(c.state().await.vault_bump, canonical);
+
+    c.send(vec![c.buy_ix(one)], &[]).await.unwrap();
+    let held = c.tokens(c.user_ata).await;
+    for bump in [canonical.wrapping_sub(1), 0] {
+        // The original sell account list, in its original order
+        let legacy = Instruction {
+            program_id: bonding_curve::id(),
+            accounts: vec![
+                AccountMeta::new(c.cfg, false),
+                AccountMeta::new(c.pool, false),
+                AccountMeta::new(c.mint, false),
+                AccountMeta::new(c.pool_ata, false),
+                AccountMeta::new(c.vault, false),
+                AccountMeta::new(c.user_ata, false),
+                AccountMeta::new(c.user.pubkey(), true),
+                AccountMeta::new_readonly(system_program::ID, false),
+                AccountMeta::new_readonly(spl_token::ID, false),
+                AccountMeta::new_readonly(anchor_spl::associated_token::ID, false),
+                AccountMeta::new_readonly(Rent::id(), false),
+            ],
+            data: bonding_curve::instruction::Sell { amount: held / 4, bump }.data(),
+        };
+        c.send(vec![legacy], &[]).await.unwrap();
+    }
+    assert_eq!(c.tokens(c.user_ata).await, held - 2 * (held / 4));
+    assert!(c.check_invariants().await.ok);
+}
+
+/// The deprecated remove_liquidity still takes the original account list in
+/// its original order, and still hands an unsold pool back to its creator.
+#[tokio::test]
+async fn test_legacy_remove_liquidity_takes_the_original_account_list() {
+    let mut c = Curve::new(pool_args()).await;
+    let pool_tokens = c.tokens(c.pool_ata).await;
+    let user_tokens = c.tokens(c.user_ata).await;
+    let legacy = Instruction {
+        program_id: bonding_curve::id(),
+        accounts: vec![
+            AccountMeta::new(c.pool, false),
+            AccountMeta::new(c.mint, false),
+            AccountMeta::new(c.pool_ata, false),
+            AccountMeta::new(c.user_ata, false),
+            AccountMeta::new(c.vault, false),
+            AccountMeta::new(c.user.pubkey(), true),
+            AccountMeta::new_readonly(Rent::id(), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(anchor_spl::associated_token::ID, false),
+        ],
+        data: bonding_curve::instruction::RemoveLiquidity { bump: 0 }.data(),
+    };
+    c.send(vec![legacy], &[]).await.unwrap();
+    assert_eq!(c.tokens(c.pool_ata).await, 0);
+    assert_eq!(c.tokens(c.user_ata).await, user_tokens + pool_tokens);
+    let pool = c.state().await;
+    assert_eq!((pool.reserve_token, pool.reserve_sol, pool.seed_sol), (0, 0, 0));
+}
+
+/// create_pool only takes schedules of 1..=MAX_TIERS non-empty tiers that
+/// allocate exactly the sold cap.
+#[tokio::test]
+async fn test_create_pool_rejects_bad_tier_schedules() {
+    let tier = |sol_width, token_alloc| Tier { sol_width, token_alloc };
+    let schedules = [
+        vec![tier(10_000_000_000, 100_000_000); 7],                     // 700M, short of the cap
+        vec![tier(10_000_000_000, 100_000_000); 9],                     // more than MAX_TIERS
+        vec![tier(0, 400_000_000), tier(10_000_000_000, 400_000_000)],  // zero width
+        vec![tier(10_000_000_000, 0), tier(10_000_000_000, 800_000_000)], // empty allocation
+    ];
+    let mut c = Curve::minted(MintSetup::default()).await;
+    for tiers in schedules {
+        let args = bonding_curve::instruction::CreatePool { tiers, ..pool_args() };
+        let err = c.send(c.create_pool_ixs(args), &[]).await.unwrap_err();
+        assert_eq!(custom_error(err), code(CustomError::InvalidTierSchedule));
+    }
+    // and the same mint takes a valid one
+    let args = bonding_curve::instruction::CreatePool { tiers: eight_tiers(), ..pool_args() };
+    c.send(c.create_pool_ixs(args), &[]).await.unwrap();
+}
+
+/// create_pool turns away any mint that isn't fixed-supply on the curve's
+/// terms, one check at a time.
+#[tokio::test]
+async fn test_create_pool_rejects_mints_validate_mint_refuses() {
+    let cases = [
+        (MintSetup { revoke_mint: false, ..MintSetup::default() }, CustomError::MintAuthorityNotRevoked),
+        (MintSetup { freeze_authority: true, ..MintSetup::default() }, CustomError::MintAuthorityNotRevoked),
+        (MintSetup { decimals: 6, ..MintSetup::default() }, CustomError::InvalidMintDecimals),
+        (MintSetup { pool_tokens: 799_999_999, ..MintSetup::default() }, CustomError::InvalidSupplyDistribution),
+        (MintSetup { treasury_tokens: 200_000_001, ..MintSetup::default() }, CustomError::InvalidSupplyDistribution),
+    ];
+    for (setup, expected) in cases {
+        let mut c = Curve::minted(setup).await;
+        let err = c.send(c.create_pool_ixs(pool_args()), &[]).await.unwrap_err();
+        assert_eq!(custom_error(err), code(expected));
+    }
+}
+
+/// Buy and sell still take the original eleven accounts, in the original
+/// order: the optional vesting / bonus accounts come after them and may be
+/// left out on pools that don't use them.
+#[tokio::test]
+async fn test_trades_accept_the_original_account_list() {
+    const ORIGINAL_ACCOUNTS: usize = 11;
+    let mut c = Curve::new(pool_args()).await;
+    let one = c.one;
+
+    let mut buy = c.buy_ix(one);
+    let mut sell = c.sell_ix(0);
+    assert_eq!(buy.accounts[ORIGINAL_ACCOUNTS - 1].pubkey, Rent::id());
+    assert_eq!(sell.accounts[ORIGINAL_ACCOUNTS - 1].pubkey, Rent::id());
+    buy.accounts.truncate(ORIGINAL_ACCOUNTS);
+    c.send(vec![buy], &[]).await.unwrap();
+
+    let held = c.tokens(c.user_ata).await;
+    sell.data = bonding_curve::instruction::SellV2 { amount: held / 2 }.data();
+    sell.accounts.truncate(ORIGINAL_ACCOUNTS);
+    c.send(vec![sell], &[]).await.unwrap();
+    assert_eq!(c.tokens(c.user_ata).await, held - held / 2);
+
+    // A vesting pool still needs the escrow, and says so.
+    let args = bonding_curve::instruction::CreatePool {
+        tiers: eight_tiers(),
+        vesting: Some(VestingConfig { vested_tiers: 1, duration: 1_000 }),
+        ..pool_args()
+    };
+    let mut c = Curve::new(args).await;
+    let mut buy = c.buy_ix(one);
+    buy.accounts.truncate(ORIGINAL_ACCOUNTS);
+    let err = c.send(vec![buy], &[]).await.unwrap_err();
+    assert_eq!(custom_error(err), code(CustomError::VestingAccountsMissing));
+}
+
+/// A locked LP stays in the program's escrow until lp_unlock_slot, and then
+/// only the recipient named at create_pool can take it out.
+#[tokio::test]
+async fn test_locked_lp_releases_to_the_recipient_after_the_lock() {
+    const LOCK_SLOTS: u64 = 1_000;
+    let recipient = Keypair::new();
+    let args = bonding_curve::instruction::CreatePool {
+        lp_lock: Some(LpLockConfig { lock_slots: LOCK_SLOTS, recipient: recipient.pubkey(), burn: false }),
+        ..pool_args()
+    };
+    let mut c = Curve::new(args).await;
+    let stranger = c.funded(1_000_000_000).await;
+    c.send(vec![system_instruction::transfer(&c.user.pubkey(), &recipient.pubkey(), 1_000_000_000)], &[])
+        .await
+        .unwrap();
+    let (_, lp_mint) = c.graduate().await;
+    let escrow_lp = get_associated_token_address(&c.lp_escrow(), &lp_mint);
+    let locked = c.tokens(escrow_lp).await;
+    let pool = c.state().await;
+    assert!(locked > 0);
+    assert_eq!(pool.lp_timelock, Some(escrow_lp));
+    assert_eq!(pool.lp_unlock_slot, pool.graduation_slot + LOCK_SLOTS);
+
+    let err = c.send(vec![c.release_lp_ix(lp_mint, recipient.pubkey())], &[&recipient]).await.unwrap_err();
+    assert_eq!(custom_error(err), code(CustomError::LpStillLocked));
+
+    c.warp(LOCK_SLOTS).await;
+    let err = c.send(vec![c.release_lp_ix(lp_mint, stranger.pubkey())], &[&stranger]).await.unwrap_err();
+    assert_eq!(custom_error(err), code(CustomError::Unauthorized));
+    c.send(vec![c.release_lp_ix(lp_mint, recipient.pubkey())], &[&recipient]).await.unwrap();
+    assert_eq!(c.tokens(get_associated_token_address(&recipient.pubkey(), &lp_mint)).await, locked);
+    assert_eq!(c.tokens(escrow_lp).await, 0);
+
+    let err = c.send(vec![c.release_lp_ix(lp_mint, recipient.pubkey())], &[&recipient]).await.unwrap_err();
+    assert_eq!(custom_error(err), code(CustomError::NothingToClaim));
+}
+
+/// With burn set, finalize burns every LP token it got and leaves nothing
+/// for release_lp.
+#[tokio::test]
+async fn test_burned_lp_leaves_nothing_to_release() {
+    let args = bonding_curve::instruction::CreatePool {
+        lp_lock: Some(LpLockConfig { lock_slots: 0, recipient: Pubkey::default(), burn: true }),
+        ..pool_args()
+    };
+    let mut c = Curve::new(args).await;
+    let (_, lp_mint) = c.graduate().await;
+    let pool = c.state().await;
+    assert!(pool.lp_burn);
+    assert_eq!(pool.lp_timelock, None);
+    assert_eq!(pool.lp_mint, Some(lp_mint));
+    assert_eq!(c.supply(lp_mint).await, 0);
+    assert!(c.ctx.banks_client.get_account(get_associated_token_address(&c.lp_escrow(), &lp_mint)).await.unwrap().is_none());
+
+    // (the escrow LP account was never created, so it fails on loading that)
+    let user = c.user.pubkey();
+    assert!(c.send(vec![c.release_lp_ix(lp_mint, user)], &[]).await.is_err());
+}
+
+/// Past the grace window anyone may drive the migration; a caller other than
+/// migration_authority gets the crank bounty out of the creator's seed in the
+/// vault.
+#[tokio::test]
+async fn test_anyone_may_crank_migration_after_the_grace_window() {
+    use bonding_curve::consts::{INITIAL_LAMPORTS_FOR_POOL, MIGRATION_CRANK_REWARD_LAMPORTS, MIGRATION_GRACE_SLOTS};
+    let mut c = Curve::new(pool_args()).await;
+    let cranker = c.funded(1_000_000_000).await;
+    c.send(vec![c.buy_ix(100 * c.one)], &[]).await.unwrap();
+
+    let err = c.send(vec![c.start_migration_ix_by(cranker.pubkey())], &[&cranker]).await.unwrap_err();
+    assert_eq!(custom_error(err), code(CustomError::Unauthorized));
+    c.warp(MIGRATION_GRACE_SLOTS + 1).await;
+    c.send(vec![c.start_migration_ix_by(cranker.pubkey())], &[&cranker]).await.unwrap();
+
+    let before = c.lamports(cranker.pubkey()).await;
+    let (finalize, _) = c.finalize_migration_ix_by(cranker.pubkey());
+    c.send(vec![finalize], &[&cranker]).await.unwrap();
+    // the cranker also paid rent for the escrow's wSOL and the LP escrow accounts
+    let ata_rent = Rent::default().minimum_balance(spl_token::state::Account::LEN);
+    assert_eq!(c.lamports(cranker.pubkey()).await + 2 * ata_rent, before + MIGRATION_CRANK_REWARD_LAMPORTS);
+    assert_eq!(MIGRATION_CRANK_REWARD_LAMPORTS, INITIAL_LAMPORTS_FOR_POOL); // the whole seed, here
+    assert_eq!(c.lamports(c.vault).await, Rent::default().minimum_balance(0));
+    let graduated = c.state().await;
+    assert!(graduated.phase == PoolPhase::Graduated);
+    assert_eq!(graduated.seed_sol, 0);
 }
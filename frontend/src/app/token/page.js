@@ -274,7 +274,7 @@ export default function TokenPage() {
   const lbDebounceRef = useRef(null);
 
   // --- Migration status / Raydium pool (NEW) ---
-  const [poolPhase, setPoolPhase] = useState(null); // "Migrating" | "RaydiumLive" | null
+  const [poolPhase, setPoolPhase] = useState(null); // "Migrating" | "Graduated" | null
   const [raydiumPool, setRaydiumPool] = useState(null); // pool id (string) once live
 
   // Devnet Raydium/explorer link builder (NEW)
@@ -712,7 +712,7 @@ export default function TokenPage() {
         const d = await r.json();
         if (cancelled || mintAtCall !== mint) return;
         if (d?.phase) setPoolPhase(d.phase);
-        const pid = d?.raydiumPool || d?.poolId || null;
+        const pid = d?.ammPool || d?.raydiumPool || d?.poolId || null;
         if (pid) setRaydiumPool(pid);
       } catch {/* ignore */}
     }
@@ -1240,7 +1240,7 @@ export default function TokenPage() {
 
   // --- Migration-aware UI flags ---
   const curveComplete = !!model && hasReserves && ySoldWhole >= CAP_TOKENS;
-  const migrationLive = !!raydiumPool || poolPhase === "RaydiumLive";
+  const migrationLive = !!raydiumPool || poolPhase === "Graduated";
   const migratingNow = poolPhase === "Migrating";
   const raydiumLinks = migrationLive
     ? raydiumDevnetLinks({ poolId: raydiumPool, mintStr: mint, sig: null })
@@ -1289,7 +1289,7 @@ export default function TokenPage() {
                   <div><b>TOKEN HAS GRADUATED</b></div>
                   {(poolPhase || raydiumPool) && (
                     <div style={{ marginTop: 4, color: "#555", fontSize: 13 }}>
-                      Phase: {poolPhase || "RaydiumLive"}
+                      Phase: {poolPhase || "Graduated"}
                     </div>
                   )}
                 </div>